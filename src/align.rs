@@ -0,0 +1,292 @@
+//! Alignment, distribution and size matching for a group of selected widgets.
+//!
+//! Widgets store positions relative to their [`DockArea`](crate::widget::DockArea)
+//! or container, so everything is computed in canvas space (origin + local position)
+//! and converted back before snapping to the grid.
+
+use crate::widget::Widget;
+use egui::{Pos2, Rect, Vec2};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AlignOp {
+    Left,
+    CenterH,
+    Right,
+    Top,
+    CenterV,
+    Bottom,
+    DistributeH,
+    DistributeV,
+    MatchWidth,
+    MatchHeight,
+}
+
+impl AlignOp {
+    pub(crate) const ALL: [AlignOp; 10] = [
+        AlignOp::Left,
+        AlignOp::CenterH,
+        AlignOp::Right,
+        AlignOp::Top,
+        AlignOp::CenterV,
+        AlignOp::Bottom,
+        AlignOp::DistributeH,
+        AlignOp::DistributeV,
+        AlignOp::MatchWidth,
+        AlignOp::MatchHeight,
+    ];
+
+    /// Short label for the toolbar
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            AlignOp::Left => "Left",
+            AlignOp::CenterH => "Center",
+            AlignOp::Right => "Right",
+            AlignOp::Top => "Top",
+            AlignOp::CenterV => "Middle",
+            AlignOp::Bottom => "Bottom",
+            AlignOp::DistributeH => "Distribute H",
+            AlignOp::DistributeV => "Distribute V",
+            AlignOp::MatchWidth => "Same width",
+            AlignOp::MatchHeight => "Same height",
+        }
+    }
+
+    /// Longer description for hover text and menus
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            AlignOp::Left => "Align left edges",
+            AlignOp::CenterH => "Align horizontal centers",
+            AlignOp::Right => "Align right edges",
+            AlignOp::Top => "Align top edges",
+            AlignOp::CenterV => "Align vertical centers",
+            AlignOp::Bottom => "Align bottom edges",
+            AlignOp::DistributeH => "Distribute horizontally with equal spacing",
+            AlignOp::DistributeV => "Distribute vertically with equal spacing",
+            AlignOp::MatchWidth => "Match the width of the widest widget",
+            AlignOp::MatchHeight => "Match the height of the tallest widget",
+        }
+    }
+
+    /// How many widgets must be selected for the operation to do anything
+    pub(crate) fn min_selection(self) -> usize {
+        match self {
+            AlignOp::DistributeH | AlignOp::DistributeV => 3,
+            _ => 2,
+        }
+    }
+}
+
+/// Apply `op` to `widgets`, `origin` gives the screen position each one's position is
+/// relative to
+pub(crate) fn apply(
+    op: AlignOp,
+    widgets: &mut [&mut Widget],
+    origin: impl Fn(&Widget) -> Pos2,
+    grid: f32,
+) {
+    if widgets.len() < op.min_selection() {
+        return;
+    }
+    let snap = |v: f32| (v / grid).round() * grid;
+    let rect = |w: &Widget| Rect::from_min_size(origin(w) + w.pos.to_vec2(), w.size);
+    let bounds = widgets
+        .iter()
+        .map(|w| rect(w))
+        .reduce(|a, b| a.union(b))
+        .unwrap_or(Rect::NOTHING);
+
+    match op {
+        AlignOp::Left => {
+            for w in widgets.iter_mut() {
+                w.pos.x = snap(bounds.left() - origin(w).x);
+            }
+        }
+        AlignOp::Right => {
+            for w in widgets.iter_mut() {
+                w.pos.x = snap(bounds.right() - origin(w).x) - w.size.x;
+            }
+        }
+        AlignOp::CenterH => {
+            for w in widgets.iter_mut() {
+                w.pos.x = snap(bounds.center().x - origin(w).x) - w.size.x * 0.5;
+            }
+        }
+        AlignOp::Top => {
+            for w in widgets.iter_mut() {
+                w.pos.y = snap(bounds.top() - origin(w).y);
+            }
+        }
+        AlignOp::Bottom => {
+            for w in widgets.iter_mut() {
+                w.pos.y = snap(bounds.bottom() - origin(w).y) - w.size.y;
+            }
+        }
+        AlignOp::CenterV => {
+            for w in widgets.iter_mut() {
+                w.pos.y = snap(bounds.center().y - origin(w).y) - w.size.y * 0.5;
+            }
+        }
+        AlignOp::DistributeH => {
+            widgets.sort_by(|a, b| rect(a).left().total_cmp(&rect(b).left()));
+            let used: f32 = widgets.iter().map(|w| w.size.x).sum();
+            let gap = (bounds.width() - used) / (widgets.len() - 1) as f32;
+            let mut x = bounds.left();
+            for w in widgets.iter_mut() {
+                w.pos.x = snap(x - origin(w).x);
+                x += w.size.x + gap;
+            }
+        }
+        AlignOp::DistributeV => {
+            widgets.sort_by(|a, b| rect(a).top().total_cmp(&rect(b).top()));
+            let used: f32 = widgets.iter().map(|w| w.size.y).sum();
+            let gap = (bounds.height() - used) / (widgets.len() - 1) as f32;
+            let mut y = bounds.top();
+            for w in widgets.iter_mut() {
+                w.pos.y = snap(y - origin(w).y);
+                y += w.size.y + gap;
+            }
+        }
+        AlignOp::MatchWidth => {
+            let width = widgets.iter().map(|w| w.size.x).fold(0.0, f32::max);
+            for w in widgets.iter_mut() {
+                w.size.x = width;
+            }
+        }
+        AlignOp::MatchHeight => {
+            let height = widgets.iter().map(|w| w.size.y).fold(0.0, f32::max);
+            for w in widgets.iter_mut() {
+                w.size.y = height;
+            }
+        }
+    }
+
+    // snapping may push a widget past its area origin, shift the whole group back so
+    // the widgets stay aligned and evenly spaced
+    let overshoot = widgets.iter().fold(Vec2::ZERO, |o, w| o.max(-w.pos.to_vec2()));
+    for w in widgets.iter_mut() {
+        w.pos += overshoot;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{DockArea, WidgetId, WidgetKind, WidgetProps};
+    use egui::{pos2, vec2};
+
+    fn widget(id: u64, pos: Pos2, size: Vec2) -> Widget {
+        Widget {
+            id: WidgetId::new(id),
+            kind: WidgetKind::Button,
+            pos,
+            size,
+            z: id as i32,
+            area: DockArea::Center,
+            parent: None,
+            props: WidgetProps::default(),
+            name: String::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Apply `op` to widgets at the canvas origin with a grid of 1, returning their rects
+    fn applied(op: AlignOp, widgets: &[(Pos2, Vec2)]) -> Vec<(Pos2, Vec2)> {
+        applied_with(op, widgets, |_| Pos2::ZERO, 1.0)
+    }
+
+    fn applied_with(
+        op: AlignOp,
+        widgets: &[(Pos2, Vec2)],
+        origin: impl Fn(&Widget) -> Pos2,
+        grid: f32,
+    ) -> Vec<(Pos2, Vec2)> {
+        let mut widgets: Vec<Widget> =
+            widgets.iter().zip(1..).map(|(&(pos, size), id)| widget(id, pos, size)).collect();
+        let mut refs: Vec<&mut Widget> = widgets.iter_mut().collect();
+        apply(op, &mut refs, origin, grid);
+        widgets.iter().map(|w| (w.pos, w.size)).collect()
+    }
+
+    #[test]
+    fn aligns_edges_and_centers() {
+        let widgets = [(pos2(10.0, 50.0), vec2(40.0, 20.0)), (pos2(30.0, 10.0), vec2(100.0, 60.0))];
+        let lefts = applied(AlignOp::Left, &widgets);
+        assert_eq!([lefts[0].0.x, lefts[1].0.x], [10.0, 10.0]);
+        let rights = applied(AlignOp::Right, &widgets);
+        assert_eq!([rights[0].0.x + 40.0, rights[1].0.x + 100.0], [130.0, 130.0]);
+        let centers = applied(AlignOp::CenterH, &widgets);
+        assert_eq!([centers[0].0.x, centers[1].0.x], [50.0, 20.0]);
+        let tops = applied(AlignOp::Top, &widgets);
+        assert_eq!([tops[0].0.y, tops[1].0.y], [10.0, 10.0]);
+        let bottoms = applied(AlignOp::Bottom, &widgets);
+        assert_eq!([bottoms[0].0.y, bottoms[1].0.y], [50.0, 10.0]);
+        let middles = applied(AlignOp::CenterV, &widgets);
+        assert_eq!([middles[0].0.y, middles[1].0.y], [30.0, 10.0]);
+    }
+
+    #[test]
+    fn distributes_with_equal_gaps() {
+        let widgets = [
+            (pos2(0.0, 0.0), vec2(20.0, 10.0)),
+            (pos2(200.0, 0.0), vec2(40.0, 10.0)),
+            (pos2(50.0, 0.0), vec2(20.0, 10.0)),
+        ];
+        let spread = applied(AlignOp::DistributeH, &widgets);
+        // 240 wide with 80 used leaves two gaps of 80
+        assert_eq!([spread[0].0.x, spread[2].0.x, spread[1].0.x], [0.0, 100.0, 200.0]);
+
+        let stacked: Vec<_> = widgets.iter().map(|&(p, s)| (pos2(p.y, p.x), vec2(s.y, s.x))).collect();
+        let spread = applied(AlignOp::DistributeV, &stacked);
+        assert_eq!([spread[0].0.y, spread[2].0.y, spread[1].0.y], [0.0, 100.0, 200.0]);
+    }
+
+    #[test]
+    fn distributing_needs_three_widgets() {
+        let widgets = [(pos2(0.0, 0.0), vec2(20.0, 10.0)), (pos2(200.0, 0.0), vec2(40.0, 10.0))];
+        assert_eq!(applied(AlignOp::DistributeH, &widgets), widgets);
+    }
+
+    #[test]
+    fn matches_the_largest_size() {
+        let widgets = [(pos2(0.0, 0.0), vec2(20.0, 30.0)), (pos2(50.0, 0.0), vec2(40.0, 10.0))];
+        let wide = applied(AlignOp::MatchWidth, &widgets);
+        assert_eq!([wide[0].1, wide[1].1], [vec2(40.0, 30.0), vec2(40.0, 10.0)]);
+        let tall = applied(AlignOp::MatchHeight, &widgets);
+        assert_eq!([tall[0].1, tall[1].1], [vec2(20.0, 30.0), vec2(40.0, 30.0)]);
+    }
+
+    #[test]
+    fn rounds_to_the_grid() {
+        let widgets = [(pos2(13.0, 0.0), vec2(20.0, 10.0)), (pos2(31.0, 20.0), vec2(20.0, 10.0))];
+        let lefts = applied_with(AlignOp::Left, &widgets, |_| Pos2::ZERO, 10.0);
+        assert_eq!([lefts[0].0.x, lefts[1].0.x], [10.0, 10.0]);
+    }
+
+    #[test]
+    fn aligns_across_origins_in_canvas_space() {
+        // the second widget is placed relative to a container at (100, 0)
+        let origin = |w: &Widget| if w.id == WidgetId::new(2) { pos2(100.0, 0.0) } else { Pos2::ZERO };
+        let widgets = [(pos2(150.0, 0.0), vec2(20.0, 10.0)), (pos2(10.0, 20.0), vec2(20.0, 10.0))];
+        let lefts = applied_with(AlignOp::Left, &widgets, origin, 1.0);
+        assert_eq!([lefts[0].0.x, lefts[1].0.x], [110.0, 10.0]);
+    }
+
+    #[test]
+    fn keeps_alignment_when_pushed_back_into_the_area() {
+        // the right edge at 104 rounds down to 100, which would put the wide widget at -4
+        let widgets = [(pos2(0.0, 0.0), vec2(104.0, 10.0)), (pos2(50.0, 20.0), vec2(20.0, 10.0))];
+        let rights = applied_with(AlignOp::Right, &widgets, |_| Pos2::ZERO, 10.0);
+        assert_eq!(rights[0].0.x, 0.0);
+        assert_eq!(rights[0].0.x + 104.0, rights[1].0.x + 20.0);
+
+        let widgets = [
+            (pos2(0.0, 0.0), vec2(10.0, 10.0)),
+            (pos2(2.0, 0.0), vec2(10.0, 10.0)),
+            (pos2(4.0, 0.0), vec2(10.0, 10.0)),
+        ];
+        let spread = applied_with(AlignOp::DistributeH, &widgets, |_| Pos2::ZERO, 10.0);
+        let xs: Vec<f32> = spread.iter().map(|(p, _)| p.x).collect();
+        assert!(xs.iter().all(|x| *x >= 0.0), "{xs:?}");
+        assert_eq!(xs[1] - xs[0], xs[2] - xs[1], "{xs:?}");
+    }
+}
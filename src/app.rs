@@ -1,25 +1,87 @@
 use crate::{
-    project::Project,
+    align::{self, AlignOp},
+    clipboard::{self, Clipboard},
+    codegen::{self, AssetMode, OutputMode, Visibility},
+    export,
+    import,
+    guides,
+    history::History,
+    preview::PreviewState,
+    project::{Asset, ImageFormat, Project},
     widget::{
-        self, DockArea, Widget, WidgetId, WidgetKind, WidgetProps, escape, snap_pos_with_grid,
+        ColumnAlign, ColumnType, ColumnWidth, DockArea, ImageFit, PlotSeries, SCATTER_RADIUS, SeriesStyle,
+        TABLE_HEADER_HEIGHT, TABLE_ROW_HEIGHT, TableColumn, Widget, WidgetId, WidgetKind, WidgetProps,
+        snap_pos_with_grid,
     },
 };
 use chrono::{Datelike, NaiveDate};
-use copypasta::ClipboardProvider;
-use egui::{pos2, vec2, Button, Color32, CornerRadius, Id, Modal, Pos2, Rect, Sense, Stroke, Ui, UiBuilder};
+use egui::{
+    pos2, vec2, Button, Color32, CornerRadius, Id, Key, KeyboardShortcut, Modal, Modifiers, Pos2, Rect,
+    Sense, Stroke, UiBuilder, Vec2,
+};
 use egui_extras::{syntax_highlighting::CodeTheme, DatePickerButton};
 use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
 fn bool_true() -> bool { true }
 
+/// Single line edit for a Rust identifier, flagged when the name isn't valid
+fn identifier_edit(ui: &mut egui::Ui, label: &str, name: &mut String) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let valid = codegen::is_identifier(name);
+        let mut edit = egui::TextEdit::singleline(name).desired_width(140.0);
+        if !valid {
+            edit = edit.text_color(ui.visuals().error_fg_color);
+        }
+        let response = ui.add(edit);
+        if !valid {
+            response.on_hover_text("Not a valid Rust identifier");
+        }
+    });
+}
+
+/// Spacing between the widgets of a layout, `None` for egui's default
+fn spacing_edit(ui: &mut egui::Ui, spacing: &mut Option<Vec2>) {
+    ui.horizontal(|ui| {
+        let mut custom = spacing.is_some();
+        ui.checkbox(&mut custom, "Spacing");
+        let mut value = spacing.unwrap_or(ui.spacing().item_spacing);
+        if custom {
+            ui.add(egui::DragValue::new(&mut value.x).range(0.0..=100.0).prefix("x: "));
+            ui.add(egui::DragValue::new(&mut value.y).range(0.0..=100.0).prefix("y: "));
+        }
+        *spacing = custom.then_some(value);
+    });
+}
+
+const UNDO_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+const REDO_SHORTCUT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::Z);
+const REDO_ALT_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Y);
+const NEW_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::N);
+const OPEN_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::O);
+const SAVE_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::S);
+const SAVE_AS_SHORTCUT: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::S);
+// copy/cut/paste arrive as `egui::Event`s, these are only shown in the Edit menu
+const CUT_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::X);
+const COPY_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::C);
+const PASTE_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::V);
+const PREVIEW_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::NONE, Key::F5);
+
 #[derive(Serialize, Deserialize)]
 pub(crate) struct RadBuilderApp {
     #[serde(skip, default = "bool_true")]
     /// Is the widget picker open
     palette_open: bool,
     project: Project,
+    /// Selected widgets
     #[serde(skip, default)]
-    selected: Option<WidgetId>,
+    selected: BTreeSet<WidgetId>,
     next_id: u64,
     /// Drag state for spawning from palette
     #[serde(skip, default)]
@@ -39,21 +101,126 @@ pub(crate) struct RadBuilderApp {
     #[serde(skip, default)]
     /// The open modal if any
     open_modal: Option<OpenModal>,
+    /// Undo/redo stack of project edits
+    #[serde(skip, default)]
+    history: History,
+    /// Move or resize of the selection in progress on the canvas
+    #[serde(skip, default)]
+    drag: Option<CanvasDrag>,
+    /// Start of a rubber-band selection on empty canvas space
+    #[serde(skip, default)]
+    marquee: Option<Pos2>,
+    /// Snap to neighbouring widgets while dragging
+    #[serde(default = "bool_true")]
+    smart_guides: bool,
+    /// Guide lines to draw this frame, in screen space
+    #[serde(skip, default)]
+    guides: Vec<[Pos2; 2]>,
+    /// Where the widgets were drawn this frame, in drawing order
+    #[serde(skip, default)]
+    drawn: Vec<Drawn>,
+    #[serde(skip, default)]
+    clipboard: Clipboard,
+    /// File the project was last opened from or saved to
+    #[serde(default)]
+    file_path: Option<PathBuf>,
+    /// Most recently used project files, newest first
+    #[serde(default)]
+    recent_files: Vec<PathBuf>,
+    /// The project as last saved, `None` until first checked against disk
+    #[serde(skip, default)]
+    saved_project: Option<Project>,
+    /// Window title last sent to the viewport
+    #[serde(skip, default)]
+    window_title: String,
+    /// Live widget values while the design runs in preview mode, `None` while editing
+    #[serde(skip, default)]
+    preview: Option<PreviewState>,
+}
+
+/// What the user did to a widget's editing chrome this frame
+#[derive(Default)]
+struct WidgetInteraction {
+    clicked: bool,
+    /// Set while the frame is dragged, with this frame's delta
+    moving: Option<Vec2>,
+    /// Set while the resize handle is dragged, with this frame's delta
+    resizing: Option<Vec2>,
+}
+
+/// Where a widget was drawn on screen
+struct Drawn {
+    id: WidgetId,
+    rect: Rect,
+    /// For containers, the screen position the widgets placed in them are relative to
+    content: Option<Pos2>,
+}
+
+/// What drawing the widgets of a panel needs besides the `Ui`
+struct DrawCx<'a> {
+    widgets: &'a mut [Widget],
+    assets: &'a [Asset],
+    selected: &'a BTreeSet<WidgetId>,
+    /// Live values to draw the widgets from while previewing
+    preview: Option<&'a mut PreviewState>,
+    interactions: &'a mut Vec<(WidgetId, WidgetInteraction)>,
+    drawn: &'a mut Vec<Drawn>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DragKind {
+    Move,
+    Resize,
+}
+
+/// A move or resize applied to every selected widget at once
+struct CanvasDrag {
+    kind: DragKind,
+    /// The widget whose frame or handle is being dragged
+    grabbed: WidgetId,
+    /// Accumulated pointer movement since the drag started
+    total: Vec2,
+    /// Position and size of every dragged widget when the drag started
+    start: Vec<(WidgetId, Pos2, Vec2)>,
 }
 
 pub(crate) enum OpenModal {
-    /// json import modal is open
-    Import,
+    /// json import modal is open, with the error of the last attempt
+    Import(Option<String>),
     /// json export modal is open
     Export,
+    /// ask before `PendingAction` throws away unsaved changes
+    ConfirmDiscard(PendingAction),
+    /// ask before exporting into a directory that already has files in it
+    ConfirmExport(PathBuf),
+    /// something went wrong, e.g. a file couldn't be read
+    Error(String),
+    /// something worked, but not entirely, e.g. an import that skipped code
+    Warning(String),
+}
+
+/// An action that replaces the current project
+#[derive(Clone)]
+pub(crate) enum PendingAction {
+    New,
+    /// open the given file, or ask for one
+    Open(Option<PathBuf>),
+    Clear,
+    /// ask for a generated `.rs` file and rebuild the design from it
+    ImportRust,
+    /// a design pasted as JSON
+    ImportJson(Box<Project>),
 }
 
+/// How many entries File → Open Recent keeps
+const MAX_RECENT_FILES: usize = 10;
+
 impl Default for RadBuilderApp {
     fn default() -> Self {
         Self {
             palette_open: true,
             project: Project::default(),
-            selected: None,
+            selected: BTreeSet::new(),
             next_id: 1,
             spawning: None,
             generated: String::new(),
@@ -67,6 +234,18 @@ impl Default for RadBuilderApp {
             live_center: None,
 
             open_modal: None,
+            history: History::default(),
+            drag: None,
+            marquee: None,
+            smart_guides: true,
+            guides: Vec::new(),
+            drawn: Vec::new(),
+            clipboard: Clipboard::default(),
+            file_path: None,
+            recent_files: Vec::new(),
+            saved_project: None,
+            window_title: String::new(),
+            preview: None,
         }
     }
 }
@@ -88,23 +267,62 @@ impl RadBuilderApp {
         }
     }
 
-    fn origin_for_area(&self, area: DockArea) -> Option<Pos2> {
+    fn area_rect(&self, area: DockArea) -> Option<Rect> {
         match area {
-            DockArea::Top => self.live_top.map(|r| r.min),
-            DockArea::Bottom => self.live_bottom.map(|r| r.min),
-            DockArea::Left => self.live_left.map(|r| r.min),
-            DockArea::Right => self.live_right.map(|r| r.min),
-            DockArea::Center => self.live_center.map(|r| r.min),
-            DockArea::Free => self.live_center.map(|r| r.min), // place Free inside center canvas
+            DockArea::Top => self.live_top,
+            DockArea::Bottom => self.live_bottom,
+            DockArea::Left => self.live_left,
+            DockArea::Right => self.live_right,
+            DockArea::Center => self.live_center,
+            DockArea::Free => self.live_center, // place Free inside center canvas
+        }
+    }
+
+    fn origin_for_area(&self, area: DockArea) -> Option<Pos2> {
+        self.area_rect(area).map(|r| r.min)
+    }
+
+    /// Screen position `w.pos` is relative to: the content of its container or its area
+    fn origin_of(&self, w: &Widget) -> Option<Pos2> {
+        match w.parent {
+            Some(parent) => self.drawn.iter().find(|d| d.id == parent)?.content,
+            None => self.origin_for_area(w.area),
+        }
+    }
+
+    /// The container of the topmost widget drawn under `pos`, ignoring `excluded`,
+    /// with the screen position of its content
+    fn container_at(&self, pos: Pos2, excluded: &BTreeSet<WidgetId>) -> Option<(&Widget, Pos2)> {
+        let hit = self.drawn.iter().rev().find(|d| d.rect.contains(pos) && !excluded.contains(&d.id))?;
+        let container = match hit.content {
+            Some(content) => Some((hit.id, content)),
+            None => {
+                let parent = self.project.widget(hit.id)?.parent?;
+                Some((parent, self.drawn.iter().find(|d| d.id == parent)?.content?))
+            }
+        };
+        container.and_then(|(id, content)| Some((self.project.widget(id)?, content)))
+    }
+
+    fn draw_cx<'a>(&'a mut self, interactions: &'a mut Vec<(WidgetId, WidgetInteraction)>) -> DrawCx<'a> {
+        DrawCx {
+            widgets: &mut self.project.widgets,
+            assets: &self.project.assets,
+            selected: &self.selected,
+            preview: self.preview.as_mut(),
+            interactions,
+            drawn: &mut self.drawn,
         }
     }
 
+    /// Add a `kind` widget centered on `at_global`, placed in `parent` when given
     fn spawn_widget(
         &mut self,
         kind: WidgetKind,
         at_global: Pos2,
         area: DockArea,
-        area_origin: Pos2,
+        parent: Option<WidgetId>,
+        origin: Pos2,
     ) {
         let id = WidgetId::new(self.next_id);
         self.next_id += 1;
@@ -141,6 +359,14 @@ impl RadBuilderApp {
                     ..Default::default()
                 },
             ),
+            WidgetKind::Image => (
+                vec2(160.0, 120.0),
+                WidgetProps {
+                    // the latest asset is most likely the one wanted
+                    image: self.project.assets.last().map(|a| a.name.clone()).unwrap_or_default(),
+                    ..Default::default()
+                },
+            ),
             WidgetKind::Checkbox => (
                 vec2(160.0, 28.0),
                 WidgetProps {
@@ -220,7 +446,7 @@ impl RadBuilderApp {
             }
             WidgetKind::Separator => (vec2(220.0, 8.0), WidgetProps::default()),
             WidgetKind::CollapsingHeader => (
-                vec2(260.0, 80.0),
+                vec2(260.0, 120.0),
                 WidgetProps {
                     text: "Section".into(),
                     checked: true, // default open
@@ -272,9 +498,37 @@ impl RadBuilderApp {
                 ];
                 (vec2(260.0, 200.0), p)
             }
+            WidgetKind::Plot => (
+                vec2(320.0, 200.0),
+                WidgetProps {
+                    series: vec![PlotSeries::sample("Series 1", SeriesStyle::Line)],
+                    ..Default::default()
+                },
+            ),
+            WidgetKind::Table => {
+                let column = |header: &str, ty| TableColumn { header: header.into(), ty, ..Default::default() };
+                let row = |cells: [&str; 3]| cells.map(String::from).to_vec();
+                (
+                    vec2(360.0, 160.0),
+                    WidgetProps {
+                        columns: vec![
+                            column("Name", ColumnType::Text),
+                            column("Quantity", ColumnType::Integer),
+                            TableColumn { width: ColumnWidth::Remainder, ..column("Price", ColumnType::Number) },
+                        ],
+                        rows: vec![row(["Apples", "3", "1.20"]), row(["Pears", "5", "0.80"]), row(["Plums", "12", "2.50"])],
+                        ..Default::default()
+                    },
+                )
+            }
+            WidgetKind::Frame | WidgetKind::ScrollArea => (vec2(240.0, 160.0), WidgetProps::default()),
+            WidgetKind::Horizontal => (vec2(320.0, 40.0), WidgetProps::default()),
+            WidgetKind::Vertical => (vec2(180.0, 160.0), WidgetProps::default()),
+            WidgetKind::Grid => (vec2(320.0, 120.0), WidgetProps { striped: false, ..Default::default() }),
+            WidgetKind::Strip => (vec2(320.0, 60.0), WidgetProps::default()),
         };
 
-        let vecpos = at_global - area_origin - size * 0.5; // local to area
+        let vecpos = at_global - origin - size * 0.5; // local to area or container
         let pos = self.snap_pos(pos2(vecpos.x, vecpos.y));
         let w = Widget {
             id,
@@ -283,16 +537,13 @@ impl RadBuilderApp {
             size,
             z: id.as_z(),
             area,
+            parent,
             props,
+            name: String::new(),
+            events: Vec::new(),
         };
         self.project.widgets.push(w);
-        self.selected = Some(id);
-    }
-
-    /// Self::selected_mut(self.selected, &mut self.project.widgets)
-    fn selected_mut(selected: Option<WidgetId>, widgets: &mut Vec<Widget>) -> Option<&mut Widget> {
-        let id = selected?;
-        widgets.iter_mut().find(|w| w.id == id)
+        self.selected = BTreeSet::from([id]);
     }
 
     fn preview_panels_ui(&mut self, ctx: &egui::Context) {
@@ -308,6 +559,10 @@ impl RadBuilderApp {
         self.live_right = None;
         self.live_center = None;
 
+        self.drawn.clear();
+        let mut interactions = Vec::new();
+        let editing = self.preview.is_none();
+
         // -------- 1) Bucket INDICES (not &mut) by area in a read-only pass --------
         let mut top_idx = Vec::new();
         let mut bottom_idx = Vec::new();
@@ -316,7 +571,9 @@ impl RadBuilderApp {
         let mut center_idx = Vec::new();
         let mut free_idx = Vec::new();
 
-        for (i, w) in self.project.widgets.iter().enumerate() {
+        // widgets in containers are drawn by their container
+        let placed = |w: &Widget| w.parent.and_then(|p| self.project.widget(p)).is_some_and(|c| c.kind.is_container());
+        for (i, w) in self.project.widgets.iter().enumerate().filter(|(_, w)| !placed(w)) {
             match w.area {
                 Top => top_idx.push(i),
                 Bottom => bottom_idx.push(i),
@@ -334,13 +591,10 @@ impl RadBuilderApp {
                 .show(ctx, |ui| {
                     let panel_rect = ui.clip_rect();
                     self.live_top = Some(panel_rect);
-                    if self.show_grid {
+                    if self.show_grid && editing {
                         self.draw_grid(ui, panel_rect);
                     }
-                    for &i in &top_idx {
-                        let w = &mut self.project.widgets[i];
-                        Self::draw_widget(ui, panel_rect, self.grid_size, &mut self.selected, w);
-                    }
+                    Self::draw_widgets(ui, Some(panel_rect.min), &top_idx, &mut self.draw_cx(&mut interactions));
                 });
         }
 
@@ -351,13 +605,10 @@ impl RadBuilderApp {
                 .show(ctx, |ui| {
                     let panel_rect = ui.clip_rect();
                     self.live_bottom = Some(panel_rect);
-                    if self.show_grid {
+                    if self.show_grid && editing {
                         self.draw_grid(ui, panel_rect);
                     }
-                    for &i in &bottom_idx {
-                        let w = &mut self.project.widgets[i];
-                        Self::draw_widget(ui, panel_rect, self.grid_size, &mut self.selected, w);
-                    }
+                    Self::draw_widgets(ui, Some(panel_rect.min), &bottom_idx, &mut self.draw_cx(&mut interactions));
                 });
         }
 
@@ -368,13 +619,10 @@ impl RadBuilderApp {
                 .show(ctx, |ui| {
                     let panel_rect = ui.clip_rect();
                     self.live_left = Some(panel_rect);
-                    if self.show_grid {
+                    if self.show_grid && editing {
                         self.draw_grid(ui, panel_rect);
                     }
-                    for &i in &left_idx {
-                        let w = &mut self.project.widgets[i];
-                        Self::draw_widget(ui, panel_rect, self.grid_size, &mut self.selected, w);
-                    }
+                    Self::draw_widgets(ui, Some(panel_rect.min), &left_idx, &mut self.draw_cx(&mut interactions));
                 });
         }
 
@@ -385,13 +633,10 @@ impl RadBuilderApp {
                 .show(ctx, |ui| {
                     let panel_rect = ui.clip_rect();
                     self.live_right = Some(panel_rect);
-                    if self.show_grid {
+                    if self.show_grid && editing {
                         self.draw_grid(ui, panel_rect);
                     }
-                    for &i in &right_idx {
-                        let w = &mut self.project.widgets[i];
-                        Self::draw_widget(ui, panel_rect, self.grid_size, &mut self.selected, w);
-                    }
+                    Self::draw_widgets(ui, Some(panel_rect.min), &right_idx, &mut self.draw_cx(&mut interactions));
                 });
         }
 
@@ -401,22 +646,23 @@ impl RadBuilderApp {
             let canvas = egui::Rect::from_min_size(ui.min_rect().min, self.project.canvas_size);
             self.live_center = Some(canvas);
 
-            let (resp, _) = ui.allocate_painter(canvas.size(), egui::Sense::hover());
+            let sense = if editing { egui::Sense::click_and_drag() } else { egui::Sense::hover() };
+            let (resp, _) = ui.allocate_painter(canvas.size(), sense);
             let painter_rect = egui::Rect::from_min_size(canvas.min, canvas.size());
 
-            if self.show_grid {
+            if self.show_grid && editing {
                 self.draw_grid(ui, painter_rect);
             }
 
             // Draw Center + Free widgets inside the center canvas
-            for &i in &center_idx {
-                let w = &mut self.project.widgets[i];
-                Self::draw_widget(ui, painter_rect, self.grid_size, &mut self.selected, w);
+            for idx in [&center_idx, &free_idx] {
+                Self::draw_widgets(ui, Some(painter_rect.min), idx, &mut self.draw_cx(&mut interactions));
             }
-            for &i in &free_idx {
-                let w = &mut self.project.widgets[i];
-                Self::draw_widget(ui, painter_rect, self.grid_size, &mut self.selected, w);
+
+            if !editing {
+                return;
             }
+            self.marquee_ui(ui, &resp, painter_rect);
 
             // --- Drag ghost + drop ---
             if let Some(kind) = self.spawning.clone() {
@@ -426,6 +672,7 @@ impl RadBuilderApp {
                         WidgetKind::Label => vec2(140.0, 24.0),
                         WidgetKind::Button => vec2(160.0, 32.0),
                         WidgetKind::ImageTextButton => vec2(200.0, 36.0),
+                        WidgetKind::Image => vec2(160.0, 120.0),
                         WidgetKind::Checkbox => vec2(160.0, 28.0),
                         WidgetKind::TextEdit => vec2(220.0, 36.0),
                         WidgetKind::Slider => vec2(220.0, 24.0),
@@ -436,11 +683,18 @@ impl RadBuilderApp {
                         WidgetKind::SelectableLabel => vec2(180.0, 24.0),
                         WidgetKind::ComboBox => vec2(220.0, 28.0),
                         WidgetKind::Separator => vec2(220.0, 8.0),
-                        WidgetKind::CollapsingHeader => vec2(260.0, 80.0),
+                        WidgetKind::CollapsingHeader => vec2(260.0, 120.0),
                         WidgetKind::DatePicker => vec2(200.0, 28.0),
                         WidgetKind::AngleSelector => vec2(220.0, 28.0),
                         WidgetKind::Password => vec2(220.0, 36.0),
                         WidgetKind::Tree => vec2(260.0, 200.0),
+                        WidgetKind::Plot => vec2(320.0, 200.0),
+                        WidgetKind::Table => vec2(360.0, 160.0),
+                        WidgetKind::Frame | WidgetKind::ScrollArea => vec2(240.0, 160.0),
+                        WidgetKind::Horizontal => vec2(320.0, 40.0),
+                        WidgetKind::Vertical => vec2(180.0, 160.0),
+                        WidgetKind::Grid => vec2(320.0, 120.0),
+                        WidgetKind::Strip => vec2(320.0, 60.0),
                     };
                     let ghost = egui::Rect::from_center_size(mouse, ghost_size);
                     let layer = egui::LayerId::new(egui::Order::Tooltip, Id::new("ghost"));
@@ -473,19 +727,427 @@ impl RadBuilderApp {
 
                 if ui.input(|i| i.pointer.any_released()) {
                     if let Some(pos) = ui.ctx().pointer_interact_pos() {
-                        let area = self.area_at(pos);
-                        if let Some(origin) = self.origin_for_area(area) {
-                            self.spawn_widget(kind, pos, area, origin);
+                        // dropped onto a container, or onto something placed in one
+                        let container = self.container_at(pos, &BTreeSet::new()).map(|(c, content)| (c.id, c.area, content));
+                        if let Some((container, area, content)) = container {
+                            self.spawn_widget(kind, pos, area, Some(container), content);
+                        } else {
+                            let area = self.area_at(pos);
+                            if let Some(origin) = self.origin_for_area(area) {
+                                self.spawn_widget(kind, pos, area, None, origin);
+                            }
                         }
                     }
                     self.spawning = None;
                 }
             }
 
-            if resp.clicked() {
-                self.selected = None;
+            if resp.clicked() && !ui.input(|i| i.modifiers.shift || i.modifiers.command) {
+                self.selected.clear();
+            }
+        });
+
+        self.apply_interactions(ctx, &interactions);
+        self.paint_guides(ctx);
+    }
+
+    /// Draw the active smart guides on top of the canvas
+    fn paint_guides(&self, ctx: &egui::Context) {
+        let layer = egui::LayerId::new(egui::Order::Foreground, Id::new("smart_guides"));
+        let painter = ctx.layer_painter(layer);
+        for &guide in &self.guides {
+            painter.line_segment(guide, Stroke::new(1.0, Color32::from_rgb(255, 80, 160)));
+        }
+    }
+
+    /// Rubber-band selection started on empty canvas space
+    fn marquee_ui(&mut self, ui: &mut egui::Ui, canvas_resp: &egui::Response, canvas: Rect) {
+        if canvas_resp.drag_started() {
+            self.marquee = ui.input(|i| i.pointer.press_origin());
+        }
+        let Some(start) = self.marquee else { return };
+        let Some(current) = ui.ctx().pointer_interact_pos() else { return };
+        let band = Rect::from_two_pos(start, current);
+
+        if canvas_resp.dragged() {
+            let painter = ui.painter_at(canvas);
+            painter.rect_filled(band, 0.0, Color32::from_rgba_unmultiplied(100, 160, 255, 24));
+            painter.rect_stroke(
+                band,
+                CornerRadius::ZERO,
+                Stroke::new(1.0, Color32::LIGHT_BLUE),
+                egui::StrokeKind::Inside,
+            );
+        }
+
+        if canvas_resp.drag_stopped() {
+            if !ui.input(|i| i.modifiers.shift || i.modifiers.command) {
+                self.selected.clear();
+            }
+            let hits = self.drawn.iter().filter(|d| {
+                band.intersects(d.rect)
+                    && self.project.widget(d.id).is_some_and(|w| matches!(w.area, DockArea::Center | DockArea::Free))
+            });
+            self.selected.extend(hits.map(|d| d.id));
+            self.marquee = None;
+        }
+    }
+
+    /// Draw the widgets at `indices` relative to `origin`, or one after the other when
+    /// `None`, and collect how the user interacted with them
+    fn draw_widgets(ui: &mut egui::Ui, origin: Option<Pos2>, indices: &[usize], cx: &mut DrawCx) {
+        for &i in indices {
+            let w = cx.widgets[i].clone();
+            if let Some(preview) = cx.preview.as_deref_mut() {
+                let mut props = preview.props(&w).clone();
+                Self::draw_widget(ui, origin, &w, &mut props, cx);
+                if let Some(preview) = cx.preview.as_deref_mut() {
+                    *preview.props(&w) = props;
+                }
+                continue;
+            }
+            let mut props = w.props.clone();
+            let interaction = Self::draw_widget(ui, origin, &w, &mut props, cx);
+            cx.widgets[i].props = props;
+            cx.interactions.push((w.id, interaction));
+        }
+    }
+
+    /// Draw the widgets placed in container `w` into its content `ui`, `drawn` is the
+    /// index of its entry in [`DrawCx::drawn`]
+    fn draw_contents(ui: &mut egui::Ui, w: &Widget, drawn: usize, cx: &mut DrawCx) {
+        let origin = ui.min_rect().min;
+        cx.drawn[drawn].content = Some(origin);
+        let children = Self::children(w, cx);
+        if w.kind == WidgetKind::Grid {
+            for row in children.chunks(w.props.grid_columns.max(1)) {
+                Self::draw_widgets(ui, None, row, cx);
+                ui.end_row();
+            }
+        } else {
+            Self::draw_widgets(ui, (!w.kind.flows()).then_some(origin), &children, cx);
+        }
+    }
+
+    /// Indices of the widgets placed in container `w`, in drawing order
+    fn children(w: &Widget, cx: &DrawCx) -> Vec<usize> {
+        (0..cx.widgets.len()).filter(|&i| cx.widgets[i].parent == Some(w.id)).collect()
+    }
+
+    /// Apply clicks, moves and resizes collected while drawing the canvas
+    fn apply_interactions(&mut self, ctx: &egui::Context, interactions: &[(WidgetId, WidgetInteraction)]) {
+        let modifiers = ctx.input(|i| i.modifiers);
+        for (id, interaction) in interactions {
+            if interaction.clicked {
+                if modifiers.shift || modifiers.command {
+                    if !self.selected.remove(id) {
+                        self.selected.insert(*id);
+                    }
+                } else {
+                    self.selected = BTreeSet::from([*id]);
+                }
             }
+        }
+
+        let moving = interactions
+            .iter()
+            .find_map(|(id, i)| i.moving.map(|d| (*id, d, DragKind::Move)));
+        let resizing = interactions
+            .iter()
+            .find_map(|(id, i)| i.resizing.map(|d| (*id, d, DragKind::Resize)));
+        let Some((grabbed, delta, kind)) = moving.or(resizing) else {
+            if let Some(drag) = self.drag.take()
+                && drag.kind == DragKind::Move
+                && let Some(pointer) = ctx.pointer_interact_pos()
+            {
+                self.drop_moved(&drag, pointer);
+            }
+            self.guides.clear();
+            return;
+        };
+
+        if !self.selected.contains(&grabbed) {
+            self.selected = BTreeSet::from([grabbed]);
+        }
+        if self.drag.as_ref().is_none_or(|d| d.kind != kind || d.grabbed != grabbed) {
+            // what is placed in a selected container goes along with it
+            let in_selected = |w: &Widget| {
+                w.parent.is_some_and(|p| self.selected.iter().any(|&s| self.project.is_within(p, s)))
+            };
+            let start = self
+                .project
+                .widgets
+                .iter()
+                .filter(|w| self.selected.contains(&w.id) && !in_selected(w))
+                .map(|w| (w.id, w.pos, w.size))
+                .collect();
+            self.drag = Some(CanvasDrag { kind, grabbed, total: Vec2::ZERO, start });
+        }
+        let Some(drag) = &mut self.drag else { return };
+        drag.total += delta;
+
+        // holding Alt suspends all snapping
+        let snapping = !modifiers.alt;
+        match drag.kind {
+            DragKind::Move => self.move_dragged(snapping),
+            DragKind::Resize => self.resize_dragged(snapping),
+        }
+        if kind == DragKind::Move
+            && let Some(pointer) = ctx.pointer_interact_pos()
+        {
+            self.mark_layout_slot(pointer);
+        }
+    }
+
+    /// Mark where the dragged widgets would go in the layout under `pointer`
+    fn mark_layout_slot(&mut self, pointer: Pos2) {
+        let Some(drag) = &self.drag else { return };
+        let dragged: BTreeSet<WidgetId> = drag.start.iter().map(|(id, ..)| *id).collect();
+        let excluded = self.project.with_contents(&dragged);
+        let marker = self
+            .container_at(pointer, &excluded)
+            .filter(|(c, _)| c.kind.flows())
+            .and_then(|(c, _)| self.layout_slot(c, pointer, &excluded).1);
+        self.guides.extend(marker);
+    }
+
+    /// Where in layout `container` widgets dropped at `pointer` go: their index among the
+    /// widgets it holds other than `excluded`, and a line marking the spot on screen
+    fn layout_slot(
+        &self,
+        container: &Widget,
+        pointer: Pos2,
+        excluded: &BTreeSet<WidgetId>,
+    ) -> (usize, Option<[Pos2; 2]>) {
+        let rects: Vec<Option<Rect>> = self
+            .project
+            .children(container.id)
+            .filter(|w| !excluded.contains(&w.id))
+            .map(|w| self.drawn.iter().find(|d| d.id == w.id).map(|d| d.rect))
+            .collect();
+        let stacked = match container.kind {
+            WidgetKind::Vertical => true,
+            WidgetKind::Strip => container.props.vertical,
+            _ => false,
+        };
+        let before = |r: &Rect| match container.kind {
+            // rows first
+            WidgetKind::Grid => r.max.y < pointer.y || (r.min.y <= pointer.y && r.center().x < pointer.x),
+            _ if stacked => r.center().y < pointer.y,
+            _ => r.center().x < pointer.x,
+        };
+        let index = rects.iter().take_while(|r| r.is_none_or(|r| before(&r))).count();
+        // the leading edge of the widget they go before, or the trailing one of the last
+        let marker = match rects.get(index).copied().flatten() {
+            Some(r) if stacked => Some([r.left_top(), r.right_top()]),
+            Some(r) => Some([r.left_top(), r.left_bottom()]),
+            None => rects.iter().rev().flatten().next().map(|r| {
+                if stacked { [r.left_bottom(), r.right_bottom()] } else { [r.right_top(), r.right_bottom()] }
+            }),
+        };
+        (index, marker)
+    }
+
+    /// Put `moved` at `index` among the other widgets in layout `container`, which are
+    /// laid out in the order of their z
+    fn reorder(&mut self, container: WidgetId, moved: &[WidgetId], index: usize) {
+        let mut order: Vec<WidgetId> =
+            self.project.children(container).map(|w| w.id).filter(|id| !moved.contains(id)).collect();
+        let index = index.min(order.len());
+        order.splice(index..index, moved.iter().copied());
+        let base = order.iter().filter_map(|&id| self.project.widget(id)).map(|w| w.z).min().unwrap_or(0);
+        for (z, id) in (base..).zip(order) {
+            if let Some(w) = self.project.widgets.iter_mut().find(|w| w.id == id) {
+                w.z = z;
+            }
+        }
+        // the generated code follows the order of the project
+        self.project.widgets.sort_by_key(|w| w.z);
+    }
+
+    /// Move the widgets of a finished move drag into the container under `pointer`, or
+    /// out of the one they were in, keeping them where they are on screen; in layouts
+    /// they go to the spot under `pointer`
+    fn drop_moved(&mut self, drag: &CanvasDrag, pointer: Pos2) {
+        let dragged: BTreeSet<WidgetId> = drag.start.iter().map(|(id, ..)| *id).collect();
+        let excluded = self.project.with_contents(&dragged);
+        let target = self.container_at(pointer, &excluded).map(|(c, content)| {
+            let slot = c.kind.flows().then(|| self.layout_slot(c, pointer, &excluded).0);
+            (c.id, c.area, content, slot)
         });
+        let mut z = self.project.widgets.iter().map(|w| w.z).max().unwrap_or(0);
+        for &id in &dragged {
+            let Some(w) = self.project.widget(id) else { continue };
+            let (parent, area) = match target {
+                Some((container, area, ..)) => (Some(container), area),
+                None => (None, w.area),
+            };
+            if parent == w.parent {
+                continue;
+            }
+            let origin = match target {
+                Some((_, _, content, _)) => Some(content),
+                None => self.origin_for_area(area),
+            };
+            let Some(origin) = origin else { continue };
+            let Some(rect) = self.drawn.iter().find(|d| d.id == id).map(|d| d.rect) else { continue };
+
+            let contents = self.project.with_contents(&BTreeSet::from([id]));
+            z += 1;
+            for w in self.project.widgets.iter_mut().filter(|w| contents.contains(&w.id)) {
+                w.area = area;
+                if w.id == id {
+                    w.parent = parent;
+                    w.pos = pos2(rect.min.x - origin.x, rect.min.y - origin.y);
+                    // on top of what is already there
+                    w.z = z;
+                }
+            }
+        }
+        if let Some((container, .., Some(index))) = target {
+            // dragged widgets inside other dragged ones keep their place in those
+            let mut moved: Vec<&Widget> = dragged
+                .iter()
+                .filter_map(|&id| self.project.widget(id))
+                .filter(|w| w.parent == Some(container))
+                .collect();
+            moved.sort_by_key(|w| w.z);
+            let moved: Vec<WidgetId> = moved.iter().map(|w| w.id).collect();
+            self.reorder(container, &moved, index);
+        }
+    }
+
+    /// Whether `w` is placed by a layout rather than by its position
+    fn in_layout(&self, w: &Widget) -> bool {
+        w.parent.and_then(|p| self.project.widget(p)).is_some_and(|c| c.kind.flows())
+    }
+
+    /// Whether `a` and `b` are placed next to each other, in the same container or on
+    /// the same surface
+    fn siblings(a: &Widget, b: &Widget) -> bool {
+        a.parent == b.parent && a.area.shares_surface(b.area)
+    }
+
+    /// Rects of the siblings of `grabbed` that aren't being dragged
+    fn guide_targets(&self, grabbed: &Widget, drag: &CanvasDrag) -> Vec<Rect> {
+        self.project
+            .widgets
+            .iter()
+            .filter(|w| Self::siblings(w, grabbed) && !drag.start.iter().any(|(id, ..)| *id == w.id))
+            .map(|w| Rect::from_min_size(w.pos, w.size))
+            .collect()
+    }
+
+    /// Guide lines in screen space from ones local to the surface of `w`
+    fn screen_guides(&self, w: &Widget, guides: Vec<[Pos2; 2]>) -> Vec<[Pos2; 2]> {
+        let origin = self.origin_of(w).unwrap_or(Pos2::ZERO).to_vec2();
+        guides.into_iter().map(|[a, b]| [a + origin, b + origin]).collect()
+    }
+
+    /// Offset the dragged widgets together, snapping the grabbed one to the grid or to guides
+    fn move_dragged(&mut self, snapping: bool) {
+        self.guides.clear();
+        let Some(drag) = &self.drag else { return };
+        let Some(&(_, anchor, _)) = drag.start.iter().find(|(id, ..)| *id == drag.grabbed) else {
+            return;
+        };
+        let mut offset = if snapping {
+            snap_pos_with_grid(anchor + drag.total, self.grid_size) - anchor
+        } else {
+            drag.total
+        };
+
+        if let Some(grabbed) = self.project.widget(drag.grabbed)
+            && snapping
+            && self.smart_guides
+            && !self.in_layout(grabbed)
+        {
+            // snap the part of the group placed next to the grabbed widget
+            let group = self
+                .project
+                .widgets
+                .iter()
+                .filter(|w| Self::siblings(w, grabbed))
+                .filter_map(|w| drag.start.iter().find(|(id, ..)| *id == w.id))
+                .map(|&(_, pos, size)| Rect::from_min_size(pos + drag.total, size))
+                .reduce(|a, b| a.union(b));
+            if let Some(group) = group {
+                let snap = guides::snap(group, &self.guide_targets(grabbed, drag), guides::Edges::All);
+                if snap.snapped_x {
+                    offset.x = drag.total.x + snap.delta.x;
+                }
+                if snap.snapped_y {
+                    offset.y = drag.total.y + snap.delta.y;
+                }
+                self.guides = self.screen_guides(grabbed, snap.guides);
+            }
+        }
+
+        // keep the top level of the group inside the areas it lives in, what is in a
+        // container can be dragged out of it
+        for w in self.project.widgets.iter().filter(|w| w.parent.is_none()) {
+            let Some(&(_, pos, size)) = drag.start.iter().find(|(id, ..)| *id == w.id) else {
+                continue;
+            };
+            let Some(area) = self.area_rect(w.area) else { continue };
+            let max = (area.size() - size).max(Vec2::ZERO);
+            offset.x = offset.x.clamp(-pos.x, (max.x - pos.x).max(-pos.x));
+            offset.y = offset.y.clamp(-pos.y, (max.y - pos.y).max(-pos.y));
+        }
+
+        for w in &mut self.project.widgets {
+            if let Some(&(_, pos, _)) = drag.start.iter().find(|(id, ..)| *id == w.id) {
+                w.pos = pos + offset;
+            }
+        }
+    }
+
+    /// Scale the bounding box of the dragged widgets placed next to the grabbed one,
+    /// keeping their relative layout; positions elsewhere aren't comparable to theirs
+    fn resize_dragged(&mut self, snapping: bool) {
+        self.guides.clear();
+        let Some(drag) = &self.drag else { return };
+        let Some(grabbed) = self.project.widget(drag.grabbed) else { return };
+        let group: Vec<(WidgetId, Pos2, Vec2)> = self
+            .project
+            .widgets
+            .iter()
+            .filter(|w| Self::siblings(w, grabbed))
+            .filter_map(|w| drag.start.iter().find(|(id, ..)| *id == w.id).copied())
+            .collect();
+        let bounds = group
+            .iter()
+            .map(|&(_, pos, size)| Rect::from_min_size(pos, size))
+            .reduce(|a, b| a.union(b));
+        let Some(bounds) = bounds else { return };
+        let mut new_size = bounds.size() + drag.total;
+
+        if snapping && self.smart_guides && !self.in_layout(grabbed) {
+            let resized = Rect::from_min_size(bounds.min, new_size);
+            let snap = guides::snap(resized, &self.guide_targets(grabbed, drag), guides::Edges::MaxOnly);
+            new_size += snap.delta;
+            self.guides = self.screen_guides(grabbed, snap.guides);
+        }
+        let new_size = new_size.max(vec2(20.0, 16.0));
+        let scale = new_size / bounds.size();
+
+        let resized: Vec<(WidgetId, Pos2, Vec2)> = self
+            .project
+            .widgets
+            .iter()
+            .filter_map(|w| {
+                let &(_, pos, size) = group.iter().find(|(id, ..)| *id == w.id)?;
+                let limit = self.area_rect(w.area).map_or(Vec2::INFINITY, |r| r.size());
+                let size = (size * scale).max(vec2(20.0, 16.0)).min(limit);
+                Some((w.id, bounds.min + (pos - bounds.min) * scale, size))
+            })
+            .collect();
+        for (id, pos, size) in resized {
+            if let Some(w) = self.project.widgets.iter_mut().find(|w| w.id == id) {
+                w.pos = pos;
+                w.size = size;
+            }
+        }
     }
 
     fn draw_grid(&self, ui: &mut egui::Ui, rect: Rect) {
@@ -509,24 +1171,42 @@ impl RadBuilderApp {
         }
     }
 
+    /// Draw `w` driven by `props` at `origin` + its position, or in the next spot of a
+    /// layout when `None`, with the chrome to select, move and resize it unless previewing
     fn draw_widget(
         ui: &mut egui::Ui,
-        canvas_rect: Rect,
-        grid: f32,
-        selected: &mut Option<WidgetId>,
-        w: &mut Widget,
-    ) {
-        let rect = Rect::from_min_size(canvas_rect.min + w.pos.to_vec2(), w.size);
-        ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
+        origin: Option<Pos2>,
+        w: &Widget,
+        props: &mut WidgetProps,
+        cx: &mut DrawCx,
+    ) -> WidgetInteraction {
+        let mut interaction = WidgetInteraction::default();
+        let editing = cx.preview.is_none();
+        let selected = cx.selected.contains(&w.id);
+        let drawn = cx.drawn.len();
+        // in layouts the rect is only known once drawn, the entry goes first to keep the drawing order
+        cx.drawn.push(Drawn { id: w.id, rect: Rect::NOTHING, content: None });
+        // the image of image widgets and buttons, styled like the generated code does it
+        let image = cx
+            .assets
+            .iter()
+            .find(|a| w.uses_image() && a.name == props.image)
+            .map(|a| props.style_image(egui::Image::new(format!("file://{}", a.path.display())), w.image_size()));
+        let body = |ui: &mut egui::Ui| {
+            if w.kind.flows()
+                && let Some(spacing) = props.spacing
+            {
+                ui.spacing_mut().item_spacing = spacing;
+            }
             match w.kind {
                 WidgetKind::MenuButton => {
-                    let items = if w.props.items.is_empty() {
+                    let items = if props.items.is_empty() {
                         vec!["Item".into()]
                     } else {
-                        w.props.items.clone()
+                        props.items.clone()
                     };
-                    let mut sel = w.props.selected.min(items.len() - 1);
-                    ui.menu_button(&w.props.text, |ui| {
+                    let mut sel = props.selected.min(items.len() - 1);
+                    ui.menu_button(&props.text, |ui| {
                         for (i, it) in items.iter().enumerate() {
                             if ui.button(it).clicked() {
                                 sel = i;
@@ -534,79 +1214,93 @@ impl RadBuilderApp {
                             }
                         }
                     });
-                    w.props.selected = sel;
+                    props.selected = sel;
                 }
                 WidgetKind::Label => {
                     ui.vertical_centered(|ui| {
-                        ui.label(&w.props.text);
+                        ui.label(&props.text);
                     });
                 }
                 WidgetKind::Button => {
-                    ui.add_sized(w.size, egui::Button::new(&w.props.text));
-                }
-                WidgetKind::ImageTextButton => {
-                    // We keep it simple: icon + text as the button label.
-                    // Users can change `icon` to any emoji / short string.
-                    let label = format!("{}  {}", w.props.icon, w.props.text);
-                    ui.add_sized(w.size, egui::Button::new(label));
+                    ui.add_sized(w.size, egui::Button::new(&props.text));
                 }
+                WidgetKind::ImageTextButton => match image {
+                    Some(image) => {
+                        ui.add_sized(w.size, egui::Button::image_and_text(image, &props.text));
+                    }
+                    None => {
+                        // without an image asset the icon (any emoji / short string) stands in
+                        let label = format!("{}  {}", props.icon, props.text);
+                        ui.add_sized(w.size, egui::Button::new(label));
+                    }
+                },
+                WidgetKind::Image => match image {
+                    Some(image) => {
+                        ui.add_sized(w.size, image);
+                    }
+                    None => {
+                        ui.centered_and_justified(|ui| ui.weak("🖼 choose an image in the Inspector"));
+                    }
+                },
                 WidgetKind::Checkbox => {
-                    let mut checked = w.props.checked;
-                    ui.add_sized(w.size, egui::Checkbox::new(&mut checked, &w.props.text));
-                    w.props.checked = checked;
+                    let mut checked = props.checked;
+                    ui.add_sized(w.size, egui::Checkbox::new(&mut checked, &props.text));
+                    props.checked = checked;
                 }
                 WidgetKind::TextEdit => {
-                    let mut buf = w.props.text.clone();
-                    let resp = egui::TextEdit::singleline(&mut buf).hint_text("text");
+                    let mut buf = props.text.clone();
+                    // the generated code hints with the text it starts out with
+                    let hint = if editing { "text" } else { w.props.text.as_str() };
+                    let resp = egui::TextEdit::singleline(&mut buf).hint_text(hint);
                     ui.add_sized(w.size, resp);
-                    w.props.text = buf;
+                    props.text = buf;
                 }
                 WidgetKind::Slider => {
-                    let mut v = w.props.value;
+                    let mut v = props.value;
                     let slider =
-                        egui::Slider::new(&mut v, w.props.min..=w.props.max).text(&w.props.text);
+                        egui::Slider::new(&mut v, props.min..=props.max).text(&props.text);
                     ui.add_sized(w.size, slider);
-                    w.props.value = v;
+                    props.value = v;
                 }
                 WidgetKind::ProgressBar => {
                     let bar =
-                        egui::ProgressBar::new(w.props.value.clamp(0.0, 1.0)).show_percentage();
+                        egui::ProgressBar::new(props.value.clamp(0.0, 1.0)).show_percentage();
                     ui.add_sized(w.size, bar);
                 }
                 WidgetKind::RadioGroup => {
-                    let mut sel = w.props.selected.min(w.props.items.len().saturating_sub(1));
+                    let mut sel = props.selected.min(props.items.len().saturating_sub(1));
                     ui.vertical(|ui| {
-                        for (i, it) in w.props.items.iter().enumerate() {
+                        for (i, it) in props.items.iter().enumerate() {
                             if ui.add(egui::RadioButton::new(sel == i, it)).clicked() {
                                 sel = i;
                             }
                         }
                     });
-                    w.props.selected = sel;
+                    props.selected = sel;
                 }
                 WidgetKind::Link => {
-                    let _ = ui.link(&w.props.text);
+                    let _ = ui.link(&props.text);
                 }
                 WidgetKind::Hyperlink => {
-                    ui.hyperlink_to(&w.props.text, &w.props.url);
+                    ui.hyperlink_to(&props.text, &props.url);
                 }
                 WidgetKind::SelectableLabel => {
-                    let mut on = w.props.checked;
+                    let mut on = props.checked;
                     if ui
-                        .add(egui::Button::selectable(on, &w.props.text))
+                        .add(egui::Button::selectable(on, &props.text))
                         .clicked()
                     {
                         on = !on;
                     }
-                    w.props.checked = on;
+                    props.checked = on;
                 }
                 WidgetKind::ComboBox => {
-                    let items = if w.props.items.is_empty() {
+                    let items = if props.items.is_empty() {
                         vec!["Item".into()]
                     } else {
-                        w.props.items.clone()
+                        props.items.clone()
                     };
-                    let mut sel = w.props.selected.min(items.len() - 1);
+                    let mut sel = props.selected.min(items.len() - 1);
                     egui::ComboBox::from_id_salt(w.id)
                         .width(w.size.x)
                         .selected_text(items[sel].clone())
@@ -615,49 +1309,93 @@ impl RadBuilderApp {
                                 ui.selectable_value(&mut sel, i, it.clone());
                             }
                         });
-                    w.props.selected = sel;
+                    props.selected = sel;
                 }
                 WidgetKind::Separator => {
                     ui.separator();
                 }
                 WidgetKind::CollapsingHeader => {
-                    egui::CollapsingHeader::new(&w.props.text)
-                        .default_open(w.props.checked)
-                        .show(ui, |ui| {
-                            ui.label("… place your inner content here …");
-                        });
+                    egui::CollapsingHeader::new(&props.text)
+                        .id_salt(("collapsing", w.id))
+                        .default_open(props.checked)
+                        .show(ui, |ui| Self::draw_contents(ui, w, drawn, cx));
+                }
+                WidgetKind::Frame => {
+                    egui::Frame::group(ui.style()).show(ui, |ui| {
+                        ui.set_min_size(ui.available_size());
+                        Self::draw_contents(ui, w, drawn, cx);
+                    });
+                }
+                WidgetKind::ScrollArea => {
+                    // while editing, dragging is for moving widgets
+                    egui::ScrollArea::both()
+                        .id_salt(("scroll", w.id))
+                        .auto_shrink(false)
+                        .scroll_source(egui::scroll_area::ScrollSource { drag: !editing, ..egui::scroll_area::ScrollSource::ALL })
+                        .show(ui, |ui| Self::draw_contents(ui, w, drawn, cx));
+                }
+                WidgetKind::Horizontal | WidgetKind::Vertical => {
+                    let contents = |ui: &mut egui::Ui| Self::draw_contents(ui, w, drawn, cx);
+                    match (w.kind == WidgetKind::Horizontal, props.align) {
+                        (true, Some(align)) => ui.with_layout(egui::Layout::left_to_right(align), contents),
+                        (false, Some(align)) => ui.with_layout(egui::Layout::top_down(align), contents),
+                        (true, None) => ui.horizontal(contents),
+                        (false, None) => ui.vertical(contents),
+                    };
+                }
+                WidgetKind::Grid => {
+                    egui::Grid::new(("grid", w.id))
+                        .num_columns(props.grid_columns.max(1))
+                        .striped(props.striped)
+                        .show(ui, |ui| Self::draw_contents(ui, w, drawn, cx));
+                }
+                WidgetKind::Strip => {
+                    cx.drawn[drawn].content = Some(ui.min_rect().min);
+                    let children = Self::children(w, cx);
+                    let strip = egui_extras::StripBuilder::new(ui)
+                        .sizes(egui_extras::Size::remainder(), children.len());
+                    let cells = |mut strip: egui_extras::Strip| {
+                        for &child in &children {
+                            strip.cell(|ui| Self::draw_widgets(ui, None, &[child], cx));
+                        }
+                    };
+                    if props.vertical {
+                        strip.vertical(cells);
+                    } else {
+                        strip.horizontal(cells);
+                    }
                 }
                 WidgetKind::DatePicker => {
                     let mut date = NaiveDate::from_ymd_opt(
-                        w.props.year,
-                        w.props.month.clamp(1, 12),
-                        w.props.day.clamp(1, 28), // simple clamp
+                        props.year,
+                        props.month.clamp(1, 12),
+                        props.day.clamp(1, 28), // simple clamp
                     )
                     .unwrap_or_else(|| NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
                     ui.horizontal(|ui| {
-                        ui.label(&w.props.text);
+                        ui.label(&props.text);
                         ui.add(DatePickerButton::new(&mut date));
                     });
-                    w.props.year = date.year();
-                    w.props.month = date.month();
-                    w.props.day = date.day();
+                    props.year = date.year();
+                    props.month = date.month();
+                    props.day = date.day();
                 }
                 WidgetKind::AngleSelector => {
                     // Angle editor as slider in degrees
-                    let mut v = w.props.value.clamp(w.props.min, w.props.max);
-                    let slider = egui::Slider::new(&mut v, w.props.min..=w.props.max)
+                    let mut v = props.value.clamp(props.min, props.max);
+                    let slider = egui::Slider::new(&mut v, props.min..=props.max)
                         .suffix("°")
-                        .text(&w.props.text);
+                        .text(&props.text);
                     ui.add_sized(w.size, slider);
-                    w.props.value = v;
+                    props.value = v;
                 }
                 WidgetKind::Password => {
-                    let mut buf = w.props.text.clone();
+                    let mut buf = props.text.clone();
                     let resp = egui::TextEdit::singleline(&mut buf)
                         .password(true)
                         .hint_text("password");
                     ui.add_sized(w.size, resp);
-                    w.props.text = buf;
+                    props.text = buf;
                 }
                 WidgetKind::Tree => {
                     // Parse items (two leading spaces per level) into nodes:
@@ -717,10 +1455,10 @@ impl RadBuilderApp {
                         }
                     }
 
-                    let lines = if w.props.items.is_empty() {
+                    let lines = if props.items.is_empty() {
                         vec!["Root".into(), "  Child".into()]
                     } else {
-                        w.props.items.clone()
+                        props.items.clone()
                     };
                     let nodes = parse_nodes(&lines);
 
@@ -733,14 +1471,112 @@ impl RadBuilderApp {
                             });
                     });
                 }
+                WidgetKind::Plot => {
+                    // while editing, dragging is for moving the widget and the view stays put
+                    let mut plot = egui_plot::Plot::new(("plot", w.id))
+                        .width(w.size.x)
+                        .height(w.size.y)
+                        .show_grid(props.grid)
+                        .allow_zoom(props.zoom && !editing)
+                        .allow_boxed_zoom(props.zoom && !editing)
+                        .allow_drag(props.drag && !editing)
+                        .allow_scroll(props.drag && !editing);
+                    if !props.x_label.is_empty() {
+                        plot = plot.x_axis_label(&props.x_label);
+                    }
+                    if !props.y_label.is_empty() {
+                        plot = plot.y_axis_label(&props.y_label);
+                    }
+                    if props.legend {
+                        plot = plot.legend(egui_plot::Legend::default());
+                    }
+                    plot.show(ui, |plot_ui| {
+                        for series in &props.series {
+                            let points = series.points.clone();
+                            match series.style {
+                                SeriesStyle::Line => plot_ui.line(egui_plot::Line::new(&series.name, points)),
+                                SeriesStyle::Bar => {
+                                    let bars = points.iter().map(|&[x, y]| egui_plot::Bar::new(x, y)).collect();
+                                    plot_ui.bar_chart(egui_plot::BarChart::new(&series.name, bars));
+                                }
+                                SeriesStyle::Scatter => plot_ui
+                                    .points(egui_plot::Points::new(&series.name, points).radius(SCATTER_RADIUS)),
+                            }
+                        }
+                    });
+                }
+                WidgetKind::Table => {
+                    let sticky = if props.sticky_header { TABLE_HEADER_HEIGHT } else { 0.0 };
+                    let mut table = egui_extras::TableBuilder::new(ui)
+                        .id_salt(("table", w.id))
+                        .striped(props.striped)
+                        .auto_shrink(false)
+                        .max_scroll_height((w.size.y - sticky).max(0.0));
+                    for column in &props.columns {
+                        let width = match column.width {
+                            ColumnWidth::Auto => egui_extras::Column::auto(),
+                            ColumnWidth::Initial(width) => egui_extras::Column::initial(width),
+                            ColumnWidth::Exact(width) => egui_extras::Column::exact(width),
+                            ColumnWidth::Remainder => egui_extras::Column::remainder(),
+                        };
+                        // while editing, dragging is for moving the widget
+                        table = table.column(width.resizable(column.resizable && !editing));
+                    }
+                    let cell = |ui: &mut egui::Ui, align: ColumnAlign, add: &dyn Fn(&mut egui::Ui)| match align.layout() {
+                        Some(layout) => {
+                            ui.with_layout(layout, add);
+                        }
+                        None => add(ui),
+                    };
+                    let header = |mut header: egui_extras::TableRow| {
+                        for column in &props.columns {
+                            header.col(|ui| cell(ui, column.align, &|ui| {
+                                ui.strong(&column.header);
+                            }));
+                        }
+                    };
+                    let rows = |body: egui_extras::TableBody| {
+                        body.rows(TABLE_ROW_HEIGHT, props.rows.len(), |mut row| {
+                            let cells = &props.rows[row.index()];
+                            for (i, column) in props.columns.iter().enumerate() {
+                                let text = cells.get(i).map_or("", String::as_str);
+                                let text = match column.ty {
+                                    ColumnType::Bool if text.trim() == "true" => "✔",
+                                    ColumnType::Bool => "",
+                                    _ => text,
+                                };
+                                row.col(|ui| cell(ui, column.align, &|ui| {
+                                    ui.label(text);
+                                }));
+                            }
+                        });
+                    };
+                    if props.sticky_header {
+                        table.header(TABLE_HEADER_HEIGHT, header).body(rows);
+                    } else {
+                        table.body(|mut body| {
+                            body.row(TABLE_HEADER_HEIGHT, header);
+                            rows(body);
+                        });
+                    }
+                }
             }
-        });
-        let is_edit_mode = ui
-            .ctx()
-            .data(|d| d.get_temp::<bool>(Id::new("edit_mode")))
-            .unwrap_or(true);
+        };
+        let rect = match origin {
+            Some(origin) => {
+                let rect = Rect::from_min_size(origin + w.pos.to_vec2(), w.size);
+                ui.scope_builder(UiBuilder::new().max_rect(rect), body);
+                rect
+            }
+            // the next spot of a layout, as much of it as the widget uses
+            None => ui.allocate_ui(w.size, body).response.rect,
+        };
+        cx.drawn[drawn].rect = rect;
+        if !editing {
+            return interaction;
+        }
         let painter = ui.painter();
-        let stroke = if *selected == Some(w.id) {
+        let stroke = if selected {
             Stroke::new(2.0, Color32::LIGHT_BLUE)
         } else {
             Stroke::new(1.0, Color32::from_gray(90))
@@ -751,66 +1587,48 @@ impl RadBuilderApp {
             stroke,
             egui::StrokeKind::Outside,
         );
-        if is_edit_mode {
-            let pad = 6.0;
-            let expanded = rect.expand(pad);
-            let top = Rect::from_min_max(expanded.min, pos2(expanded.max.x, rect.min.y));
-            let bottom = Rect::from_min_max(pos2(expanded.min.x, rect.max.y), expanded.max);
-            let left = Rect::from_min_max(
-                pos2(expanded.min.x, rect.min.y),
-                pos2(rect.min.x, rect.max.y),
-            );
-            let right = Rect::from_min_max(
-                pos2(rect.max.x, rect.min.y),
-                pos2(expanded.max.x, rect.max.y),
-            );
+        let pad = 6.0;
+        let expanded = rect.expand(pad);
+        let top = Rect::from_min_max(expanded.min, pos2(expanded.max.x, rect.min.y));
+        let bottom = Rect::from_min_max(pos2(expanded.min.x, rect.max.y), expanded.max);
+        let left = Rect::from_min_max(
+            pos2(expanded.min.x, rect.min.y),
+            pos2(rect.min.x, rect.max.y),
+        );
+        let right = Rect::from_min_max(
+            pos2(rect.max.x, rect.min.y),
+            pos2(expanded.max.x, rect.max.y),
+        );
 
-            let mut any_clicked = false;
-            let mut drag_delta = egui::Vec2::ZERO;
-            for (i, edge) in [top, right, bottom, left].into_iter().enumerate() {
-                let id = ui.make_persistent_id(("edge", w.id, i as u8));
-                let resp = ui.interact(edge, id, Sense::click_and_drag());
-                if resp.hovered() {
-                    ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
-                }
-                if resp.clicked() {
-                    any_clicked = true;
-                }
-                if resp.dragged() {
-                    drag_delta += resp.drag_delta();
-                }
+        for (i, edge) in [top, right, bottom, left].into_iter().enumerate() {
+            let id = ui.make_persistent_id(("edge", w.id, i as u8));
+            let resp = ui.interact(edge, id, Sense::click_and_drag());
+            if resp.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
             }
-            if any_clicked {
-                *selected = Some(w.id);
+            if resp.clicked() {
+                interaction.clicked = true;
             }
-            if drag_delta != egui::Vec2::ZERO {
-                w.pos += drag_delta;
-                w.pos = snap_pos_with_grid(w.pos, grid);
-                let maxx = (canvas_rect.width() - w.size.x).max(0.0);
-                let maxy = (canvas_rect.height() - w.size.y).max(0.0);
-                w.pos.x = w.pos.x.clamp(0.0, maxx);
-                w.pos.y = w.pos.y.clamp(0.0, maxy);
+            if resp.dragged() {
+                *interaction.moving.get_or_insert(Vec2::ZERO) += resp.drag_delta();
             }
+        }
 
-            // resize handle unchanged, plus clamp
-            let handle = {
-                let hs = 12.0;
-                Rect::from_min_size(expanded.max - vec2(hs, hs), vec2(hs, hs))
-            };
-            let rid = ui.make_persistent_id(("resize", w.id));
-            let rresp = ui.interact(handle, rid, Sense::click_and_drag());
-            if rresp.hovered() {
-                ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeNwSe);
-            }
-            if rresp.dragged() {
-                let delta = rresp.drag_delta();
-                w.size += delta;
-                w.size.x = w.size.x.max(20.0).min(canvas_rect.width());
-                w.size.y = w.size.y.max(16.0).min(canvas_rect.height());
-            }
-            ui.painter()
-                .rect_filled(handle, 2.0, Color32::from_rgb(100, 160, 255));
+        let handle = {
+            let hs = 12.0;
+            Rect::from_min_size(expanded.max - vec2(hs, hs), vec2(hs, hs))
+        };
+        let rid = ui.make_persistent_id(("resize", w.id));
+        let rresp = ui.interact(handle, rid, Sense::click_and_drag());
+        if rresp.hovered() {
+            ui.ctx().set_cursor_icon(egui::CursorIcon::ResizeNwSe);
         }
+        if rresp.dragged() {
+            interaction.resizing = Some(rresp.drag_delta());
+        }
+        ui.painter()
+            .rect_filled(handle, 2.0, Color32::from_rgb(100, 160, 255));
+        interaction
     }
 
     fn snap_pos(&self, p: Pos2) -> Pos2 {
@@ -830,6 +1648,7 @@ impl RadBuilderApp {
         self.palette_item(ui, "Label", WidgetKind::Label);
         self.palette_item(ui, "Button", WidgetKind::Button);
         self.palette_item(ui, "Image + Text Button", WidgetKind::ImageTextButton);
+        self.palette_item(ui, "Image", WidgetKind::Image);
         self.palette_item(ui, "Checkbox", WidgetKind::Checkbox);
         self.palette_item(ui, "TextEdit", WidgetKind::TextEdit);
         self.palette_item(ui, "Slider", WidgetKind::Slider);
@@ -845,11 +1664,22 @@ impl RadBuilderApp {
         self.palette_item(ui, "Angle Selector", WidgetKind::AngleSelector);
         self.palette_item(ui, "Password", WidgetKind::Password);
         self.palette_item(ui, "Tree", WidgetKind::Tree);
+        self.palette_item(ui, "Plot", WidgetKind::Plot);
+        self.palette_item(ui, "Table", WidgetKind::Table);
+        self.palette_item(ui, "Frame", WidgetKind::Frame);
+        self.palette_item(ui, "Scroll Area", WidgetKind::ScrollArea);
+        self.palette_item(ui, "Horizontal Layout", WidgetKind::Horizontal);
+        self.palette_item(ui, "Vertical Layout", WidgetKind::Vertical);
+        self.palette_item(ui, "Grid Layout", WidgetKind::Grid);
+        self.palette_item(ui, "Strip", WidgetKind::Strip);
+
+        ui.separator();
+        self.assets_ui(ui);
 
         ui.separator();
         ui.label("Tips:");
         ui.small(
-            "• Click frame around control to select it\n• Drag to move, drag the corner to resize\n• Snap-to-grid can be changed in Settings",
+            "• Click frame around control to select it\n• Shift/Ctrl-click or drag on empty canvas to select several\n• Drag to move, drag the corner to resize\n• Drop onto a frame, scroll area, collapsing header or layout to place it inside\n• Drag within a layout to reorder\n• Snap-to-grid and smart guides can be changed in Settings\n• Hold Alt while dragging to place freely",
         );
     }
 
@@ -860,47 +1690,164 @@ impl RadBuilderApp {
         }
     }
 
+    /// The image files of the project, with buttons to add and remove them
+    fn assets_ui(&mut self, ui: &mut egui::Ui) {
+        ui.label("Assets");
+        let mut remove = None;
+        for (i, asset) in self.project.assets.iter().enumerate() {
+            let users = self
+                .project
+                .widgets
+                .iter()
+                .filter(|w| w.uses_image() && w.props.image == asset.name)
+                .count();
+            ui.horizontal(|ui| {
+                if ui.small_button("🗑").on_hover_text("Remove from the project").clicked() {
+                    remove = Some(i);
+                }
+                let mut name = egui::RichText::new(&asset.name);
+                if !asset.path.is_file() {
+                    name = name.color(ui.visuals().error_fg_color);
+                }
+                ui.label(name)
+                    .on_hover_text(format!("{}\nused by {users} widget(s)", asset.path.display()));
+            });
+        }
+        if let Some(i) = remove {
+            self.project.assets.remove(i);
+        }
+        if ui.button("Add Image…").on_hover_text("PNG, JPEG or SVG").clicked() {
+            self.add_asset();
+        }
+    }
+
+    /// Ask for an image file and add it to the assets, returning the name to use it by
+    fn add_asset(&mut self) -> Option<String> {
+        let path = rfd::FileDialog::new()
+            .set_title("Add Image")
+            .add_filter("Images", &ImageFormat::EXTENSIONS)
+            .pick_file()?;
+        if let Some(asset) = self.project.assets.iter().find(|a| a.path == path) {
+            return Some(asset.name.clone());
+        }
+        let file_name = path.file_name()?.to_string_lossy().into_owned();
+        if let Some(problem) = Asset::name_problem(&file_name) {
+            self.open_modal = Some(OpenModal::Error(format!("Could not add {}:\n{problem}", path.display())));
+            return None;
+        }
+        // names are file names in the generated crate, keep them unique: logo.png, logo-2.png, …
+        let (stem, ext) = file_name.rsplit_once('.').unwrap_or((&file_name, ""));
+        let mut name = file_name.clone();
+        for n in 2.. {
+            if self.project.asset(&name).is_none() {
+                break;
+            }
+            name = format!("{stem}-{n}.{ext}");
+        }
+        self.project.assets.push(Asset { name: name.clone(), path });
+        Some(name)
+    }
+
     fn inspector_ui(&mut self, ui: &mut egui::Ui) {
         let grid = self.grid_size; // read before mutably borrowing self
-        let mut duplicate = None;
+        let canvas_size = self.project.canvas_size;
+        let mut duplicate = false;
+        let mut delete = false;
+        let mut add_image = false;
 
         ui.heading("Inspector");
         ui.separator();
-        if let Some(w) = Self::selected_mut(self.selected, &mut self.project.widgets) {
+        let selection: Vec<&Widget> = self
+            .project
+            .widgets
+            .iter()
+            .filter(|w| self.selected.contains(&w.id))
+            .collect();
+        let Some(&first) = selection.first() else {
+            ui.weak("No selection");
+            return;
+        };
+        let count = selection.len();
+        let same_kind = selection
+            .iter()
+            .all(|s| std::mem::discriminant(&s.kind) == std::mem::discriminant(&first.kind));
+        let has_text = selection.iter().all(|s| s.kind.has_text());
+
+        // edit a copy of the first selected widget, then apply what changed to all of them
+        let before = first.clone();
+        let mut edited = before.clone();
+        let w = &mut edited;
+        if count == 1 {
             ui.label(format!("ID: {:?}", w.id));
-            ui.add_space(6.0);
-            match w.kind {
-                WidgetKind::Label
-                // todo add click type(eg. click, release) and onclick function
-                | WidgetKind::Button
-                | WidgetKind::ImageTextButton
-                | WidgetKind::TextEdit
-                | WidgetKind::Checkbox
-                | WidgetKind::Slider
-                | WidgetKind::Link
-                | WidgetKind::Hyperlink
-                | WidgetKind::SelectableLabel
-                | WidgetKind::CollapsingHeader
-                | WidgetKind::Password
-                | WidgetKind::AngleSelector
-                | WidgetKind::DatePicker => {
-                    ui.label("Text");
-                    ui.text_edit_singleline(&mut w.props.text);
-                }
-                WidgetKind::ProgressBar
-                | WidgetKind::RadioGroup
-                | WidgetKind::ComboBox
-                | WidgetKind::Tree
-                | WidgetKind::Separator => {}
-                WidgetKind::MenuButton => {
-                    ui.label("Text");
-                    ui.text_edit_singleline(&mut w.props.text);
+            ui.add_space(4.0);
+            ui.label("Name");
+            let problem = codegen::name_problem(&self.project, w);
+            // shown while the name is blank, which is when the field name falls back to it
+            let fallback = w.state_field().unwrap_or_default();
+            let mut edit = egui::TextEdit::singleline(&mut w.name).hint_text(fallback);
+            if problem.is_some() {
+                edit = edit.text_color(ui.visuals().error_fg_color);
+            }
+            let response = ui.add(edit);
+            match problem {
+                Some(problem) => {
+                    response.on_hover_text(problem);
+                }
+                None => {
+                    response.on_hover_text("Name of the generated state field, blank for the default");
                 }
             }
+        } else {
+            ui.label(format!("{count} widgets selected"));
+        }
+        ui.add_space(6.0);
+        if has_text {
+            ui.label("Text");
+            ui.text_edit_singleline(&mut w.props.text);
+        }
+        if same_kind {
             match w.kind {
-                WidgetKind::ImageTextButton => {
-                    ui.label("Icon / Emoji");
-                    ui.text_edit_singleline(&mut w.props.icon);
+                WidgetKind::ImageTextButton | WidgetKind::Image => {
+                    let button = w.kind == WidgetKind::ImageTextButton;
+                    if button && w.props.image.is_empty() {
+                        ui.label("Icon / Emoji");
+                        ui.text_edit_singleline(&mut w.props.icon);
+                    }
+                    ui.label("Image");
+                    ui.horizontal(|ui| {
+                        let none = if button { "(icon instead)" } else { "(none)" };
+                        let selected = if w.props.image.is_empty() { none } else { w.props.image.as_str() };
+                        egui::ComboBox::from_id_salt(("image", w.id))
+                            .selected_text(selected.to_owned())
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut w.props.image, String::new(), none);
+                                for asset in &self.project.assets {
+                                    ui.selectable_value(&mut w.props.image, asset.name.clone(), &asset.name);
+                                }
+                            });
+                        add_image = ui.button("Add…").on_hover_text("Add an image file to the assets").clicked();
+                    });
+                    if w.uses_image() {
+                        ui.horizontal(|ui| {
+                            ui.label("Fit");
+                            egui::ComboBox::from_id_salt(("fit", w.id))
+                                .selected_text(w.props.fit.label())
+                                .show_ui(ui, |ui| {
+                                    for fit in ImageFit::ALL {
+                                        ui.selectable_value(&mut w.props.fit, fit, fit.label());
+                                    }
+                                });
+                        });
+                        if w.props.fit == ImageFit::Original {
+                            ui.add(egui::Slider::new(&mut w.props.scale, 0.1..=4.0).text("scale"));
+                        }
+                        ui.horizontal(|ui| {
+                            ui.label("Tint");
+                            ui.color_edit_button_srgba(&mut w.props.tint);
+                            ui.label("Rounding");
+                            ui.add(egui::DragValue::new(&mut w.props.rounding));
+                        });
+                    }
                 }
                 WidgetKind::Checkbox => {
                     ui.checkbox(&mut w.props.checked, "checked");
@@ -955,6 +1902,44 @@ impl RadBuilderApp {
                 WidgetKind::CollapsingHeader => {
                     ui.checkbox(&mut w.props.checked, "open by default");
                 }
+                WidgetKind::Horizontal | WidgetKind::Vertical => {
+                    // across the direction of the layout
+                    let names = if w.kind == WidgetKind::Horizontal {
+                        ["top", "center", "bottom"]
+                    } else {
+                        ["left", "center", "right"]
+                    };
+                    let name = |align: Option<egui::Align>| match align {
+                        None => "default",
+                        Some(egui::Align::Min) => names[0],
+                        Some(egui::Align::Center) => names[1],
+                        Some(egui::Align::Max) => names[2],
+                    };
+                    ui.horizontal(|ui| {
+                        ui.label("Align");
+                        egui::ComboBox::from_id_salt(("align", w.id))
+                            .selected_text(name(w.props.align))
+                            .show_ui(ui, |ui| {
+                                for align in [None, Some(egui::Align::Min), Some(egui::Align::Center), Some(egui::Align::Max)] {
+                                    ui.selectable_value(&mut w.props.align, align, name(align));
+                                }
+                            });
+                    });
+                    spacing_edit(ui, &mut w.props.spacing);
+                }
+                WidgetKind::Grid => {
+                    ui.horizontal(|ui| {
+                        ui.label("Columns");
+                        ui.add(egui::DragValue::new(&mut w.props.grid_columns).range(1..=12));
+                        ui.checkbox(&mut w.props.striped, "striped");
+                    });
+                    spacing_edit(ui, &mut w.props.spacing);
+                }
+                WidgetKind::Strip => {
+                    ui.checkbox(&mut w.props.vertical, "stacked");
+                    spacing_edit(ui, &mut w.props.spacing);
+                    ui.weak("Each widget gets an even share of the strip");
+                }
                 WidgetKind::DatePicker => {
                     ui.horizontal(|ui| {
                         ui.label("Year");
@@ -978,128 +1963,868 @@ impl RadBuilderApp {
                         egui::Slider::new(&mut w.props.max, w.props.min..=1080.0).text("max (deg)"),
                     );
                 }
+                WidgetKind::Plot => {
+                    ui.horizontal(|ui| {
+                        ui.label("X axis");
+                        ui.text_edit_singleline(&mut w.props.x_label);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Y axis");
+                        ui.text_edit_singleline(&mut w.props.y_label);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut w.props.legend, "legend");
+                        ui.checkbox(&mut w.props.grid, "grid");
+                    });
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut w.props.zoom, "zoom");
+                        ui.checkbox(&mut w.props.drag, "drag");
+                    });
+                    ui.label("Series");
+                    let mut remove = None;
+                    for i in 0..w.props.series.len() {
+                        let field = w.series_field(i).unwrap_or_default();
+                        let series = &mut w.props.series[i];
+                        ui.horizontal(|ui| {
+                            if ui.small_button("🗑").clicked() {
+                                remove = Some(i);
+                            }
+                            ui.add(egui::TextEdit::singleline(&mut series.name).desired_width(90.0))
+                                .on_hover_text(format!("Data in the state field `{field}`"));
+                            let style = series.style;
+                            egui::ComboBox::from_id_salt(("series", w.id, i))
+                                .selected_text(style.label())
+                                .show_ui(ui, |ui| {
+                                    for style in SeriesStyle::ALL {
+                                        ui.selectable_value(&mut series.style, style, style.label());
+                                    }
+                                });
+                            if series.style != style {
+                                // sample data that suits the new style
+                                series.points = PlotSeries::sample(&series.name, series.style).points;
+                            }
+                        });
+                    }
+                    if let Some(i) = remove {
+                        w.props.series.remove(i);
+                    }
+                    ui.horizontal(|ui| {
+                        for style in SeriesStyle::ALL {
+                            if ui.button(format!("+ {}", style.label())).clicked() {
+                                let name = format!("Series {}", w.props.series.len() + 1);
+                                w.props.series.push(PlotSeries::sample(&name, style));
+                            }
+                        }
+                    });
+                    ui.weak("Plots show sample data, the app fills the series fields");
+                }
+                WidgetKind::Table => {
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut w.props.striped, "striped");
+                        ui.checkbox(&mut w.props.sticky_header, "sticky header");
+                    });
+                    ui.checkbox(&mut w.props.sortable, "sort by clicking a header");
+                    ui.label("Columns");
+                    let mut remove = None;
+                    for i in 0..w.props.columns.len() {
+                        let field = w.column_field(i).unwrap_or_default();
+                        let column = &mut w.props.columns[i];
+                        ui.horizontal(|ui| {
+                            if ui.small_button("🗑").clicked() {
+                                remove = Some(i);
+                            }
+                            ui.add(egui::TextEdit::singleline(&mut column.header).desired_width(90.0))
+                                .on_hover_text(format!("Stored in the row field `{field}`"));
+                            egui::ComboBox::from_id_salt(("column type", w.id, i))
+                                .selected_text(column.ty.label())
+                                .show_ui(ui, |ui| {
+                                    for ty in ColumnType::ALL {
+                                        ui.selectable_value(&mut column.ty, ty, ty.label());
+                                    }
+                                });
+                        });
+                        ui.horizontal(|ui| {
+                            ui.add_space(24.0);
+                            let width = match column.width {
+                                ColumnWidth::Initial(width) | ColumnWidth::Exact(width) => width,
+                                ColumnWidth::Auto | ColumnWidth::Remainder => 100.0,
+                            };
+                            egui::ComboBox::from_id_salt(("column width", w.id, i))
+                                .selected_text(column.width.label())
+                                .show_ui(ui, |ui| {
+                                    for policy in [
+                                        ColumnWidth::Auto,
+                                        ColumnWidth::Initial(width),
+                                        ColumnWidth::Exact(width),
+                                        ColumnWidth::Remainder,
+                                    ] {
+                                        ui.selectable_value(&mut column.width, policy, policy.label());
+                                    }
+                                });
+                            if let ColumnWidth::Initial(width) | ColumnWidth::Exact(width) = &mut column.width {
+                                ui.add(egui::DragValue::new(width).range(8.0..=2000.0).suffix(" px"));
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            ui.add_space(24.0);
+                            ui.checkbox(&mut column.resizable, "resizable");
+                            for align in ColumnAlign::ALL {
+                                ui.selectable_value(&mut column.align, align, format!("{align:?}"));
+                            }
+                        });
+                    }
+                    if let Some(i) = remove {
+                        w.props.columns.remove(i);
+                        for row in &mut w.props.rows {
+                            if i < row.len() {
+                                row.remove(i);
+                            }
+                        }
+                    }
+                    if ui.button("+ Column").clicked() {
+                        w.props.columns.push(TableColumn::default());
+                    }
+                    ui.label("Sample rows");
+                    let columns = w.props.columns.clone();
+                    let mut remove = None;
+                    egui::Grid::new(("rows", w.id)).striped(true).show(ui, |ui| {
+                        for (r, row) in w.props.rows.iter_mut().enumerate() {
+                            row.resize(row.len().max(columns.len()), String::new());
+                            for (column, cell) in columns.iter().zip(row.iter_mut()) {
+                                let edit = ui.add(egui::TextEdit::singleline(cell).desired_width(60.0));
+                                if let Some(problem) = column.ty.parse_problem(cell) {
+                                    edit.on_hover_text(problem);
+                                }
+                            }
+                            if ui.small_button("🗑").clicked() {
+                                remove = Some(r);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                    if let Some(r) = remove {
+                        w.props.rows.remove(r);
+                    }
+                    if ui.button("+ Row").clicked() {
+                        w.props.rows.push(vec![String::new(); columns.len()]);
+                    }
+                    ui.weak("Tables show these rows, the app fills the rows field");
+                }
                 WidgetKind::Password => { /* no extra props */ }
                 _ => {}
             }
-            ui.separator();
-            ui.horizontal(|ui| {
-                ui.label("Area");
-                let mut area = w.area;
-                egui::ComboBox::from_id_salt(("area", w.id))
-                    .selected_text(format!("{:?}", area))
-                    .show_ui(ui, |ui| {
-                        for a in [
-                            DockArea::Free,
-                            DockArea::Top,
-                            DockArea::Bottom,
-                            DockArea::Left,
-                            DockArea::Right,
-                            DockArea::Center,
-                        ] {
-                            ui.selectable_value(&mut area, a, format!("{:?}", a));
+            if !w.kind.events().is_empty() {
+                ui.add_space(4.0);
+                ui.label("Events");
+                for &event in w.kind.events() {
+                    let mut enabled = w.events.contains(&event);
+                    let response = ui.checkbox(&mut enabled, event.label());
+                    if count == 1 {
+                        response.clone().on_hover_text(format!(
+                            "Reported as {}::{}",
+                            self.project.codegen.event_enum_name,
+                            w.event_variant(event)
+                        ));
+                    }
+                    if response.changed() {
+                        w.events.retain(|e| *e != event);
+                        if enabled {
+                            w.events.push(event);
+                            w.events.sort();
                         }
-                    });
-                if area != w.area {
-                    w.area = area;
-                    // reset pos within new area (keeps roughly same coords snapped)
-                    w.pos = snap_pos_with_grid(w.pos, grid);
+                    }
                 }
-            });
-            ui.add_space(2.0);
-            ui.label("Position");
-            ui.horizontal(|ui| {
-                ui.label("X:");
-                ui.add(egui::DragValue::new(&mut w.pos.x).range(0..=self.project.canvas_size.x.ceil() as u32));
-                ui.label("Y:");
-                ui.add(egui::DragValue::new(&mut w.pos.y).range(0..=self.project.canvas_size.y.ceil() as u32));
-            });
-            ui.label("Size");
-            ui.horizontal(|ui| {
-                ui.label("W:");
-                ui.add(egui::DragValue::new(&mut w.size.x).range(16.0..=2000.0));
-                ui.label("H:");
-                ui.add(egui::DragValue::new(&mut w.size.y).range(12.0..=2000.0));
-            });
+            }
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("Area");
+            let mut area = w.area;
+            egui::ComboBox::from_id_salt(("area", w.id))
+                .selected_text(format!("{:?}", area))
+                .show_ui(ui, |ui| {
+                    for a in [
+                        DockArea::Free,
+                        DockArea::Top,
+                        DockArea::Bottom,
+                        DockArea::Left,
+                        DockArea::Right,
+                        DockArea::Center,
+                    ] {
+                        ui.selectable_value(&mut area, a, format!("{:?}", a));
+                    }
+                });
+            w.area = area;
+        });
+        if let Some(parent) = w.parent {
+            ui.weak(format!("Placed in {parent:?}, moving to another area takes it out"));
+        }
+        if self.in_layout(w) {
+            ui.weak("Its layout places it, drag it to reorder");
+        }
+        ui.add_space(2.0);
+        ui.label("Position");
+        ui.horizontal(|ui| {
+            ui.label("X:");
+            ui.add(egui::DragValue::new(&mut w.pos.x).range(0..=canvas_size.x.ceil() as u32));
+            ui.label("Y:");
+            ui.add(egui::DragValue::new(&mut w.pos.y).range(0..=canvas_size.y.ceil() as u32));
+        });
+        ui.label("Size");
+        ui.horizontal(|ui| {
+            ui.label("W:");
+            ui.add(egui::DragValue::new(&mut w.size.x).range(16.0..=2000.0));
+            ui.label("H:");
+            ui.add(egui::DragValue::new(&mut w.size.y).range(12.0..=2000.0));
+        });
 
-            ui.add_space(6.0);
-            // Duplicate button
-            if ui.button("Duplicate").on_hover_cursor(egui::CursorIcon::Copy).clicked() {
-                let mut new_w = w.clone();
-                new_w.id = WidgetId::new(self.next_id);
-                // move above
-                new_w.z += 1;
-                // move down to the right
-                new_w.pos.x += 26.0;
-                new_w.pos.y += 26.0;
-                // add to widgets
-                duplicate = Some(new_w);
-
-                self.selected = None;
+        ui.add_space(6.0);
+        // Duplicate button
+        if ui.button("Duplicate").on_hover_cursor(egui::CursorIcon::Copy).clicked() {
+            duplicate = true;
+        }
+
+        ui.add_space(6.0);
+        // Delete button
+        if ui.add(Button::new("Delete").fill(Color32::from_rgb(210, 0, 0))).clicked() {
+            delete = true;
+        }
+
+        if edited != before {
+            let moved = self.project.with_contents(&self.selected);
+            for target in self.project.widgets.iter_mut().filter(|t| moved.contains(&t.id)) {
+                if before.area != edited.area && !target.parent.is_some_and(|p| moved.contains(&p)) {
+                    // taken out of its container, reset pos within new area (keeps roughly same coords snapped)
+                    target.pos = snap_pos_with_grid(target.pos, grid);
+                    target.parent = None;
+                }
+                if !self.selected.contains(&target.id) {
+                    // what is placed in a moved container goes along with it
+                    target.area = edited.area;
+                    continue;
+                }
+                target.apply_edits(&before, &edited);
             }
+        }
+        if add_image && let Some(name) = self.add_asset() {
+            for target in self.project.widgets.iter_mut().filter(|t| self.selected.contains(&t.id)) {
+                target.props.image = name.clone();
+            }
+        }
+        if duplicate {
+            self.duplicate_selected();
+        }
+        if delete {
+            self.delete_selected();
+        }
+    }
+
+    fn align_selected(&mut self, op: AlignOp) {
+        let origins: HashMap<WidgetId, Pos2> = self
+            .project
+            .widgets
+            .iter()
+            .filter(|w| self.selected.contains(&w.id))
+            .map(|w| (w.id, self.origin_of(w).unwrap_or(Pos2::ZERO)))
+            .collect();
+        let origin = |w: &Widget| origins.get(&w.id).copied().unwrap_or(Pos2::ZERO);
+
+        let mut targets: Vec<&mut Widget> = self
+            .project
+            .widgets
+            .iter_mut()
+            .filter(|w| self.selected.contains(&w.id))
+            .collect();
+        align::apply(op, &mut targets, origin, self.grid_size);
+    }
 
-            ui.add_space(6.0);
-            // Delete button
-            if ui.add(Button::new("Delete").fill(Color32::from_rgb(210, 0, 0))).clicked() {
-                let id = w.id; // capture
-                self.project.widgets.retain(|w| w.id != id);
-                self.selected = None;
+    /// Row of alignment buttons below the menu bar
+    fn toolbar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let mut previewing = self.preview.is_some();
+            let hover = format!("Run the design like the generated app ({})", ui.ctx().format_shortcut(&PREVIEW_SHORTCUT));
+            if ui.toggle_value(&mut previewing, "▶ Preview").on_hover_text(hover).changed() {
+                self.toggle_preview();
+            }
+            if previewing {
+                ui.weak("Changes made while previewing are not kept");
+                return;
+            }
+            ui.separator();
+            ui.label("Arrange:");
+            for (i, op) in AlignOp::ALL.into_iter().enumerate() {
+                // separate align / distribute / match groups
+                if matches!(i, 3 | 6 | 8) {
+                    ui.separator();
+                }
+                let enabled = self.selected.len() >= op.min_selection();
+                if ui
+                    .add_enabled(enabled, Button::new(op.label()).small())
+                    .on_hover_text(op.description())
+                    .clicked()
+                {
+                    self.align_selected(op);
+                }
+            }
+        });
+    }
+
+    /// Switch between editing the design and running it like the generated app,
+    /// starting every preview from the values the design sets
+    fn toggle_preview(&mut self) {
+        if self.preview.take().is_none() {
+            self.preview = Some(PreviewState::default());
+        }
+        self.spawning = None;
+        self.drag = None;
+        self.marquee = None;
+        self.guides.clear();
+    }
+
+    fn duplicate_selected(&mut self) {
+        let copied = self.project.with_contents(&self.selected);
+        let mut copies: Vec<Widget> =
+            self.project.widgets.iter().filter(|w| copied.contains(&w.id)).cloned().collect();
+        let ids = self.renumber(&mut copies);
+        for new_w in &mut copies {
+            match new_w.parent.and_then(|p| ids.get(&p)) {
+                // the copy of its container holds it
+                Some(&parent) => new_w.parent = Some(parent),
+                None => {
+                    // move above
+                    new_w.z += 1;
+                    // move down to the right
+                    new_w.pos.x += 26.0;
+                    new_w.pos.y += 26.0;
+                }
             }
-        } else {
-            ui.weak("No selection");
         }
+        self.dedupe_names(&mut copies);
+        self.selected = ids.iter().filter(|(id, _)| self.selected.contains(id)).map(|(_, &new)| new).collect();
+        self.project.widgets.extend(copies);
+    }
 
-        if let Some(new_w) = duplicate {
-            self.selected = Some(new_w.id);
+    /// Where relative asset paths of pasted designs point, next to the open file like its own
+    fn design_dir(&self) -> &Path {
+        self.file_path.as_deref().and_then(Path::parent).unwrap_or(Path::new(""))
+    }
 
-            // add to widgets
-            self.project.widgets.push(new_w);
+    /// Give `widgets` fresh ids, returning the new id of each old one
+    fn renumber(&mut self, widgets: &mut [Widget]) -> HashMap<WidgetId, WidgetId> {
+        let mut ids = HashMap::new();
+        for w in widgets {
+            let id = WidgetId::new(self.next_id);
             self.next_id += 1;
+            ids.insert(w.id, id);
+            w.id = id;
+        }
+        ids
+    }
+
+    /// Remove the selected widgets and everything placed in them
+    fn delete_selected(&mut self) {
+        let gone = self.project.with_contents(&self.selected);
+        self.project.widgets.retain(|w| !gone.contains(&w.id));
+        self.selected.clear();
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.saved_project.as_ref() != Some(&self.project)
+    }
+
+    /// Run `action` now, or ask first if it would discard unsaved changes
+    fn request(&mut self, action: PendingAction) {
+        if self.is_dirty() {
+            self.open_modal = Some(OpenModal::ConfirmDiscard(action));
+        } else {
+            self.perform(action);
+        }
+    }
+
+    fn perform(&mut self, action: PendingAction) {
+        match action {
+            PendingAction::New => {
+                self.replace_project(Project::default(), None);
+            }
+            PendingAction::Open(path) => {
+                let path = path.or_else(|| {
+                    rfd::FileDialog::new()
+                        .add_filter("egui RAD project", &["json"])
+                        .pick_file()
+                });
+                if let Some(path) = path {
+                    self.open_file(&path);
+                }
+            }
+            PendingAction::Clear => {
+                self.history.commit_pending(&self.project);
+                self.project = Project::default();
+                self.selected.clear();
+                // its values belong to the widgets just removed
+                self.preview = None;
+                self.history.record("Clear project", &self.project);
+            }
+            PendingAction::ImportRust => self.import_rust(),
+            PendingAction::ImportJson(project) => self.replace_project(*project, None),
+        }
+    }
+
+    fn open_file(&mut self, path: &Path) {
+        match Project::load(path) {
+            Ok(project) => {
+                self.replace_project(project, Some(path.to_path_buf()));
+                self.add_recent(path);
+            }
+            Err(e) => {
+                self.recent_files.retain(|p| p != path);
+                self.open_modal = Some(OpenModal::Error(format!(
+                    "Could not open {}:\n{e}",
+                    path.display()
+                )));
+            }
+        }
+    }
+
+    /// Swap in a freshly loaded (or new) project, forgetting history, selection and preview
+    fn replace_project(&mut self, project: Project, path: Option<PathBuf>) {
+        self.next_id = project.next_free_id();
+        self.saved_project = Some(project.clone());
+        self.history.reset(&project);
+        self.project = project;
+        self.file_path = path;
+        self.selected.clear();
+        self.generated.clear();
+        self.preview = None;
+    }
+
+    /// Save to the current file, asking for one if there is none. Returns whether it saved.
+    fn save(&mut self) -> bool {
+        match self.file_path.clone() {
+            Some(path) => self.save_to(&path),
+            None => self.save_as(),
+        }
+    }
+
+    fn save_as(&mut self) -> bool {
+        let file_name = self
+            .file_path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map_or("design.json".into(), |n| n.to_string_lossy().into_owned());
+        let path = rfd::FileDialog::new()
+            .add_filter("egui RAD project", &["json"])
+            .set_file_name(file_name)
+            .save_file();
+        path.is_some_and(|path| self.save_to(&path))
+    }
+
+    fn save_to(&mut self, path: &Path) -> bool {
+        match self.project.save(path) {
+            Ok(()) => {
+                self.saved_project = Some(self.project.clone());
+                self.file_path = Some(path.to_path_buf());
+                self.add_recent(path);
+                true
+            }
+            Err(e) => {
+                self.open_modal = Some(OpenModal::Error(format!(
+                    "Could not save {}:\n{e}",
+                    path.display()
+                )));
+                false
+            }
+        }
+    }
+
+    fn add_recent(&mut self, path: &Path) {
+        self.recent_files.retain(|p| p != path);
+        self.recent_files.insert(0, path.to_path_buf());
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+
+    /// Show the file name and a dirty marker in the window title
+    fn update_title(&mut self, ctx: &egui::Context) {
+        if self.saved_project.is_none() {
+            // compare a restored session against what is on disk
+            let on_disk = self.file_path.as_deref().and_then(|p| Project::load(p).ok());
+            self.saved_project = Some(on_disk.unwrap_or_default());
+        }
+        let name = self
+            .file_path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map_or("Untitled".into(), |n| n.to_string_lossy().into_owned());
+        let dirty = if self.is_dirty() { "*" } else { "" };
+        let title = format!("{dirty}{name} - egui RAD GUI Builder");
+        if title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = title;
+        }
+    }
+
+    fn undo(&mut self) {
+        if self.history.undo(&mut self.project) {
+            self.generated.clear();
+        }
+    }
+
+    fn redo(&mut self) {
+        if self.history.redo(&mut self.project) {
+            self.generated.clear();
+        }
+    }
+
+    /// Handle app wide keyboard shortcuts
+    fn shortcuts(&mut self, ctx: &egui::Context) {
+        if self.open_modal.is_some() {
+            return;
+        }
+        // file shortcuts work even while typing; check the shifted one first
+        if ctx.input_mut(|i| i.consume_shortcut(&SAVE_AS_SHORTCUT)) {
+            self.save_as();
+        } else if ctx.input_mut(|i| i.consume_shortcut(&SAVE_SHORTCUT)) {
+            self.save();
+        } else if ctx.input_mut(|i| i.consume_shortcut(&OPEN_SHORTCUT)) {
+            self.request(PendingAction::Open(None));
+        } else if ctx.input_mut(|i| i.consume_shortcut(&NEW_SHORTCUT)) {
+            self.request(PendingAction::New);
+        } else if ctx.input_mut(|i| i.consume_shortcut(&PREVIEW_SHORTCUT)) {
+            self.toggle_preview();
+        }
+        // the previewed widgets handle their own keys, nothing edits the design
+        if self.preview.is_some() {
+            return;
+        }
+
+        // let focused text fields handle their own undo
+        if ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+        // check the shifted shortcut first, `Ctrl+Z` also matches while shift is held
+        if ctx.input_mut(|i| i.consume_shortcut(&REDO_SHORTCUT) || i.consume_shortcut(&REDO_ALT_SHORTCUT)) {
+            self.redo();
+        } else if ctx.input_mut(|i| i.consume_shortcut(&UNDO_SHORTCUT)) {
+            self.undo();
+        }
+
+        let events = ctx.input(|i| i.events.clone());
+        for event in events {
+            match event {
+                egui::Event::Copy => {
+                    self.copy_selected();
+                }
+                egui::Event::Cut => self.cut_selected(),
+                egui::Event::Paste(text) => self.paste(&text, ctx.pointer_hover_pos()),
+                _ => {}
+            }
+        }
+    }
+
+    fn copy_selected(&mut self) -> bool {
+        let copied = self.project.with_contents(&self.selected);
+        let widgets: Vec<Widget> = self.project.widgets.iter().filter(|w| copied.contains(&w.id)).cloned().collect();
+        if widgets.is_empty() {
+            return false;
+        }
+        clipboard::to_json(&widgets).is_some_and(|json| self.clipboard.set_text(json))
+    }
+
+    fn cut_selected(&mut self) {
+        if self.copy_selected() {
+            self.history.commit_pending(&self.project);
+            self.delete_selected();
+            self.history.record("Cut", &self.project);
+        }
+    }
+
+    /// Add widgets from clipboard JSON with fresh ids.
+    ///
+    /// They land under `pointer` when it is over a dock area, otherwise at their
+    /// original spot in the area of the current selection.  Widgets copied along with
+    /// their container stay in it, the others are placed at the top level.  Assets of a
+    /// pasted project that its widgets show are added unless one of that name exists.
+    fn paste(&mut self, text: &str, pointer: Option<Pos2>) {
+        let Some(mut pasted) = clipboard::from_json(text) else { return };
+        pasted.resolve_assets(self.design_dir());
+        let mut widgets = pasted.widgets;
+        widgets.sort_by_key(|w| w.z);
+        let ids = self.renumber(&mut widgets);
+        for w in &mut widgets {
+            w.parent = w.parent.and_then(|p| ids.get(&p).copied());
+        }
+        let top_left = widgets.iter().filter(|w| w.parent.is_none()).map(|w| w.pos).reduce(|a, b| a.min(b));
+        let Some(top_left) = top_left else { return };
+
+        let at_pointer = pointer.and_then(|p| {
+            let area = self.area_at(p);
+            let rect = self.area_rect(area)?;
+            rect.contains(p).then(|| (area, self.snap_pos(p - rect.min.to_vec2())))
+        });
+        let selection_area = self
+            .project
+            .widgets
+            .iter()
+            .find(|w| self.selected.contains(&w.id))
+            .map(|w| w.area);
+
+        for w in &mut widgets {
+            w.z = w.id.as_z();
+            if let Some((area, at)) = at_pointer {
+                w.area = area;
+                if w.parent.is_none() {
+                    w.pos = at + (w.pos - top_left);
+                }
+            } else if let Some(area) = selection_area {
+                w.area = area;
+            }
+        }
+
+        self.dedupe_names(&mut widgets);
+        self.selected = widgets.iter().filter(|w| w.parent.is_none()).map(|w| w.id).collect();
+        let used: Vec<Asset> = pasted
+            .assets
+            .into_iter()
+            .filter(|a| widgets.iter().any(|w| w.uses_image() && w.props.image == a.name))
+            .filter(|a| self.project.asset(&a.name).is_none())
+            .collect();
+        self.history.commit_pending(&self.project);
+        self.project.assets.extend(used);
+        self.project.widgets.extend(widgets);
+        self.history.record("Paste", &self.project);
+    }
+
+    /// Give copied widgets whose name is already taken a numbered one instead
+    fn dedupe_names(&self, widgets: &mut [Widget]) {
+        let mut taken: HashSet<String> = self.project.widgets.iter().filter_map(|w| w.ident()).collect();
+        for w in widgets.iter_mut().filter(|w| !w.name.is_empty()) {
+            let base = w.name.clone();
+            let mut n = 2;
+            while taken.contains(&w.name) {
+                w.name = format!("{base}_{n}");
+                n += 1;
+            }
+            taken.insert(w.name.clone());
         }
     }
 
     fn top_bar(&mut self, ui: &mut egui::Ui) {
         egui::MenuBar::new().ui(ui, |ui| {
             ui.menu_button("File", |ui| {
+                let new = Button::new("New").shortcut_text(ui.ctx().format_shortcut(&NEW_SHORTCUT));
+                if ui.add(new).clicked() {
+                    self.request(PendingAction::New);
+                    ui.close_kind(egui::UiKind::Menu);
+                }
+                let open = Button::new("Open…").shortcut_text(ui.ctx().format_shortcut(&OPEN_SHORTCUT));
+                if ui.add(open).clicked() {
+                    self.request(PendingAction::Open(None));
+                    ui.close_kind(egui::UiKind::Menu);
+                }
+                ui.add_enabled_ui(!self.recent_files.is_empty(), |ui| {
+                    ui.menu_button("Open Recent", |ui| {
+                        let mut chosen = None;
+                        for path in &self.recent_files {
+                            if ui.button(path.display().to_string()).clicked() {
+                                chosen = Some(path.clone());
+                            }
+                        }
+                        ui.separator();
+                        if ui.button("Clear Recent").clicked() {
+                            self.recent_files.clear();
+                        }
+                        if let Some(path) = chosen {
+                            self.request(PendingAction::Open(Some(path)));
+                            ui.close_kind(egui::UiKind::Menu);
+                        }
+                    });
+                });
+                let save = Button::new("Save").shortcut_text(ui.ctx().format_shortcut(&SAVE_SHORTCUT));
+                if ui.add(save).clicked() {
+                    self.save();
+                    ui.close_kind(egui::UiKind::Menu);
+                }
+                let save_as =
+                    Button::new("Save As…").shortcut_text(ui.ctx().format_shortcut(&SAVE_AS_SHORTCUT));
+                if ui.add(save_as).clicked() {
+                    self.save_as();
+                    ui.close_kind(egui::UiKind::Menu);
+                }
+                ui.separator();
                 if ui.button("Generate Code").clicked() {
                     self.generate_code();
                     ui.close_kind(egui::UiKind::Menu);
                 }
+                if ui.button("Export Cargo Project…").clicked() {
+                    ui.close_kind(egui::UiKind::Menu);
+                    self.export_cargo_project();
+                }
                 if ui.button("Export JSON").clicked() {
                     self.open_modal = Some(OpenModal::Export);
                     ui.close_kind(egui::UiKind::Menu);
                 }
                 if ui.button("Import JSON").clicked() {
-                    self.open_modal = Some(OpenModal::Import);
+                    self.open_modal = Some(OpenModal::Import(None));
+                    ui.close_kind(egui::UiKind::Menu);
+                }
+                if ui
+                    .button("Import Rust Code…")
+                    .on_hover_text("Rebuild the design from code generated by this builder")
+                    .clicked()
+                {
                     ui.close_kind(egui::UiKind::Menu);
+                    self.request(PendingAction::ImportRust);
                 }
+                ui.separator();
                 if ui.button("Clear Project").clicked() {
-                    self.project = Project::default();
-                    self.selected = None;
+                    self.request(PendingAction::Clear);
                     ui.close_kind(egui::UiKind::Menu);
                 }
             });
 
+            // editing the design waits until the preview is closed
+            ui.add_enabled_ui(self.preview.is_none(), |ui| {
+                ui.menu_button("Edit", |ui| {
+                    let undo_text = match self.history.undo_label() {
+                        Some(label) => format!("Undo {label}"),
+                        None => "Undo".to_owned(),
+                    };
+                    let undo = ui.add_enabled(
+                        self.history.undo_label().is_some(),
+                        Button::new(undo_text).shortcut_text(ui.ctx().format_shortcut(&UNDO_SHORTCUT)),
+                    );
+                    if undo.clicked() {
+                        self.undo();
+                        ui.close_kind(egui::UiKind::Menu);
+                    }
+
+                    let redo_text = match self.history.redo_label() {
+                        Some(label) => format!("Redo {label}"),
+                        None => "Redo".to_owned(),
+                    };
+                    let redo = ui.add_enabled(
+                        self.history.redo_label().is_some(),
+                        Button::new(redo_text).shortcut_text(ui.ctx().format_shortcut(&REDO_SHORTCUT)),
+                    );
+                    if redo.clicked() {
+                        self.redo();
+                        ui.close_kind(egui::UiKind::Menu);
+                    }
+
+                    ui.separator();
+                    let has_selection = !self.selected.is_empty();
+                    let cut = ui.add_enabled(
+                        has_selection,
+                        Button::new("Cut").shortcut_text(ui.ctx().format_shortcut(&CUT_SHORTCUT)),
+                    );
+                    if cut.clicked() {
+                        self.cut_selected();
+                        ui.close_kind(egui::UiKind::Menu);
+                    }
+                    let copy = ui.add_enabled(
+                        has_selection,
+                        Button::new("Copy").shortcut_text(ui.ctx().format_shortcut(&COPY_SHORTCUT)),
+                    );
+                    if copy.clicked() {
+                        self.copy_selected();
+                        ui.close_kind(egui::UiKind::Menu);
+                    }
+                    let paste =
+                        ui.add(Button::new("Paste").shortcut_text(ui.ctx().format_shortcut(&PASTE_SHORTCUT)));
+                    if paste.clicked() {
+                        if let Some(text) = self.clipboard.get_text() {
+                            self.paste(&text, None);
+                        }
+                        ui.close_kind(egui::UiKind::Menu);
+                    }
+                });
+
+                ui.menu_button("Arrange", |ui| {
+                    for op in AlignOp::ALL {
+                        let enabled = self.selected.len() >= op.min_selection();
+                        if ui.add_enabled(enabled, Button::new(op.description())).clicked() {
+                            self.align_selected(op);
+                            ui.close_kind(egui::UiKind::Menu);
+                        }
+                    }
+                });
+            });
+
             ui.menu_button("View", |ui| {
                 ui.checkbox(&mut self.palette_open, "Show Palette");
+                ui.separator();
+                let preview = Button::selectable(self.preview.is_some(), "Preview")
+                    .shortcut_text(ui.ctx().format_shortcut(&PREVIEW_SHORTCUT));
+                if ui.add(preview).clicked() {
+                    self.toggle_preview();
+                    ui.close_kind(egui::UiKind::Menu);
+                }
             });
-            ui.menu_button("Settings", |ui| {
-                ui.checkbox(&mut self.show_grid, "Show grid");
-                ui.horizontal(|ui| {
-                    ui.label("Grid");
-                    ui.add(egui::DragValue::new(&mut self.grid_size).range(1.0..=64.0));
-                });
-                ui.horizontal(|ui| {
-                    ui.label("Canvas size");
-                    ui.add(egui::DragValue::new(&mut self.project.canvas_size.x));
-                    ui.add(egui::DragValue::new(&mut self.project.canvas_size.y));
+            ui.add_enabled_ui(self.preview.is_none(), |ui| {
+                ui.menu_button("Settings", |ui| {
+                    ui.checkbox(&mut self.show_grid, "Show grid");
+                    ui.checkbox(&mut self.smart_guides, "Smart guides")
+                        .on_hover_text("Snap to edges, centers and spacing of nearby widgets");
+                    ui.weak("Hold Alt while dragging to suspend snapping");
+                    ui.horizontal(|ui| {
+                        ui.label("Grid");
+                        ui.add(egui::DragValue::new(&mut self.grid_size).range(1.0..=64.0));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Canvas size");
+                        ui.add(egui::DragValue::new(&mut self.project.canvas_size.x));
+                        ui.add(egui::DragValue::new(&mut self.project.canvas_size.y));
+                    });
+                    ui.separator();
+                    ui.strong("Panels");
+                    ui.add_space(4.0);
+                    ui.checkbox(&mut self.project.panel_top_enabled, "Top");
+                    ui.checkbox(&mut self.project.panel_bottom_enabled, "Bottom");
+                    ui.checkbox(&mut self.project.panel_left_enabled, "Left");
+                    ui.checkbox(&mut self.project.panel_right_enabled, "Right");
+                    ui.separator();
+                    ui.strong("Code generation");
+                    ui.add_space(4.0);
+                    let codegen = &mut self.project.codegen;
+                    ui.horizontal(|ui| {
+                        ui.label("Output");
+                        egui::ComboBox::from_id_salt("codegen_output")
+                            .selected_text(codegen.output.label())
+                            .show_ui(ui, |ui| {
+                                for mode in OutputMode::ALL {
+                                    ui.selectable_value(&mut codegen.output, mode, mode.label());
+                                }
+                            });
+                    });
+                    identifier_edit(ui, "State struct", &mut codegen.state_name);
+                    identifier_edit(ui, "UI function", &mut codegen.ui_fn_name);
+                    identifier_edit(ui, "Event enum", &mut codegen.event_enum_name);
+                    ui.horizontal(|ui| {
+                        ui.label("Images");
+                        egui::ComboBox::from_id_salt("codegen_assets")
+                            .selected_text(codegen.assets.label())
+                            .show_ui(ui, |ui| {
+                                for mode in AssetMode::ALL {
+                                    ui.selectable_value(&mut codegen.assets, mode, mode.label());
+                                }
+                            });
+                    })
+                    .response
+                    .on_hover_text("Embed copies of the files in assets/ into the binary, or load them when the app runs");
+                    ui.add_enabled_ui(codegen.output == OutputMode::Module, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Visibility");
+                            egui::ComboBox::from_id_salt("codegen_visibility")
+                                .selected_text(codegen.visibility.label())
+                                .show_ui(ui, |ui| {
+                                    for vis in Visibility::ALL {
+                                        ui.selectable_value(&mut codegen.visibility, vis, vis.label());
+                                    }
+                                });
+                        });
+                    })
+                    .response
+                    .on_disabled_hover_text("Only used when generating a module");
                 });
-                ui.separator();
-                ui.strong("Panels");
-                ui.add_space(4.0);
-                ui.checkbox(&mut self.project.panel_top_enabled, "Top");
-                ui.checkbox(&mut self.project.panel_bottom_enabled, "Bottom");
-                ui.checkbox(&mut self.project.panel_left_enabled, "Left");
-                ui.checkbox(&mut self.project.panel_right_enabled, "Right");
             });
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.button("Generate Code").clicked() {
@@ -1112,42 +2837,47 @@ impl RadBuilderApp {
     }
 
     fn modals(&mut self, ctx: &egui::Context) {
-        let Some(ref open_modal) = self.open_modal else { return; };
+        // taken out so the modal contents can freely mutate `self`
+        let Some(mut open_modal) = self.open_modal.take() else { return; };
 
         fn default_modal(id: Id, ctx: &egui::Context) -> Modal {
             let view_rect_height = ctx.content_rect().height().abs();
             // 5% + 10px padding on top and bottom
             let width = view_rect_height * 0.9 - 10.0;
             Modal {
-                area: Modal::default_area(id.into()).default_size(vec2(550.0, width)),
+                area: Modal::default_area(id).default_size(vec2(550.0, width)),
                 backdrop_color: Color32::from_black_alpha(100),
                 frame: None,
             }
         }
 
         let mut close_modal = false;
-        let modal = match open_modal {
-            OpenModal::Import => default_modal("Import JSON modal".into(), ctx).show(ctx, |ui| {
+        let mut confirmed = None;
+        let mut save_first = false;
+        let mut export_to = None;
+        let modal = match &mut open_modal {
+            OpenModal::Import(error) => default_modal("Import JSON modal".into(), ctx).show(ctx, |ui| {
                     // todo add text box for alt input
 
                     // todo add icon
                     if ui.button("Paste JSON").clicked() {
-                        let mut ctx = copypasta::ClipboardContext::new().unwrap();
-                        if let Ok(paste_str) = ctx.get_contents()
-                        && let Ok(p) = serde_json::from_str::<Project>(&paste_str) {
-                            self.project = p;
-                            self.selected = None;
-                            // update the next_id to be correct
-                            self.next_id = self.project.widgets.iter().map(|w| w.id.value()).max()
-                                .map(|id| id + 1).unwrap_or(0);
-
-                            self.generated.clear();
+                        match self.clipboard.get_text().map(|paste_str| Project::from_json(&paste_str)) {
+                            Some(Ok(mut p)) => {
+                                p.resolve_assets(self.design_dir());
+                                // may open the discard confirmation in place of this modal
+                                self.request(PendingAction::ImportJson(Box::new(p)));
+                                close_modal = true;
+                            }
+                            Some(Err(e)) => *error = Some(format!("Import failed: {e}")),
+                            None => *error = Some("The clipboard doesn't contain any text".into()),
                         }
-                        close_modal = true;
+                    }
+                    if let Some(error) = error {
+                        ui.colored_label(ui.visuals().error_fg_color, error.as_str());
                     }
                 }),
             OpenModal::Export => default_modal("Export JSON modal".into(), ctx).show(ctx, |ui| {
-                    if let Ok(ex_json) = serde_json::to_string_pretty(&self.project) {
+                    if let Ok(ex_json) = self.project.to_json() {
                         // todo add icon
                         if ui.button("Copy Exported").clicked() {
                             ui.ctx().copy_text(ex_json.clone());
@@ -1165,10 +2895,74 @@ impl RadBuilderApp {
                             });
                     } else { ui.label("Failed to serialize project to json"); }
                 }),
+            OpenModal::ConfirmDiscard(action) => Modal::new("Confirm discard modal".into()).show(ctx, |ui| {
+                    ui.heading("Unsaved changes");
+                    ui.label("The current project has changes that haven't been saved.");
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui.button("Save").clicked() {
+                            close_modal = true;
+                            save_first = true;
+                            confirmed = Some(action.clone());
+                        }
+                        if ui.button("Discard").clicked() {
+                            close_modal = true;
+                            confirmed = Some(action.clone());
+                        }
+                        if ui.button("Cancel").clicked() {
+                            close_modal = true;
+                        }
+                    });
+                }),
+            OpenModal::ConfirmExport(dir) => Modal::new("Confirm export modal".into()).show(ctx, |ui| {
+                    ui.heading("Directory not empty");
+                    ui.label(format!(
+                        "{} already has files in it. Exporting overwrites the Cargo project files, \
+                         keeping the protected regions of src/ui.rs.",
+                        dir.display()
+                    ));
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        if ui.button("Overwrite").clicked() {
+                            close_modal = true;
+                            export_to = Some(dir.clone());
+                        }
+                        if ui.button("Cancel").clicked() {
+                            close_modal = true;
+                        }
+                    });
+                }),
+            OpenModal::Error(message) => Modal::new("Error modal".into()).show(ctx, |ui| {
+                    ui.heading("Error");
+                    ui.label(message.as_str());
+                    ui.add_space(8.0);
+                    if ui.button("OK").clicked() {
+                        close_modal = true;
+                    }
+                }),
+            OpenModal::Warning(message) => Modal::new("Warning modal".into()).show(ctx, |ui| {
+                    ui.heading("Warning");
+                    egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                        ui.label(message.as_str());
+                    });
+                    ui.add_space(8.0);
+                    if ui.button("OK").clicked() {
+                        close_modal = true;
+                    }
+                }),
         };
 
-        if close_modal || modal.should_close() {
-            self.open_modal = None;
+        // keep it open unless closed, or replaced by something shown from inside it
+        if !close_modal && !modal.should_close() && self.open_modal.is_none() {
+            self.open_modal = Some(open_modal);
+        }
+        if let Some(action) = confirmed
+            && (!save_first || self.save())
+        {
+            self.perform(action);
+        }
+        if let Some(dir) = export_to {
+            self.write_cargo_project(&dir, true);
         }
     }
 
@@ -1177,9 +2971,18 @@ impl RadBuilderApp {
         ui.label("Rust code. Copy-paste into your app.");
 
         // todo add icon
-        if ui.button("Copy code").clicked() {
-            ui.ctx().copy_text(self.generated.clone());
-        }
+        ui.horizontal(|ui| {
+            if ui.button("Copy code").clicked() {
+                ui.ctx().copy_text(self.generated.clone());
+            }
+            if ui
+                .add_enabled(!self.generated.is_empty(), egui::Button::new("Write to file…"))
+                .on_hover_text("Overwrite a file with the code, keeping its protected regions")
+                .clicked()
+            {
+                self.write_generated();
+            }
+        });
 
         // A scrollable viewport for the generated text:
         egui::ScrollArea::vertical()
@@ -1191,260 +2994,91 @@ impl RadBuilderApp {
             });
     }
 
-    fn generate_code(&mut self) {
-        use DockArea::*;
+    /// Write a ready to build Cargo project for the design into a chosen directory, asking
+    /// first if it isn't empty
+    fn export_cargo_project(&mut self) {
+        let Some(dir) = rfd::FileDialog::new().set_title("Export Cargo Project").pick_folder() else {
+            return;
+        };
+        if dir.read_dir().is_ok_and(|mut entries| entries.next().is_some()) {
+            self.open_modal = Some(OpenModal::ConfirmExport(dir));
+        } else {
+            self.write_cargo_project(&dir, false);
+        }
+    }
 
-        self.generated.clear();
-        let mut out = &mut self.generated;
-        out.push_str("// --- generated by egui RAD GUI Builder ---\n");
-        out.push_str("use eframe::egui;\n");
-        // todo only add if used by code
-        out.push_str("use egui_extras::DatePickerButton;\n");
-        out.push_str("use chrono::NaiveDate;\n\n");
-
-        let has_tree = self
-            .project
-            .widgets
-            .iter()
-            .any(|w| matches!(w.kind, WidgetKind::Tree));
-        if has_tree {
-            out.push_str(
-                "#[derive(Clone)]\n\
-				 struct GenTreeNode { label: String, children: Vec<GenTreeNode> }\n\
-				 \n\
-				 fn gen_show_tree(ui: &mut egui::Ui, nodes: &[GenTreeNode]) {\n\
-				 \tfor n in nodes {\n\
-				 \t\tif n.children.is_empty() { ui.label(&n.label); }\n\
-				 \t\telse { ui.collapsing(&n.label, |ui| gen_show_tree(ui, &n.children)); }\n\
-				 \t}\n\
-				 }\n\n",
-            );
+    /// Export to `dir`, replacing the files of an earlier export when `overwrite` is set
+    fn write_cargo_project(&mut self, dir: &Path, overwrite: bool) {
+        if let Err(e) = export::write_cargo_project(&self.project, dir, overwrite) {
+            self.open_modal = Some(OpenModal::Error(format!(
+                "Could not export to {}:\n{e}",
+                dir.display()
+            )));
         }
+    }
 
-        out.push_str("struct GeneratedState {\n");
-        out.push_str(&format!(
-            "    enable_top: bool, enable_bottom: bool, enable_left: bool, enable_right: bool,\n"
-        ));
-        for w in &self.project.widgets {
-            match w.kind {
-                WidgetKind::TextEdit => out.push_str(&format!("    text_{}: String,\n", w.id)),
-                WidgetKind::Checkbox => out.push_str(&format!("    checked_{}: bool,\n", w.id)),
-                WidgetKind::Slider => out.push_str(&format!("    value_{}: f32,\n", w.id)),
-                WidgetKind::ProgressBar => out.push_str(&format!("    progress_{}: f32,\n", w.id)),
-                WidgetKind::SelectableLabel => out.push_str(&format!("    sel_{}: bool,\n", w.id)),
-                WidgetKind::RadioGroup | WidgetKind::ComboBox | WidgetKind::MenuButton => {
-                    out.push_str(&format!("    sel_{}: usize,\n", w.id))
-                }
-                WidgetKind::CollapsingHeader => {
-                    out.push_str(&format!("    open_{}: bool,\n", w.id))
-                }
-                WidgetKind::DatePicker => out.push_str(&format!("    date_{}: NaiveDate,\n", w.id)),
-                WidgetKind::Password => out.push_str(&format!("    pass_{}: String,\n", w.id)),
-                WidgetKind::AngleSelector => out.push_str(&format!("    angle_{}: f32,\n", w.id)),
-                _ => {}
-            }
+    /// Write the generated code to a file, merging in the user regions it already has
+    fn write_generated(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Write Generated Code")
+            .add_filter("Rust", &["rs"])
+            .set_file_name("ui.rs")
+            .save_file()
+        else {
+            return;
+        };
+        let result = codegen::regions::merge_with_file(&self.generated, &path)
+            .and_then(|code| std::fs::write(&path, code));
+        if let Err(e) = result {
+            self.open_modal = Some(OpenModal::Error(format!(
+                "Could not write {}:\n{e}",
+                path.display()
+            )));
         }
-        out.push_str("}\n\n");
-
-        out.push_str("impl Default for GeneratedState {\n");
-        out.push_str("    fn default() -> Self {\n");
-        out.push_str("        Self {\n");
-        out.push_str(&format!(
-            "            enable_top: {}, enable_bottom: {}, enable_left: {}, enable_right: {},\n",
-            if self.project.panel_top_enabled {
-                "true"
-            } else {
-                "false"
-            },
-            if self.project.panel_bottom_enabled {
-                "true"
-            } else {
-                "false"
-            },
-            if self.project.panel_left_enabled {
-                "true"
-            } else {
-                "false"
-            },
-            if self.project.panel_right_enabled {
-                "true"
-            } else {
-                "false"
-            },
-        ));
-
-        for w in &self.project.widgets {
-            match w.kind {
-                WidgetKind::TextEdit => {
-                    out.push_str(&format!(
-                        "            text_{}: \"{}\".to_owned(),\n",
-                        w.id,
-                        widget::escape(&w.props.text)
-                    ));
-                }
-                WidgetKind::Checkbox => {
-                    out.push_str(&format!(
-                        "            checked_{}: {},\n",
-                        w.id,
-                        if w.props.checked { "true" } else { "false" }
-                    ));
-                }
-                WidgetKind::Slider => {
-                    out.push_str(&format!(
-                        "            value_{}: {:.3},\n",
-                        w.id, w.props.value
-                    ));
-                }
-                WidgetKind::ProgressBar => {
-                    let p = w.props.value.clamp(0.0, 1.0);
-                    out.push_str(&format!("            progress_{}: {:.3},\n", w.id, p));
-                }
-                WidgetKind::SelectableLabel => {
-                    out.push_str(&format!(
-                        "            sel_{}: {},\n",
-                        w.id,
-                        if w.props.checked { "true" } else { "false" }
-                    ));
-                }
-                WidgetKind::RadioGroup | WidgetKind::ComboBox | WidgetKind::MenuButton => {
-                    let sel = if w.props.items.is_empty() {
-                        0
-                    } else {
-                        w.props.selected.min(w.props.items.len() - 1)
-                    };
-                    out.push_str(&format!("            sel_{}: {},\n", w.id, sel));
-                }
-                WidgetKind::CollapsingHeader => {
-                    out.push_str(&format!(
-                        "            open_{}: {},\n",
-                        w.id,
-                        if w.props.checked { "true" } else { "false" }
-                    ));
-                }
-                WidgetKind::DatePicker => {
-                    let y = w.props.year;
-                    let m = w.props.month.clamp(1, 12);
-                    let d = w.props.day.clamp(1, 28);
-                    out.push_str(&format!(
-                        "            date_{}: NaiveDate::from_ymd_opt({}, {}, {}).unwrap(),\n",
-                        w.id, y, m, d
-                    ));
-                }
-                WidgetKind::Password => {
-                    out.push_str(&format!(
-                        "            pass_{}: \"{}\".to_owned(),\n",
-                        w.id,
-                        widget::escape(&w.props.text)
-                    ));
-                }
-                WidgetKind::AngleSelector => {
-                    out.push_str(&format!(
-                        "            angle_{}: {:.3},\n",
-                        w.id, w.props.value
-                    ));
+    }
+
+    /// Replace the design with the one rebuilt from a generated `.rs` file, as a new design
+    /// without a file of its own
+    fn import_rust(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Import Rust Code")
+            .add_filter("Rust", &["rs"])
+            .pick_file()
+        else {
+            return;
+        };
+        let imported = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|source| import::from_rust(&source).map_err(|e| e.to_string()));
+        match imported {
+            Ok(imported) => {
+                self.replace_project(imported.project, None);
+                if !imported.unrecognized.is_empty() {
+                    let skipped: Vec<String> = imported.unrecognized.iter().map(|u| u.to_string()).collect();
+                    self.open_modal = Some(OpenModal::Warning(format!(
+                        "This code of {} was not understood and is not part of the design:\n{}",
+                        path.display(),
+                        skipped.join("\n")
+                    )));
                 }
-                _ => {}
             }
-        }
-        out.push_str("        }\n");
-        out.push_str("    }\n");
-        out.push_str("}\n\n");
-
-        let mut top = Vec::new();
-        let mut bottom = Vec::new();
-        let mut left = Vec::new();
-        let mut right = Vec::new();
-        let mut center = Vec::new();
-        let mut free = Vec::new();
-        for w in &self.project.widgets {
-            match w.area {
-                Top => top.push(w),
-                Bottom => bottom.push(w),
-                Left => left.push(w),
-                Right => right.push(w),
-                Center => center.push(w),
-                Free => free.push(w),
+            Err(e) => {
+                self.open_modal = Some(OpenModal::Error(format!(
+                    "Could not import {}:\n{e}",
+                    path.display()
+                )));
             }
         }
+    }
 
-        out.push_str("fn generated_ui(ctx: &egui::Context, state: &mut GeneratedState) {\n");
-
-        // TOP
-        out.push_str("    if state.enable_top {\n");
-        out.push_str("        egui::TopBottomPanel::top(\"gen_top\")\n");
-        out.push_str("            .resizable(true)\n");
-        out.push_str("            .show(ctx, |ui| {\n");
-        for w in top {
-            w.emit_widget(&mut out, "ui.min_rect().min");
-        }
-        out.push_str("            });\n");
-        out.push_str("    }\n");
-
-        // BOTTOM
-        out.push_str("    if state.enable_bottom {\n");
-        out.push_str("        egui::TopBottomPanel::bottom(\"gen_bottom\")\n");
-        out.push_str("            .resizable(true)\n");
-        out.push_str("            .show(ctx, |ui| {\n");
-        for w in bottom {
-            w.emit_widget(&mut out, "ui.min_rect().min");
-        }
-        out.push_str("            });\n");
-        out.push_str("    }\n");
-
-        // LEFT
-        out.push_str("    if state.enable_left {\n");
-        out.push_str("        egui::SidePanel::left(\"gen_left\")\n");
-        out.push_str("            .resizable(true)\n");
-        out.push_str("            .show(ctx, |ui| {\n");
-        for w in left {
-            w.emit_widget(&mut out, "ui.min_rect().min");
-        }
-        out.push_str("            });\n");
-        out.push_str("    }\n");
-
-        // RIGHT
-        out.push_str("    if state.enable_right {\n");
-        out.push_str("        egui::SidePanel::right(\"gen_right\")\n");
-        out.push_str("            .resizable(true)\n");
-        out.push_str("            .show(ctx, |ui| {\n");
-        for w in right {
-            w.emit_widget(&mut out, "ui.min_rect().min");
-        }
-        out.push_str("            });\n");
-        out.push_str("    }\n");
-
-        // CENTER (+ FREE): use CentralPanel; widgets are placed absolutely within it.
-        out.push_str("    egui::CentralPanel::default().show(ctx, |ui| {\n");
-        // fixed logical canvas (keeps your designed size)
-        out.push_str(&format!(
-			"        let canvas = egui::Rect::from_min_size(ui.min_rect().min, egui::vec2({:.1}, {:.1}));\n",
-			self.project.canvas_size.x, self.project.canvas_size.y
-		));
-        out.push_str("        let _ = ui.allocate_painter(canvas.size(), egui::Sense::hover());\n");
-        for w in center {
-            w.emit_widget(&mut out, "canvas.min");
-        }
-        for w in free {
-            w.emit_widget(&mut out, "canvas.min");
-        }
-        out.push_str("    });\n");
-
-        out.push_str("}\n\n");
-
-        // ---------- Example eframe app (updated to call generated_ui with ctx) ----------
-        out.push_str(
-			"pub struct GeneratedApp { state: GeneratedState }\n\
-			 impl Default for GeneratedApp { fn default() -> Self { Self { state: Default::default() } } }\n\
-			 impl eframe::App for GeneratedApp {\n\
-			 \tfn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {\n\
-			 \t\tgenerated_ui(ctx, &mut self.state);\n\
-			 \t}\n\
-			 }\n\n\
-			 fn main() -> eframe::Result<()> {\n\
-			 \tlet native_options = eframe::NativeOptions::default();\n\
-			 \teframe::run_native(\"Generated UI\", native_options, Box::new(|_cc| Ok(Box::new(GeneratedApp::default()))))\n\
-			 }\n",
-		);
+    fn generate_code(&mut self) {
+        match codegen::generate(&self.project) {
+            Ok(code) => self.generated = code,
+            Err(e) => {
+                self.generated.clear();
+                self.open_modal = Some(OpenModal::Error(format!("Could not generate code:\n{e}")));
+            }
+        }
     }
 }
 
@@ -1454,21 +3088,29 @@ impl eframe::App for RadBuilderApp {
     }
 
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.shortcuts(ctx);
+        self.update_title(ctx);
+
         egui::TopBottomPanel::top("menubar").show(ctx, |ui| self.top_bar(ui));
-        if self.palette_open {
+        egui::TopBottomPanel::top("toolbar").show(ctx, |ui| self.toolbar(ui));
+        // the preview shows nothing but the design
+        let editing = self.preview.is_none();
+        if self.palette_open && editing {
             egui::SidePanel::left("palette")
                 .resizable(true)
                 .show(ctx, |ui| {
                     self.palette_ui(ui);
                 });
         }
-        egui::SidePanel::right("inspector")
-            .default_width(260.0)
-            .show(ctx, |ui| {
-                self.inspector_ui(ui);
-                ui.separator();
-                self.generated_panel(ui);
-            });
+        if editing {
+            egui::SidePanel::right("inspector")
+                .default_width(260.0)
+                .show(ctx, |ui| {
+                    self.inspector_ui(ui);
+                    ui.separator();
+                    self.generated_panel(ui);
+                });
+        }
 
         self.preview_panels_ui(ctx);
 
@@ -1478,5 +3120,178 @@ impl eframe::App for RadBuilderApp {
 
         // show modals
         self.modals(ctx);
+
+        // wait for drags and text edits to finish so they become a single undo step
+        let settled = !ctx.input(|i| i.pointer.any_down()) && ctx.memory(|m| m.focused().is_none());
+        self.history.observe(&self.project, settled);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn widget(id: u64, area: DockArea, pos: Pos2, size: Vec2) -> Widget {
+        Widget {
+            id: WidgetId::new(id),
+            kind: WidgetKind::Button,
+            pos,
+            size,
+            z: id as i32,
+            area,
+            parent: None,
+            props: WidgetProps::default(),
+            name: String::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// An app showing `widgets` with the center area at the screen origin, dragging all of them
+    fn dragging(widgets: Vec<Widget>, kind: DragKind, total: Vec2) -> RadBuilderApp {
+        let start = widgets.iter().map(|w| (w.id, w.pos, w.size)).collect();
+        let grabbed = widgets[0].id;
+        RadBuilderApp {
+            project: Project { widgets, ..Project::default() },
+            live_center: Some(Rect::from_min_size(Pos2::ZERO, vec2(700.0, 500.0))),
+            live_left: Some(Rect::from_min_size(pos2(-200.0, 0.0), vec2(200.0, 500.0))),
+            drag: Some(CanvasDrag { kind, grabbed, total, start }),
+            ..RadBuilderApp::default()
+        }
+    }
+
+    fn rects(app: &RadBuilderApp) -> Vec<(Pos2, Vec2)> {
+        app.project.widgets.iter().map(|w| (w.pos, w.size)).collect()
+    }
+
+    #[test]
+    fn group_move_keeps_relative_offsets() {
+        let mut app = dragging(
+            vec![
+                widget(1, DockArea::Center, pos2(20.0, 20.0), vec2(80.0, 24.0)),
+                widget(2, DockArea::Free, pos2(140.0, 90.0), vec2(60.0, 24.0)),
+                widget(3, DockArea::Left, pos2(10.0, 10.0), vec2(60.0, 24.0)),
+            ],
+            DragKind::Move,
+            vec2(33.0, 17.0),
+        );
+        app.move_dragged(false);
+        assert_eq!(
+            rects(&app),
+            vec![
+                (pos2(53.0, 37.0), vec2(80.0, 24.0)),
+                (pos2(173.0, 107.0), vec2(60.0, 24.0)),
+                (pos2(43.0, 27.0), vec2(60.0, 24.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn group_move_stops_at_the_area_edge() {
+        let mut app = dragging(
+            vec![
+                widget(1, DockArea::Center, pos2(100.0, 20.0), vec2(80.0, 24.0)),
+                widget(2, DockArea::Center, pos2(40.0, 60.0), vec2(60.0, 24.0)),
+            ],
+            DragKind::Move,
+            vec2(-70.0, 0.0),
+        );
+        app.move_dragged(false);
+        assert_eq!(app.project.widgets[0].pos, pos2(60.0, 20.0));
+        assert_eq!(app.project.widgets[1].pos, pos2(0.0, 60.0));
+    }
+
+    #[test]
+    fn group_resize_scales_around_the_group() {
+        let mut app = dragging(
+            vec![
+                widget(1, DockArea::Center, pos2(100.0, 100.0), vec2(100.0, 20.0)),
+                widget(2, DockArea::Center, pos2(200.0, 120.0), vec2(100.0, 20.0)),
+            ],
+            DragKind::Resize,
+            vec2(200.0, 40.0),
+        );
+        app.resize_dragged(false);
+        assert_eq!(
+            rects(&app),
+            vec![
+                (pos2(100.0, 100.0), vec2(200.0, 40.0)),
+                (pos2(300.0, 140.0), vec2(200.0, 40.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn group_resize_leaves_widgets_of_other_areas_alone() {
+        let mut app = dragging(
+            vec![
+                widget(1, DockArea::Center, pos2(100.0, 100.0), vec2(100.0, 20.0)),
+                widget(2, DockArea::Left, pos2(10.0, 10.0), vec2(100.0, 20.0)),
+                widget(3, DockArea::Center, pos2(200.0, 100.0), vec2(100.0, 20.0)),
+            ],
+            DragKind::Resize,
+            vec2(200.0, 0.0),
+        );
+        app.resize_dragged(false);
+        assert_eq!(
+            rects(&app),
+            vec![
+                (pos2(100.0, 100.0), vec2(200.0, 20.0)),
+                (pos2(10.0, 10.0), vec2(100.0, 20.0)),
+                (pos2(300.0, 100.0), vec2(200.0, 20.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn replacing_the_project_ends_the_preview() {
+        for action in [PendingAction::Clear, PendingAction::New] {
+            let widgets = vec![widget(1, DockArea::Center, Pos2::ZERO, vec2(80.0, 24.0))];
+            let mut app = RadBuilderApp {
+                project: Project { widgets, ..Project::default() },
+                preview: Some(PreviewState::default()),
+                ..RadBuilderApp::default()
+            };
+            app.perform(action);
+            assert!(app.project.widgets.is_empty());
+            assert!(app.preview.is_none());
+        }
+    }
+
+    #[test]
+    fn pasted_widgets_get_fresh_ids_and_stay_in_their_container() {
+        let widgets = vec![widget(1, DockArea::Center, Pos2::ZERO, vec2(80.0, 24.0))];
+        let mut app = RadBuilderApp {
+            project: Project { widgets, ..Project::default() },
+            next_id: 2,
+            ..RadBuilderApp::default()
+        };
+        let group = widget(1, DockArea::Center, pos2(20.0, 20.0), vec2(200.0, 100.0));
+        let mut inner = widget(2, DockArea::Center, pos2(10.0, 10.0), vec2(80.0, 24.0));
+        inner.parent = Some(group.id);
+        app.paste(&clipboard::to_json(&[group, inner]).unwrap(), None);
+
+        let ids: Vec<_> = app.project.widgets.iter().map(|w| (w.id.value(), w.parent.map(|p| p.value()))).collect();
+        assert_eq!(ids, [(1, None), (2, None), (3, Some(2))]);
+        assert_eq!(app.selected, BTreeSet::from([WidgetId::new(2)]));
+    }
+
+    #[test]
+    fn pasted_projects_bring_the_assets_their_widgets_show() {
+        let mut app = RadBuilderApp { file_path: Some(PathBuf::from("/designs/app.json")), ..RadBuilderApp::default() };
+        let mut image = widget(1, DockArea::Center, Pos2::ZERO, vec2(64.0, 64.0));
+        image.kind = WidgetKind::Image;
+        image.props.image = "logo.png".into();
+        let copied = Project {
+            widgets: vec![image],
+            assets: vec![
+                Asset { name: "logo.png".into(), path: "img/logo.png".into() },
+                Asset { name: "unused.png".into(), path: "img/unused.png".into() },
+            ],
+            ..Project::default()
+        };
+        app.paste(&copied.to_json().unwrap(), None);
+
+        assert_eq!(app.project.widgets.len(), 1);
+        assert_eq!(app.project.assets, [Asset { name: "logo.png".into(), path: "/designs/img/logo.png".into() }]);
     }
 }
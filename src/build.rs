@@ -0,0 +1,112 @@
+//! Compile a design file into an app from its build script.
+//!
+//! ```no_run
+//! // in main() of build.rs
+//! egui_rad_builder::build::compile("ui.json").expect("could not generate the UI");
+//! ```
+//!
+//! ```ignore
+//! // src/main.rs
+//! mod ui {
+//!     egui_rad_builder::include_rad!("ui.json");
+//! }
+//! ```
+//!
+//! The design is regenerated whenever the JSON changes, so a design saved from the
+//! builder shows up in the app on the next `cargo build`.
+
+use crate::{
+    codegen::{self, GenerateError},
+    project::{Project, ProjectError},
+};
+use std::{
+    fmt, io,
+    path::{Component, Path, PathBuf},
+};
+
+/// Failure to turn a design into code from a build script
+#[derive(Debug)]
+pub enum BuildError {
+    /// Not running inside a build script
+    NoOutDir,
+    /// The design path is absolute or leaves the crate with `..`, so the generated
+    /// file would end up outside `OUT_DIR`
+    InvalidDesign { design: String },
+    /// The design couldn't be read or parsed
+    Project { design: String, error: ProjectError },
+    /// The design couldn't be turned into code
+    Generate { design: String, error: GenerateError },
+    /// The generated code couldn't be written to `OUT_DIR`
+    Write { path: PathBuf, error: io::Error },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::NoOutDir => write!(f, "OUT_DIR is not set, call this from a build script"),
+            BuildError::InvalidDesign { design } => {
+                write!(f, "design {design} must be a path inside the crate, without `..`")
+            }
+            BuildError::Project { design, error } => write!(f, "could not read design {design}: {error}"),
+            BuildError::Generate { design, error } => {
+                write!(f, "could not generate code from {design}: {error}")
+            }
+            BuildError::Write { path, error } => write!(f, "could not write {}: {error}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Generate the module for `design` (relative to the crate root) into `OUT_DIR`
+/// and ask cargo to rerun when it changes. Returns the path of the generated file.
+///
+/// Use the same path with [`include_rad!`](crate::include_rad) to include it. The path
+/// must be relative and must not contain `..`.
+pub fn compile(design: &str) -> Result<PathBuf, BuildError> {
+    let inside = |c: Component| matches!(c, Component::Normal(_) | Component::CurDir);
+    if !Path::new(design).components().all(inside) {
+        return Err(BuildError::InvalidDesign { design: design.into() });
+    }
+    println!("cargo:rerun-if-changed={design}");
+    let out_dir = std::env::var("OUT_DIR").map_err(|_| BuildError::NoOutDir)?;
+
+    let project = Project::load(design).map_err(|error| BuildError::Project {
+        design: design.into(),
+        error,
+    })?;
+    // embedded with `include_bytes!`, or read at runtime, either way part of the build
+    for asset in &project.assets {
+        println!("cargo:rerun-if-changed={}", asset.path.display());
+    }
+    let code = codegen::generate_module(&project).map_err(|error| BuildError::Generate {
+        design: design.into(),
+        error,
+    })?;
+
+    // must match the path built by `include_rad!`, checked above to stay within `rad`
+    let path = PathBuf::from(format!("{out_dir}/rad/{design}.rs"));
+    let write = || {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, code)
+    };
+    match write() {
+        Ok(()) => Ok(path),
+        Err(error) => Err(BuildError::Write { path, error }),
+    }
+}
+
+/// Include the code generated by [`build::compile`](crate::build::compile) for a design.
+///
+/// Expands to the state struct and UI function with the names and visibility set in the
+/// design's code generation settings (public `GeneratedState` and `generated_ui` by
+/// default), best wrapped in a module of their own. Takes the same relative path without `..` as
+/// `compile`, which refuses any other.
+#[macro_export]
+macro_rules! include_rad {
+    ($design:literal) => {
+        include!(concat!(env!("OUT_DIR"), "/rad/", $design, ".rs"));
+    };
+}
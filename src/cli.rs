@@ -0,0 +1,283 @@
+//! Headless command line interface, so code generation can run in scripts and CI.
+//!
+//! ```text
+//! egui-rad-builder generate design.json -o src/ui.rs
+//! egui-rad-builder generate design.json -o src/ui.rs --check
+//! egui-rad-builder export design.json my_app
+//! egui-rad-builder import src/ui.rs -o design.json
+//! ```
+
+use crate::{
+    codegen::{self, regions},
+    export::{self, ExportError},
+    import,
+    project::Project,
+};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Generated code written, or up to date with `--check`
+pub(crate) const EXIT_OK: i32 = 0;
+/// `--check` found the output missing or different from what would be generated
+pub(crate) const EXIT_STALE: i32 = 1;
+/// Bad command line arguments
+pub(crate) const EXIT_USAGE: i32 = 2;
+/// The design file (or the code to import) couldn't be read or parsed
+pub(crate) const EXIT_PARSE: i32 = 3;
+/// The design was read but code couldn't be generated from it
+pub(crate) const EXIT_GENERATE: i32 = 4;
+/// The generated code couldn't be written
+pub(crate) const EXIT_WRITE: i32 = 5;
+
+const USAGE: &str = "\
+usage: egui-rad-builder generate <design.json> [-o <out.rs>] [--check]
+       egui-rad-builder export <design.json> <dir> [--force]
+       egui-rad-builder import <code.rs> [-o <design.json>]
+
+Without arguments the GUI builder is started.
+
+commands:
+    generate    generate Rust code from a saved design, to stdout or the -o file
+    export      write a ready to build Cargo project for the design to <dir>
+    import      rebuild a design from generated code, to stdout or the -o file
+
+options:
+    -o, --output <file>    write the code (or design) to <file> instead of stdout
+        --check            don't write anything, exit with 1 if <file> is not up to date
+        --force            export even if <dir> is not empty, overwriting files
+    -h, --help             print this help
+
+exit codes:
+    0  success      1  --check found stale output    2  usage error
+    3  design could not be read or parsed              4  code generation failed
+    5  output could not be written";
+
+struct Generate {
+    design: PathBuf,
+    output: Option<PathBuf>,
+    check: bool,
+}
+
+struct Import {
+    code: PathBuf,
+    output: Option<PathBuf>,
+}
+
+struct Export {
+    design: PathBuf,
+    dir: PathBuf,
+    force: bool,
+}
+
+/// Handle the command line, `None` if the GUI should start instead
+pub(crate) fn run() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (command, rest) = args.split_first()?;
+    Some(match command.as_str() {
+        "generate" => match parse_generate(rest) {
+            Ok(cmd) => generate(&cmd),
+            Err(message) => usage_error(&message),
+        },
+        "export" => match parse_export(rest) {
+            Ok(cmd) => export(&cmd),
+            Err(message) => usage_error(&message),
+        },
+        "import" => match parse_import(rest) {
+            Ok(cmd) => import(&cmd),
+            Err(message) => usage_error(&message),
+        },
+        "-h" | "--help" | "help" => {
+            println!("{USAGE}");
+            EXIT_OK
+        }
+        other => usage_error(&format!("unknown command `{other}`")),
+    })
+}
+
+fn usage_error(message: &str) -> i32 {
+    eprintln!("error: {message}\n\n{USAGE}");
+    EXIT_USAGE
+}
+
+fn parse_generate(args: &[String]) -> Result<Generate, String> {
+    let mut design = None;
+    let mut output = None;
+    let mut check = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => {
+                let path = args.next().ok_or_else(|| format!("`{arg}` needs a file name"))?;
+                output = Some(PathBuf::from(path));
+            }
+            "--check" => check = true,
+            flag if flag.starts_with('-') && flag != "-" => {
+                return Err(format!("unknown option `{flag}`"));
+            }
+            path if design.is_none() => design = Some(PathBuf::from(path)),
+            extra => return Err(format!("unexpected argument `{extra}`")),
+        }
+    }
+    let design = design.ok_or("missing design file")?;
+    if check && output.is_none() {
+        return Err("`--check` needs the file to compare against, pass it with -o".into());
+    }
+    Ok(Generate { design, output, check })
+}
+
+fn parse_export(args: &[String]) -> Result<Export, String> {
+    let mut paths = Vec::new();
+    let mut force = false;
+    for arg in args {
+        match arg.as_str() {
+            "--force" => force = true,
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{flag}`")),
+            path => paths.push(PathBuf::from(path)),
+        }
+    }
+    match <[PathBuf; 2]>::try_from(paths) {
+        Ok([design, dir]) => Ok(Export { design, dir, force }),
+        Err(_) => Err("export needs a design file and a target directory".into()),
+    }
+}
+
+fn parse_import(args: &[String]) -> Result<Import, String> {
+    let mut code = None;
+    let mut output = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => {
+                let path = args.next().ok_or_else(|| format!("`{arg}` needs a file name"))?;
+                output = Some(PathBuf::from(path));
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{flag}`")),
+            path if code.is_none() => code = Some(PathBuf::from(path)),
+            extra => return Err(format!("unexpected argument `{extra}`")),
+        }
+    }
+    let code = code.ok_or("missing code file")?;
+    Ok(Import { code, output })
+}
+
+fn load(design: &Path) -> Result<Project, i32> {
+    Project::load(design).map_err(|e| {
+        eprintln!("error: could not read design {}: {e}", design.display());
+        EXIT_PARSE
+    })
+}
+
+fn export(cmd: &Export) -> i32 {
+    let project = match load(&cmd.design) {
+        Ok(project) => project,
+        Err(code) => return code,
+    };
+    match export::write_cargo_project(&project, &cmd.dir, cmd.force) {
+        Ok(()) => EXIT_OK,
+        Err(ExportError::Generate(e)) => {
+            eprintln!("error: could not generate code from {}: {e}", cmd.design.display());
+            EXIT_GENERATE
+        }
+        Err(ExportError::NotEmpty(dir)) => {
+            eprintln!("error: {} is not empty, pass --force to overwrite", dir.display());
+            EXIT_WRITE
+        }
+        Err(e) => {
+            eprintln!("error: could not export to {}: {e}", cmd.dir.display());
+            EXIT_WRITE
+        }
+    }
+}
+
+fn import(cmd: &Import) -> i32 {
+    let imported = match fs::read_to_string(&cmd.code) {
+        Ok(source) => import::from_rust(&source).map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    let imported = match imported {
+        Ok(imported) => imported,
+        Err(e) => {
+            eprintln!("error: could not import {}: {e}", cmd.code.display());
+            return EXIT_PARSE;
+        }
+    };
+    for skipped in &imported.unrecognized {
+        eprintln!("warning: {}: not imported, {skipped}", cmd.code.display());
+    }
+
+    let written = match &cmd.output {
+        Some(output) => imported.project.save(output).map_err(|e| (output.display().to_string(), e.to_string())),
+        None => imported
+            .project
+            .to_json()
+            .map_err(|e| e.to_string())
+            .and_then(|json| writeln!(std::io::stdout(), "{json}").map_err(|e| e.to_string()))
+            .map_err(|e| ("stdout".to_owned(), e)),
+    };
+    match written {
+        Ok(()) => EXIT_OK,
+        Err((target, e)) => {
+            eprintln!("error: could not write {target}: {e}");
+            EXIT_WRITE
+        }
+    }
+}
+
+fn generate(cmd: &Generate) -> i32 {
+    let project = match load(&cmd.design) {
+        Ok(project) => project,
+        Err(code) => return code,
+    };
+    let code = match codegen::generate(&project) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: could not generate code from {}: {e}", cmd.design.display());
+            return EXIT_GENERATE;
+        }
+    };
+
+    let Some(output) = &cmd.output else {
+        return match std::io::stdout().write_all(code.as_bytes()) {
+            Ok(()) => EXIT_OK,
+            Err(e) => {
+                eprintln!("error: could not write to stdout: {e}");
+                EXIT_WRITE
+            }
+        };
+    };
+    // keep the hand-written code in the protected regions of the existing file
+    let code = match regions::merge_with_file(&code, output) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: could not merge the user regions of {}: {e}", output.display());
+            return EXIT_GENERATE;
+        }
+    };
+    if cmd.check {
+        return match fs::read_to_string(output) {
+            Ok(existing) if existing == code => EXIT_OK,
+            Ok(_) => {
+                eprintln!(
+                    "{} is out of date, regenerate it from {}",
+                    output.display(),
+                    cmd.design.display()
+                );
+                EXIT_STALE
+            }
+            Err(e) => {
+                eprintln!("{} could not be read: {e}", output.display());
+                EXIT_STALE
+            }
+        };
+    }
+    match fs::write(output, code) {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("error: could not write {}: {e}", output.display());
+            EXIT_WRITE
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Copy and paste widgets through the system clipboard as JSON.
+
+use crate::{project::Project, widget::Widget};
+use copypasta::ClipboardProvider;
+
+/// Serialize widgets the way they are pasted back
+pub(crate) fn to_json(widgets: &[Widget]) -> Option<String> {
+    serde_json::to_string_pretty(widgets).ok()
+}
+
+/// Read widgets from a widget list, a single widget, or a whole project, which brings
+/// its assets along
+pub(crate) fn from_json(text: &str) -> Option<Project> {
+    if let Ok(widgets) = serde_json::from_str::<Vec<Widget>>(text) {
+        return Some(Project { widgets, ..Project::default() });
+    }
+    if let Ok(widget) = serde_json::from_str::<Widget>(text) {
+        return Some(Project { widgets: vec![widget], ..Project::default() });
+    }
+    Project::from_json(text).ok()
+}
+
+/// Lazily opened system clipboard, kept open so X11 can keep serving what we copied
+#[derive(Default)]
+pub(crate) struct Clipboard {
+    ctx: Option<copypasta::ClipboardContext>,
+}
+
+impl Clipboard {
+    fn provider(&mut self) -> Option<&mut copypasta::ClipboardContext> {
+        if self.ctx.is_none() {
+            self.ctx = copypasta::ClipboardContext::new().ok();
+        }
+        self.ctx.as_mut()
+    }
+
+    pub(crate) fn set_text(&mut self, text: String) -> bool {
+        self.provider().is_some_and(|ctx| ctx.set_contents(text).is_ok())
+    }
+
+    pub(crate) fn get_text(&mut self) -> Option<String> {
+        self.provider()?.get_contents().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{WidgetId, WidgetKind};
+
+    const BUTTON: &str =
+        r#"{"id": 7, "kind": {"t": "Button"}, "pos": [10, 20], "size": [80, 24], "z": 7, "area": "Center"}"#;
+
+    #[test]
+    fn reads_a_widget_list() {
+        let label = BUTTON.replace("Button", "Label").replace("\"id\": 7", "\"id\": 8");
+        let pasted = from_json(&format!("[{BUTTON}, {label}]")).unwrap();
+        let kinds: Vec<_> = pasted.widgets.iter().map(|w| (w.id, w.kind.clone())).collect();
+        assert_eq!(kinds, [(WidgetId::new(7), WidgetKind::Button), (WidgetId::new(8), WidgetKind::Label)]);
+        assert!(pasted.assets.is_empty());
+    }
+
+    #[test]
+    fn reads_a_single_widget() {
+        let pasted = from_json(BUTTON).unwrap();
+        assert_eq!(pasted.widgets.len(), 1);
+        assert_eq!(pasted.widgets[0].pos, egui::pos2(10.0, 20.0));
+    }
+
+    #[test]
+    fn reads_a_whole_project_with_its_assets() {
+        let json = format!(r#"{{"widgets": [{BUTTON}], "assets": [{{"name": "logo.png", "path": "img/logo.png"}}]}}"#);
+        let pasted = from_json(&json).unwrap();
+        assert_eq!(pasted.widgets.len(), 1);
+        assert_eq!(pasted.asset("logo.png").unwrap().path, std::path::Path::new("img/logo.png"));
+    }
+
+    #[test]
+    fn round_trips_copied_widgets() {
+        let widgets = from_json(BUTTON).unwrap().widgets;
+        assert_eq!(from_json(&to_json(&widgets).unwrap()).unwrap().widgets, widgets);
+    }
+
+    #[test]
+    fn rejects_other_text() {
+        assert!(from_json("hello").is_none());
+        assert!(from_json("[1, 2]").is_none());
+    }
+}
@@ -0,0 +1,744 @@
+//! Turns a [`Project`] into Rust source code for an egui app.
+
+pub mod regions;
+mod requirements;
+
+use crate::{
+    project::{Asset, Project},
+    widget::{ColumnType, ColumnWidth, DockArea, Widget, WidgetId, WidgetKind},
+};
+use chrono::NaiveDate;
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt};
+
+pub use requirements::{Dependency, Helper, Requirements};
+
+/// What the generator emits besides the state struct and UI function
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutputMode {
+    /// A runnable program with a demo `eframe::App` and `main`
+    #[default]
+    App,
+    /// Only the state struct and UI function, to be used from an existing app
+    Module,
+}
+
+impl OutputMode {
+    pub const ALL: [OutputMode; 2] = [OutputMode::App, OutputMode::Module];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            OutputMode::App => "App with main",
+            OutputMode::Module => "Module only",
+        }
+    }
+}
+
+/// Visibility of the generated items in [`OutputMode::Module`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Visibility {
+    Private,
+    PubCrate,
+    #[default]
+    Pub,
+}
+
+impl Visibility {
+    pub const ALL: [Visibility; 3] = [Visibility::Private, Visibility::PubCrate, Visibility::Pub];
+
+    /// The keyword as written before an item
+    pub fn tokens(self) -> TokenStream {
+        match self {
+            Visibility::Private => TokenStream::new(),
+            Visibility::PubCrate => quote! { pub(crate) },
+            Visibility::Pub => quote! { pub },
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Visibility::Private => "private",
+            Visibility::PubCrate => "pub(crate)",
+            Visibility::Pub => "pub",
+        }
+    }
+}
+
+/// How generated code gets at the image files of the project's assets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssetMode {
+    /// Compiled into the binary with `include_bytes!`, from `assets/` of the crate
+    #[default]
+    Embed,
+    /// Loaded at runtime from `assets/` of the working directory
+    Load,
+}
+
+impl AssetMode {
+    pub const ALL: [AssetMode; 2] = [AssetMode::Embed, AssetMode::Load];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AssetMode::Embed => "Embed in the binary",
+            AssetMode::Load => "Load from files",
+        }
+    }
+}
+
+/// Per project generator settings, saved with the design
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CodegenSettings {
+    pub output: OutputMode,
+    /// Name of the generated state struct
+    pub state_name: String,
+    /// Name of the generated UI function
+    pub ui_fn_name: String,
+    /// Name of the enum of widget events the UI function returns
+    pub event_enum_name: String,
+    pub visibility: Visibility,
+    /// How image assets are included
+    pub assets: AssetMode,
+}
+
+impl Default for CodegenSettings {
+    fn default() -> Self {
+        Self {
+            output: OutputMode::App,
+            state_name: "GeneratedState".into(),
+            ui_fn_name: "generated_ui".into(),
+            event_enum_name: "UiEvent".into(),
+            visibility: Visibility::Pub,
+            assets: AssetMode::Embed,
+        }
+    }
+}
+
+/// Whether `name` can be used as a Rust identifier (ASCII only, not a keyword)
+pub fn is_identifier(name: &str) -> bool {
+    const KEYWORDS: &[&str] = &[
+        "_", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+        "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+        "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+        "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !KEYWORDS.contains(&name)
+}
+
+/// Why a project couldn't be turned into code
+#[derive(Debug)]
+pub enum GenerateError {
+    /// A widget has properties that can't be turned into working code
+    InvalidWidget { id: WidgetId, message: String },
+    /// A name in the [`CodegenSettings`] isn't a valid identifier
+    InvalidName { what: &'static str, name: String },
+    /// An asset can't be included under its name
+    InvalidAsset { name: String, message: String },
+    /// The canvas size isn't a pair of positive numbers
+    InvalidCanvas { size: egui::Vec2 },
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::InvalidWidget { id, message } => write!(f, "widget {id}: {message}"),
+            GenerateError::InvalidName { what, name } => {
+                write!(f, "the {what} name `{name}` is not a valid Rust identifier")
+            }
+            GenerateError::InvalidAsset { name, message } => write!(f, "asset {name:?}: {message}"),
+            GenerateError::InvalidCanvas { size } => {
+                write!(f, "the canvas size {} × {} must be positive numbers", size.x, size.y)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+/// Fields of the state struct that are always generated
+const RESERVED_FIELDS: [&str; 4] = ["enable_top", "enable_bottom", "enable_left", "enable_right"];
+
+/// Why `widget` can't be known by its name (or derived field name) in `project`
+pub fn name_problem(project: &Project, widget: &Widget) -> Option<String> {
+    if !widget.name.is_empty() && !is_identifier(&widget.name) {
+        return Some(format!("`{}` is not a valid Rust identifier", widget.name));
+    }
+    let ident = widget.ident()?;
+    if RESERVED_FIELDS.contains(&ident.as_str()) {
+        return Some(format!("`{ident}` is reserved for the generated code"));
+    }
+    project
+        .widgets
+        .iter()
+        .find(|w| w.id != widget.id && w.ident().as_ref() == Some(&ident))
+        .map(|other| format!("`{ident}` is already used by widget {}", other.id))
+}
+
+/// Reject projects that would produce code that doesn't compile or panics at startup
+fn validate(project: &Project, settings: &CodegenSettings) -> Result<(), GenerateError> {
+    let mut variants = HashMap::new();
+    let names = [
+        ("state struct", &settings.state_name),
+        ("UI function", &settings.ui_fn_name),
+        ("event enum", &settings.event_enum_name),
+    ];
+    for (what, name) in names {
+        if !is_identifier(name) {
+            return Err(GenerateError::InvalidName { what, name: name.clone() });
+        }
+    }
+    let size = project.canvas_size;
+    if !(size.x.is_finite() && size.y.is_finite() && size.min_elem() > 0.0) {
+        return Err(GenerateError::InvalidCanvas { size });
+    }
+    for (i, asset) in project.assets.iter().enumerate() {
+        let invalid = |message: String| GenerateError::InvalidAsset { name: asset.name.clone(), message };
+        if let Some(problem) = Asset::name_problem(&asset.name) {
+            return Err(invalid(problem));
+        }
+        if project.assets[..i].iter().any(|a| a.name == asset.name) {
+            return Err(invalid("another asset has the same name".into()));
+        }
+    }
+    // derived fields and row structs are named after their widget, so the names themselves can clash
+    let mut fields: HashMap<String, WidgetId> =
+        project.widgets.iter().filter_map(|w| Some((w.ident()?, w.id))).collect();
+    let mut types = HashMap::new();
+    for w in &project.widgets {
+        let invalid = |message: String| GenerateError::InvalidWidget { id: w.id, message };
+        if let Some(problem) = name_problem(project, w) {
+            return Err(invalid(problem));
+        }
+        if let Some(parent) = w.parent {
+            match project.widget(parent) {
+                None => return Err(invalid(format!("its container {parent} doesn't exist"))),
+                Some(p) if !p.kind.is_container() => {
+                    return Err(invalid(format!("widget {parent} can't hold other widgets")));
+                }
+                Some(_) if project.is_within(parent, w.id) => {
+                    return Err(invalid("it is placed inside itself".into()));
+                }
+                Some(_) => {}
+            }
+        }
+        for field in w.derived_fields() {
+            if RESERVED_FIELDS.contains(&field.as_str()) {
+                return Err(invalid(format!("field `{field}` is reserved for the generated code")));
+            }
+            match fields.insert(field.clone(), w.id) {
+                Some(other) if other == w.id => {
+                    return Err(invalid(format!("two series would both be stored in `{field}`")));
+                }
+                Some(other) => return Err(invalid(format!("field `{field}` is already used by widget {other}"))),
+                None => {}
+            }
+        }
+        if w.props.series.iter().flat_map(|s| s.points.iter().flatten()).any(|v| !v.is_finite()) {
+            return Err(invalid("plot data must be finite numbers".into()));
+        }
+        if let Some(row) = w.row_struct() {
+            let builtin = [&settings.state_name, &settings.event_enum_name, "GeneratedApp", "GenTreeNode"];
+            if builtin.contains(&row.as_str()) {
+                return Err(invalid(format!("the row struct `{row}` would clash with the generated code")));
+            }
+            if let Some(other) = types.insert(row.clone(), w.id) {
+                return Err(invalid(format!("the row struct `{row}` is also generated for widget {other}")));
+            }
+            if w.props.columns.is_empty() {
+                return Err(invalid("a table needs at least one column".into()));
+            }
+            let mut widths = w.props.columns.iter().filter_map(|c| match c.width {
+                ColumnWidth::Initial(width) | ColumnWidth::Exact(width) => Some(width),
+                ColumnWidth::Auto | ColumnWidth::Remainder => None,
+            });
+            if widths.any(|width| !(width.is_finite() && width >= 0.0)) {
+                return Err(invalid("column widths must be positive numbers".into()));
+            }
+            let columns: Vec<_> = (0..w.props.columns.len()).filter_map(|i| w.column_field(i)).collect();
+            if let Some((i, field)) = columns.iter().enumerate().find(|(i, f)| columns[..*i].contains(f)) {
+                return Err(invalid(format!("column {} would be stored in `{field}` like an earlier one", i + 1)));
+            }
+            for (r, cells) in w.props.rows.iter().enumerate() {
+                for (column, cell) in w.props.columns.iter().zip(cells) {
+                    if let Some(problem) = column.ty.parse_problem(cell) {
+                        return Err(invalid(format!("row {}, column {:?}: {problem}", r + 1, column.header)));
+                    }
+                }
+            }
+        }
+        for event in w.active_events() {
+            let variant = w.event_variant(event);
+            if let Some(other) = variants.insert(variant.clone(), w.id)
+                && other != w.id
+            {
+                return Err(invalid(format!("event `{variant}` is also reported by widget {other}")));
+            }
+        }
+        let numbers = [w.pos.x, w.pos.y, w.size.x, w.size.y, w.props.value, w.props.min, w.props.max];
+        if numbers.iter().any(|v| !v.is_finite()) {
+            return Err(invalid("position, size and values must be finite numbers".into()));
+        }
+        if w.uses_image() && project.asset(&w.props.image).is_none() {
+            return Err(invalid(match w.props.image.as_str() {
+                "" => "no image asset chosen".into(),
+                name => format!("there is no asset {name:?}"),
+            }));
+        }
+        if w.kind.flows() && w.props.spacing.is_some_and(|s| !(s.x.is_finite() && s.y.is_finite() && s.min_elem() >= 0.0)) {
+            return Err(invalid("the spacing must be positive numbers".into()));
+        }
+        if w.uses_image() && !(w.props.scale.is_finite() && w.props.scale > 0.0) {
+            return Err(invalid("the image scale must be a positive number".into()));
+        }
+        if matches!(w.kind, WidgetKind::DatePicker) {
+            // mirrors the clamping done when emitting the date
+            let (y, m, d) = (w.props.year, w.props.month.clamp(1, 12), w.props.day.clamp(1, 28));
+            if NaiveDate::from_ymd_opt(y, m, d).is_none() {
+                return Err(invalid(format!("{y}-{m:02}-{d:02} is not a valid date")));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Generate code for `project` as set up in its [`CodegenSettings`]: by default a
+/// complete program (state, UI function and a demo `main`)
+pub fn generate(project: &Project) -> Result<String, GenerateError> {
+    generate_with(project, &project.codegen)
+}
+
+/// Generate only the state struct and UI function, whatever the project's output mode,
+/// to be included in an existing app (see [`include_rad!`](crate::include_rad))
+pub fn generate_module(project: &Project) -> Result<String, GenerateError> {
+    let settings = CodegenSettings {
+        output: OutputMode::Module,
+        ..project.codegen.clone()
+    };
+    generate_with(project, &settings)
+}
+
+/// `LoginClicked` -> `login_clicked`
+pub(crate) fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            out.push('_');
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    out
+}
+
+/// Whether any widget reports events, which makes the UI function return them
+pub fn has_events(project: &Project) -> bool {
+    project.widgets.iter().any(|w| w.active_events().next().is_some())
+}
+
+/// An identifier checked by [`validate`]
+pub(crate) fn ident(name: &str) -> Ident {
+    Ident::new(name, Span::call_site())
+}
+
+/// A float literal with a fixed number of decimals, e.g. `0.500`
+pub(crate) fn float(value: f32, decimals: usize) -> Literal {
+    format!("{value:.decimals$}").parse().expect("finite floats are valid literals")
+}
+
+/// Pretty print generated items like rustfmt would, with a blank line between items
+/// other than imports, and turn the region placeholders into protected regions
+pub(crate) fn format(items: TokenStream) -> String {
+    let file: syn::File = syn::parse2(items).expect("the generator builds valid items from validated names");
+    let mut out = String::new();
+    let mut after_use = false;
+    for item in file.items {
+        let is_use = matches!(item, syn::Item::Use(_));
+        // imports stay together
+        if !(out.is_empty() || (is_use && after_use)) {
+            out.push('\n');
+        }
+        after_use = is_use;
+        out.push_str(&prettyplease::unparse(&syn::File { shebang: None, attrs: Vec::new(), items: vec![item] }));
+    }
+    regions::expand(&out)
+}
+
+/// Type of the state field of `kind`
+fn state_type(kind: &WidgetKind) -> Option<TokenStream> {
+    Some(match kind {
+        WidgetKind::TextEdit | WidgetKind::Password => quote! { String },
+        WidgetKind::Checkbox | WidgetKind::SelectableLabel | WidgetKind::CollapsingHeader => quote! { bool },
+        WidgetKind::Slider | WidgetKind::ProgressBar | WidgetKind::AngleSelector => quote! { f32 },
+        WidgetKind::RadioGroup | WidgetKind::ComboBox | WidgetKind::MenuButton => quote! { usize },
+        WidgetKind::DatePicker => quote! { NaiveDate },
+        _ => return None,
+    })
+}
+
+/// Initial value of the state field of `w`
+fn initial_value(w: &Widget) -> Option<TokenStream> {
+    Some(match w.kind {
+        WidgetKind::TextEdit | WidgetKind::Password => {
+            let text = Literal::string(&w.props.text);
+            quote! { #text.to_owned() }
+        }
+        WidgetKind::Checkbox | WidgetKind::SelectableLabel | WidgetKind::CollapsingHeader => {
+            let checked = w.props.checked;
+            quote! { #checked }
+        }
+        WidgetKind::Slider | WidgetKind::AngleSelector => {
+            let value = float(w.props.value, 3);
+            quote! { #value }
+        }
+        WidgetKind::ProgressBar => {
+            let value = float(w.props.value.clamp(0.0, 1.0), 3);
+            quote! { #value }
+        }
+        WidgetKind::RadioGroup | WidgetKind::ComboBox | WidgetKind::MenuButton => {
+            let sel = if w.props.items.is_empty() {
+                0
+            } else {
+                w.props.selected.min(w.props.items.len() - 1)
+            };
+            let sel = Literal::usize_unsuffixed(sel);
+            quote! { #sel }
+        }
+        WidgetKind::DatePicker => {
+            let y = Literal::i32_unsuffixed(w.props.year);
+            let m = Literal::u32_unsuffixed(w.props.month.clamp(1, 12));
+            let d = Literal::u32_unsuffixed(w.props.day.clamp(1, 28));
+            quote! { NaiveDate::from_ymd_opt(#y, #m, #d).unwrap() }
+        }
+        _ => return None,
+    })
+}
+
+/// Name, type and initial value of each state field of `w`
+fn state_fields(w: &Widget) -> Vec<(Ident, TokenStream, TokenStream)> {
+    if w.kind == WidgetKind::Plot {
+        return w
+            .props
+            .series
+            .iter()
+            .zip(w.series_fields())
+            .map(|(series, field)| {
+                let points = series.points.iter().map(|&[x, y]| {
+                    let (x, y) = (Literal::f64_unsuffixed(x), Literal::f64_unsuffixed(y));
+                    quote! { [#x, #y] }
+                });
+                // not `vec!`, whose tokens would be printed as `- 0.5`
+                (ident(&field), quote! { Vec<[f64; 2]> }, quote! { Vec::from([#(#points),*]) })
+            })
+            .collect();
+    }
+    if let (Some(field), Some(row)) = (w.state_field(), w.row_struct()) {
+        let row = ident(&row);
+        let rows = w.props.rows.iter().map(|cells| {
+            let values = w.props.columns.iter().enumerate().map(|(i, column)| {
+                let field = ident(&w.column_field(i).unwrap_or_default());
+                let cell = cells.get(i).map_or("", |cell| cell.trim());
+                // blank cells are the type's default, see `ColumnType::parse_problem`
+                let value = match column.ty {
+                    ColumnType::Text => {
+                        let text = Literal::string(cells.get(i).map_or("", String::as_str));
+                        quote! { #text.to_owned() }
+                    }
+                    ColumnType::Integer => {
+                        let value = Literal::i64_unsuffixed(cell.parse().unwrap_or_default());
+                        quote! { #value }
+                    }
+                    ColumnType::Number => {
+                        let value = Literal::f64_unsuffixed(cell.parse().unwrap_or_default());
+                        quote! { #value }
+                    }
+                    ColumnType::Bool => {
+                        let value = cell.parse::<bool>().unwrap_or_default();
+                        quote! { #value }
+                    }
+                };
+                quote! { #field: #value }
+            });
+            quote! { #row { #(#values),* } }
+        });
+        let mut fields = vec![(ident(&field), quote! { Vec<#row> }, quote! { Vec::from([#(#rows),*]) })];
+        if let Some(sort) = w.sort_field() {
+            fields.push((ident(&sort), quote! { Option<(usize, bool)> }, quote! { None }));
+        }
+        return fields;
+    }
+    let (Some(field), Some(ty), Some(value)) = (w.state_field(), state_type(&w.kind), initial_value(w)) else {
+        return Vec::new();
+    };
+    vec![(ident(&field), ty, value)]
+}
+
+/// The struct holding a row of table `w`, and with sorting a comparison by column
+fn row_struct(w: &Widget, vis: &TokenStream) -> Option<TokenStream> {
+    let row = ident(&w.row_struct()?);
+    let fields: Vec<_> = (0..w.props.columns.len())
+        .map(|i| ident(&w.column_field(i).unwrap_or_default()))
+        .collect();
+    let types = w.props.columns.iter().map(|column| match column.ty {
+        ColumnType::Text => quote! { String },
+        ColumnType::Integer => quote! { i64 },
+        ColumnType::Number => quote! { f64 },
+        ColumnType::Bool => quote! { bool },
+    });
+    let mut items = quote! {
+        #[derive(Clone, Debug, PartialEq)]
+        #vis struct #row {
+            #(#vis #fields: #types,)*
+        }
+    };
+    if w.props.sortable {
+        let arms = w.props.columns.iter().zip(&fields).enumerate().map(|(i, (column, field))| {
+            let i = Literal::usize_unsuffixed(i);
+            // f64 is only partially ordered
+            let cmp = ident(if column.ty == ColumnType::Number { "total_cmp" } else { "cmp" });
+            quote! { #i => self.#field.#cmp(&other.#field), }
+        });
+        items.extend(quote! {
+            impl #row {
+                fn cmp_by(&self, other: &Self, column: usize) -> std::cmp::Ordering {
+                    match column {
+                        #(#arms)*
+                        _ => std::cmp::Ordering::Equal,
+                    }
+                }
+            }
+        });
+    }
+    Some(items)
+}
+
+/// The statements of `w` with those of the widgets placed in it
+fn emit_nested(
+    project: &Project,
+    w: &Widget,
+    origin: Option<&TokenStream>,
+    event_enum: &Ident,
+    assets: AssetMode,
+) -> TokenStream {
+    // children are placed relative to the top left of the container's content
+    let inner = (!w.kind.flows()).then(|| quote! { ui.min_rect().min });
+    let children: Vec<_> = project
+        .children(w.id)
+        .map(|child| emit_nested(project, child, inner.as_ref(), event_enum, assets))
+        .collect();
+    w.emit_widget(origin, &children, event_enum, assets)
+}
+
+/// Generate code for `project` with `settings` instead of the ones saved in the project
+pub fn generate_with(project: &Project, settings: &CodegenSettings) -> Result<String, GenerateError> {
+    use DockArea::*;
+
+    validate(project, settings)?;
+    let module = settings.output == OutputMode::Module;
+    // in a standalone program visibility doesn't matter, keep it short
+    let vis = if module { settings.visibility.tokens() } else { TokenStream::new() };
+    let state_name = ident(&settings.state_name);
+    let ui_fn = ident(&settings.ui_fn_name);
+    let event_enum = ident(&settings.event_enum_name);
+    let events = has_events(project);
+    let mut items = TokenStream::new();
+
+    let requirements = Requirements::of(project);
+    for import in &requirements.imports {
+        let path: syn::Path = syn::parse_str(import).expect("imports are valid paths");
+        items.extend(quote! { use #path; });
+    }
+    for helper in &requirements.helpers {
+        items.extend(helper.tokens());
+    }
+    for w in &project.widgets {
+        items.extend(row_struct(w, &vis));
+    }
+
+    let fields = project
+        .widgets
+        .iter()
+        .flat_map(state_fields)
+        .map(|(field, ty, _)| quote! { #vis #field: #ty });
+    let region = regions::field_placeholder("state_fields");
+    items.extend(quote! {
+        #vis struct #state_name {
+            #vis enable_top: bool,
+            #vis enable_bottom: bool,
+            #vis enable_left: bool,
+            #vis enable_right: bool,
+            #(#fields,)*
+            #region: (),
+        }
+    });
+
+    let variants: Vec<_> = project
+        .widgets
+        .iter()
+        .flat_map(|w| w.active_events().map(move |event| w.event_variant(event)))
+        .collect();
+    if events {
+        let variants = variants.iter().map(|v| ident(v));
+        items.extend(quote! {
+            #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+            #vis enum #event_enum {
+                #(#variants,)*
+            }
+        });
+    }
+
+    let enable_top = project.panel_top_enabled;
+    let enable_bottom = project.panel_bottom_enabled;
+    let enable_left = project.panel_left_enabled;
+    let enable_right = project.panel_right_enabled;
+    let inits = project
+        .widgets
+        .iter()
+        .flat_map(state_fields)
+        .map(|(field, _, value)| quote! { #field: #value });
+    let region = regions::field_placeholder("state_defaults");
+    items.extend(quote! {
+        impl Default for #state_name {
+            fn default() -> Self {
+                Self {
+                    enable_top: #enable_top,
+                    enable_bottom: #enable_bottom,
+                    enable_left: #enable_left,
+                    enable_right: #enable_right,
+                    #(#inits,)*
+                    #region: (),
+                }
+            }
+        }
+    });
+
+    if events {
+        let arms = variants.iter().map(|variant| {
+            let region = regions::placeholder(&format!("on_{}", snake_case(variant)));
+            let variant = ident(variant);
+            quote! { #event_enum::#variant => { #region } }
+        });
+        items.extend(quote! {
+            impl #state_name {
+                #vis fn handle_event(&mut self, event: #event_enum) {
+                    match event {
+                        #(#arms)*
+                    }
+                }
+            }
+        });
+    }
+
+    // widgets of the docked panels are placed relative to the panel, the others on the canvas
+    let emit = |areas: &[DockArea], origin: TokenStream| -> Vec<TokenStream> {
+        areas
+            .iter()
+            .flat_map(|area| project.widgets.iter().filter(move |w| w.area == *area && w.parent.is_none()))
+            .map(|w| emit_nested(project, w, Some(&origin), &event_enum, settings.assets))
+            .collect()
+    };
+    let panel = |enabled: &str, panel: TokenStream, area: DockArea| {
+        let enabled = ident(enabled);
+        let widgets = emit(&[area], quote! { ui.min_rect().min });
+        // keep unused closure arguments quiet
+        let ui = ident(if widgets.is_empty() { "_ui" } else { "ui" });
+        quote! {
+            if state.#enabled {
+                #panel.resizable(true).show(ctx, |#ui| {
+                    #(#widgets)*
+                });
+            }
+        }
+    };
+    let top = panel("enable_top", quote! { egui::TopBottomPanel::top("gen_top") }, Top);
+    let bottom = panel("enable_bottom", quote! { egui::TopBottomPanel::bottom("gen_bottom") }, Bottom);
+    let left = panel("enable_left", quote! { egui::SidePanel::left("gen_left") }, Left);
+    let right = panel("enable_right", quote! { egui::SidePanel::right("gen_right") }, Right);
+    // CENTER (+ FREE): use CentralPanel; widgets are placed absolutely within it.
+    let center = emit(&[Center, Free], quote! { canvas.min });
+    let (canvas_w, canvas_h) = (float(project.canvas_size.x, 1), float(project.canvas_size.y, 1));
+
+    let (returns, collect, result) = if events {
+        (
+            quote! { -> Vec<#event_enum> },
+            quote! { let mut events: Vec<#event_enum> = Vec::new(); },
+            quote! { events },
+        )
+    } else {
+        Default::default()
+    };
+    let region = regions::placeholder("items");
+    items.extend(quote! {
+        #vis fn #ui_fn(ctx: &egui::Context, state: &mut #state_name) #returns {
+            #collect
+            #top
+            #bottom
+            #left
+            #right
+            egui::CentralPanel::default().show(ctx, |ui| {
+                // fixed logical canvas (keeps your designed size)
+                let canvas = egui::Rect::from_min_size(ui.min_rect().min, egui::vec2(#canvas_w, #canvas_h));
+                let _ = ui.allocate_painter(canvas.size(), egui::Sense::hover());
+                #(#center)*
+            });
+            #result
+        }
+
+        #region
+    });
+
+    if !module {
+        // ---------- Example eframe app (updated to call the UI function with ctx) ----------
+        let call = if events {
+            quote! {
+                for event in #ui_fn(ctx, &mut self.state) {
+                    self.state.handle_event(event);
+                }
+            }
+        } else {
+            quote! { #ui_fn(ctx, &mut self.state); }
+        };
+        let create = if requirements.image_loaders {
+            quote! {
+                |cc| {
+                    egui_extras::install_image_loaders(&cc.egui_ctx);
+                    Ok(Box::new(GeneratedApp::default()))
+                }
+            }
+        } else {
+            quote! { |_cc| Ok(Box::new(GeneratedApp::default())) }
+        };
+        items.extend(quote! {
+            pub struct GeneratedApp {
+                state: #state_name,
+            }
+
+            impl Default for GeneratedApp {
+                fn default() -> Self {
+                    Self { state: Default::default() }
+                }
+            }
+
+            impl eframe::App for GeneratedApp {
+                fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+                    #call
+                }
+            }
+
+            fn main() -> eframe::Result<()> {
+                let native_options = eframe::NativeOptions::default();
+                eframe::run_native(
+                    "Generated UI",
+                    native_options,
+                    Box::new(#create),
+                )
+            }
+        });
+    }
+
+    Ok(format!("// --- generated by egui RAD GUI Builder ---\n{}", format(items)))
+}
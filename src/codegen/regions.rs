@@ -0,0 +1,295 @@
+//! Protected regions: hand-written code between `// rad:user-begin <name>` and
+//! `// rad:user-end` survives regeneration.
+//!
+//! The generator emits empty regions wherever user code makes sense (extra state
+//! fields, event handlers, free items). Comments don't survive token streams, so it
+//! builds placeholders instead that [`expand`] turns into regions once the code is
+//! pretty printed. [`merge`] copies the contents of every
+//! region found in the existing file into the freshly generated code. Regions that
+//! no longer exist in the new code (e.g. the handler of a deleted widget) are kept,
+//! commented out, at the end of the file so nothing is lost.
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use std::{collections::HashMap, fmt, io, ops::RangeInclusive, path::Path};
+
+const BEGIN: &str = "// rad:user-begin ";
+const END: &str = "// rad:user-end";
+const ORPHANED: &str = "// rad:orphaned user regions, their place in the generated code is gone";
+const PLACEHOLDER: &str = "__rad_region";
+
+/// A malformed region in an existing file
+#[derive(Debug, PartialEq, Eq)]
+pub struct RegionError {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for RegionError {}
+
+/// The begin marker of region `name` at `indent`, followed by the end marker
+fn empty(indent: &str, name: &str) -> String {
+    format!("{indent}{BEGIN}{name}\n{indent}{END}\n")
+}
+
+/// Statement or item standing in for region `name`: `__rad_region!(name);`
+pub(crate) fn placeholder(name: &str) -> TokenStream {
+    let name = Ident::new(name, Span::call_site());
+    let mac = Ident::new(PLACEHOLDER, Span::call_site());
+    quote! { #mac!(#name); }
+}
+
+/// Field name standing in for region `name` in a struct or struct expression, to be
+/// given a `()` type or value: `__rad_region_name: ()`
+pub(crate) fn field_placeholder(name: &str) -> Ident {
+    Ident::new(&format!("{PLACEHOLDER}_{name}"), Span::call_site())
+}
+
+/// Replace the placeholder lines of pretty printed code with empty regions
+pub(crate) fn expand(code: &str) -> String {
+    let mut out = String::with_capacity(code.len());
+    for line in code.split_inclusive('\n') {
+        let trimmed = line.trim();
+        let name = trimmed
+            .strip_prefix(PLACEHOLDER)
+            .and_then(|rest| rest.strip_prefix("!(")?.strip_suffix(");"))
+            .or_else(|| trimmed.strip_prefix(PLACEHOLDER)?.strip_prefix('_')?.strip_suffix(": (),"));
+        match name {
+            Some(name) => out.push_str(&empty(&line[..line.len() - line.trim_start().len()], name)),
+            None => out.push_str(line),
+        }
+    }
+    out
+}
+
+struct Region<'a> {
+    name: &'a str,
+    /// 1-based lines of the begin and end markers
+    lines: RangeInclusive<usize>,
+    /// Lines between the markers, each with its line ending
+    body: Vec<&'a str>,
+}
+
+/// Every region of `source` in order of appearance
+fn parse(source: &str) -> Result<Vec<Region<'_>>, RegionError> {
+    let mut regions = Vec::new();
+    let mut open: Option<(usize, Region)> = None;
+    for (i, line) in source.split_inclusive('\n').enumerate() {
+        let trimmed = line.trim();
+        let error = |message: String| RegionError { line: i + 1, message };
+        // trimming drops the space after a begin marker without a name
+        let begin = trimmed
+            .strip_prefix(BEGIN.trim_end())
+            .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace));
+        if let Some(name) = begin {
+            let name = name.trim();
+            if let Some((_, region)) = &open {
+                return Err(error(format!("region `{name}` starts inside region `{}`", region.name)));
+            }
+            if name.is_empty() {
+                return Err(error("region without a name".into()));
+            }
+            if regions.iter().any(|r: &Region| r.name == name) {
+                return Err(error(format!("region `{name}` appears twice")));
+            }
+            open = Some((i, Region { name, lines: i + 1..=i + 1, body: Vec::new() }));
+        } else if trimmed == END {
+            match open.take() {
+                Some((begin, region)) => regions.push(Region { lines: begin + 1..=i + 1, ..region }),
+                None => return Err(error("region end without a begin".into())),
+            }
+        } else if let Some((_, region)) = &mut open {
+            region.body.push(line);
+        }
+    }
+    match open {
+        Some((line, region)) => Err(RegionError {
+            line: line + 1,
+            message: format!("region `{}` is never closed", region.name),
+        }),
+        None => Ok(regions),
+    }
+}
+
+/// The 1-based lines of every region in `source`, markers included
+pub(crate) fn line_ranges(source: &str) -> Result<Vec<RangeInclusive<usize>>, RegionError> {
+    Ok(parse(source)?.into_iter().map(|r| r.lines).collect())
+}
+
+/// Copy the user regions of `existing` into `generated`
+pub fn merge(generated: &str, existing: &str) -> Result<String, RegionError> {
+    let regions = parse(existing)?;
+    let order: Vec<&str> = regions.iter().map(|r| r.name).collect();
+    let mut kept: HashMap<&str, Vec<&str>> = regions
+        .into_iter()
+        .filter(|r| !r.body.is_empty())
+        .map(|r| (r.name, r.body))
+        .collect();
+
+    let mut out = String::with_capacity(generated.len() + existing.len());
+    let mut skipping = false;
+    for line in generated.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if skipping && trimmed != END {
+            continue;
+        }
+        skipping = false;
+        out.push_str(line);
+        if let Some(name) = trimmed.strip_prefix(BEGIN)
+            && let Some(body) = kept.remove(name.trim())
+        {
+            body.iter().for_each(|l| out.push_str(l));
+            // drop whatever the generator put inside
+            skipping = true;
+        }
+    }
+
+    let orphaned: Vec<&str> = order.into_iter().filter(|name| kept.contains_key(name)).collect();
+    if !orphaned.is_empty() {
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(&format!("\n{ORPHANED}\n/*\n"));
+        for name in orphaned {
+            out.push_str(&format!("{BEGIN}{name}\n"));
+            kept[name].iter().for_each(|l| out.push_str(l));
+            if !out.ends_with('\n') {
+                out.push('\n');
+            }
+            out.push_str(&format!("{END}\n"));
+        }
+        out.push_str("*/\n");
+    }
+    Ok(out)
+}
+
+/// `generated` with the regions of the file at `path` merged in, unchanged if there is no file yet
+pub fn merge_with_file(generated: &str, path: &Path) -> io::Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(existing) => merge(generated, &existing).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(generated.into()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GENERATED: &str = "\
+struct State {
+    // rad:user-begin state_fields
+    // rad:user-end
+}
+fn on_click() {
+    // rad:user-begin on_click
+    // rad:user-end
+}
+";
+
+    #[test]
+    fn expands_placeholders_into_empty_regions() {
+        let code = "struct S {\n    __rad_region_fields: (),\n}\n    __rad_region!(items);\n";
+        assert_eq!(
+            expand(code),
+            "struct S {\n    // rad:user-begin fields\n    // rad:user-end\n}\n    // rad:user-begin items\n    // rad:user-end\n"
+        );
+    }
+
+    #[test]
+    fn merges_regions_back_by_name() {
+        // in another order than generated, with the generator's own contents replaced
+        let existing = "\
+fn on_click() {
+    // rad:user-begin on_click
+    println!(\"clicked\");
+    // rad:user-end
+}
+struct State {
+    // rad:user-begin state_fields
+    count: u32,
+    // rad:user-end
+}
+";
+        let generated = GENERATED.replace("    // rad:user-begin on_click\n", "    // rad:user-begin on_click\n    todo!();\n");
+        let merged = merge(&generated, existing).unwrap();
+        assert_eq!(
+            merged,
+            GENERATED
+                .replace("state_fields\n", "state_fields\n    count: u32,\n")
+                .replace("on_click\n", "on_click\n    println!(\"clicked\");\n")
+        );
+        // merging again changes nothing
+        assert_eq!(merge(&merged, &merged).unwrap(), merged);
+    }
+
+    #[test]
+    fn empty_regions_leave_the_generated_code_alone() {
+        assert_eq!(merge(GENERATED, GENERATED).unwrap(), GENERATED);
+        assert_eq!(merge(GENERATED, "").unwrap(), GENERATED);
+    }
+
+    #[test]
+    fn orphaned_regions_go_into_a_trailing_comment() {
+        let existing = "\
+// rad:user-begin on_gone
+gone();
+// rad:user-end
+// rad:user-begin on_click
+kept();
+// rad:user-end
+// rad:user-begin on_empty
+// rad:user-end
+";
+        let merged = merge(GENERATED, existing).unwrap();
+        assert!(merged.starts_with(&GENERATED.replace("on_click\n", "on_click\nkept();\n")));
+        assert!(merged.ends_with(&format!(
+            "\n{ORPHANED}\n/*\n// rad:user-begin on_gone\ngone();\n// rad:user-end\n*/\n"
+        )));
+    }
+
+    #[test]
+    fn malformed_regions_are_errors() {
+        let error = |source: &str| merge(GENERATED, source).unwrap_err();
+        assert_eq!(
+            error("// rad:user-begin a\n// rad:user-begin b\n// rad:user-end\n"),
+            RegionError { line: 2, message: "region `b` starts inside region `a`".into() }
+        );
+        assert_eq!(
+            error("\n// rad:user-begin a\nfn f() {}\n"),
+            RegionError { line: 2, message: "region `a` is never closed".into() }
+        );
+        assert_eq!(error("x\n// rad:user-end\n").line, 2);
+        assert_eq!(error("// rad:user-begin \n// rad:user-end\n").line, 1);
+        let twice = "// rad:user-begin a\n// rad:user-end\n// rad:user-begin a\n// rad:user-end\n";
+        assert_eq!(error(twice), RegionError { line: 3, message: "region `a` appears twice".into() });
+    }
+
+    #[test]
+    fn reports_the_lines_of_regions() {
+        assert_eq!(line_ranges(GENERATED).unwrap(), vec![2..=3, 6..=7]);
+    }
+
+    #[test]
+    fn merges_with_a_file_if_there_is_one() {
+        let path = std::env::temp_dir().join(format!("rad-regions-{}.rs", std::process::id()));
+        assert_eq!(merge_with_file(GENERATED, &path).unwrap(), GENERATED);
+
+        let edited = GENERATED.replace("on_click\n", "on_click\n    kept();\n");
+        std::fs::write(&path, &edited).unwrap();
+        let merged = merge_with_file(GENERATED, &path);
+        std::fs::write(&path, "// rad:user-end\n").unwrap();
+        let malformed = merge_with_file(GENERATED, &path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(merged.unwrap(), edited);
+        assert_eq!(malformed.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,169 @@
+//! What the code generated for a project needs: imports, helper items and crates.
+
+use crate::{
+    codegen::AssetMode,
+    project::{ImageFormat, Project},
+    widget::{Widget, WidgetKind},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Version of the egui crates the generated code is written against, same as the builder's
+const EGUI_VERSION: &str = "0.33";
+const CHRONO_VERSION: &str = "0.4";
+/// egui_plot is released separately, this is the version for [`EGUI_VERSION`]
+const EGUI_PLOT_VERSION: &str = "0.34";
+/// Version of the `image` crate egui_extras decodes PNG and JPEG with
+const IMAGE_VERSION: &str = "0.25";
+
+/// Items emitted once before the state struct when a widget needs them
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Helper {
+    /// `GenTreeNode` and `gen_show_tree` for [`WidgetKind::Tree`]
+    Tree,
+    /// `gen_sort_header` for tables sorted by clicking a header
+    SortHeader,
+}
+
+impl Helper {
+    /// The helper items
+    pub fn tokens(self) -> TokenStream {
+        match self {
+            Helper::Tree => quote! {
+                #[derive(Clone)]
+                struct GenTreeNode {
+                    label: String,
+                    children: Vec<GenTreeNode>,
+                }
+
+                fn gen_show_tree(ui: &mut egui::Ui, nodes: &[GenTreeNode]) {
+                    for n in nodes {
+                        if n.children.is_empty() {
+                            ui.label(&n.label);
+                        } else {
+                            ui.collapsing(&n.label, |ui| gen_show_tree(ui, &n.children));
+                        }
+                    }
+                }
+            },
+            Helper::SortHeader => quote! {
+                fn gen_sort_header(ui: &mut egui::Ui, label: &str, column: usize, sort: &mut Option<(usize, bool)>) -> bool {
+                    let arrow = match *sort {
+                        Some((c, true)) if c == column => " ⬆",
+                        Some((c, false)) if c == column => " ⬇",
+                        _ => "",
+                    };
+                    let header = egui::Label::new(egui::RichText::new(format!("{label}{arrow}")).strong());
+                    if !ui.add(header.sense(egui::Sense::click())).clicked() {
+                        return false;
+                    }
+                    *sort = match *sort {
+                        Some((c, ascending)) if c == column => Some((column, !ascending)),
+                        _ => Some((column, true)),
+                    };
+                    true
+                }
+            },
+        }
+    }
+}
+
+/// A crate the generated code depends on
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dependency {
+    pub version: &'static str,
+    pub features: BTreeSet<&'static str>,
+    /// Whether the crate's default features are enabled too
+    pub default_features: bool,
+}
+
+/// Everything the generated code depends on, so the preamble and manifests only
+/// mention what is actually used
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Requirements {
+    /// Paths for the `use` declarations at the top of the generated code
+    pub imports: BTreeSet<&'static str>,
+    pub helpers: BTreeSet<Helper>,
+    /// Crates by name
+    pub crates: BTreeMap<&'static str, Dependency>,
+    /// Whether `egui_extras::install_image_loaders` has to run before the UI is shown
+    pub image_loaders: bool,
+}
+
+impl Requirements {
+    /// Collect the requirements of every widget in `project`
+    pub fn of(project: &Project) -> Self {
+        let mut req = Self::default();
+        req.import("eframe::egui");
+        req.depend("eframe", EGUI_VERSION, &[]);
+        for w in &project.widgets {
+            req.add_widget(w);
+            req.add_image(w, project.codegen.assets);
+        }
+        req
+    }
+
+    fn add_image(&mut self, w: &Widget, assets: AssetMode) {
+        if !w.uses_image() {
+            return;
+        }
+        self.image_loaders = true;
+        self.depend("egui_extras", EGUI_VERSION, &[]);
+        if assets == AssetMode::Load {
+            self.depend("egui_extras", EGUI_VERSION, &["file"]);
+        }
+        match ImageFormat::of(&w.props.image) {
+            Some(ImageFormat::Svg) => self.depend("egui_extras", EGUI_VERSION, &["svg"]),
+            Some(format) => {
+                self.depend("egui_extras", EGUI_VERSION, &["image"]);
+                let feature = if format == ImageFormat::Png { "png" } else { "jpeg" };
+                self.depend("image", IMAGE_VERSION, &[feature]);
+                // only the decoders of the formats in use
+                if let Some(image) = self.crates.get_mut("image") {
+                    image.default_features = false;
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn add_widget(&mut self, w: &Widget) {
+        match w.kind {
+            WidgetKind::DatePicker => {
+                self.import("egui_extras::DatePickerButton");
+                self.import("chrono::NaiveDate");
+                self.depend("egui_extras", EGUI_VERSION, &["chrono"]);
+                self.depend("chrono", CHRONO_VERSION, &[]);
+            }
+            WidgetKind::Tree => {
+                self.helpers.insert(Helper::Tree);
+            }
+            WidgetKind::Plot => {
+                self.depend("egui_plot", EGUI_PLOT_VERSION, &[]);
+            }
+            WidgetKind::Strip => {
+                self.depend("egui_extras", EGUI_VERSION, &[]);
+            }
+            WidgetKind::Table => {
+                self.depend("egui_extras", EGUI_VERSION, &[]);
+                if w.props.sortable {
+                    self.helpers.insert(Helper::SortHeader);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn import(&mut self, path: &'static str) {
+        self.imports.insert(path);
+    }
+
+    fn depend(&mut self, name: &'static str, version: &'static str, features: &[&'static str]) {
+        self.crates
+            .entry(name)
+            .or_insert_with(|| Dependency { version, features: BTreeSet::new(), default_features: true })
+            .features
+            .extend(features);
+    }
+}
@@ -0,0 +1,197 @@
+//! Export a design as a complete Cargo project that builds and runs as is.
+//!
+//! ```text
+//! <dir>/Cargo.toml
+//! <dir>/.gitignore
+//! <dir>/src/main.rs    eframe app calling the UI function
+//! <dir>/src/ui.rs      the generated module
+//! <dir>/assets/        copies of the project's image assets
+//! ```
+
+use crate::{
+    codegen::{self, CodegenSettings, GenerateError, OutputMode, Requirements, Visibility, regions},
+    project::{ASSET_DIR, Project},
+};
+use quote::quote;
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+/// Failure to export a Cargo project
+#[derive(Debug)]
+pub enum ExportError {
+    Generate(GenerateError),
+    /// The target directory already has files in it
+    NotEmpty(PathBuf),
+    /// An asset couldn't be copied from its path
+    Asset { path: PathBuf, error: io::Error },
+    Io(io::Error),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Generate(e) => write!(f, "{e}"),
+            ExportError::NotEmpty(dir) => write!(f, "{} is not empty", dir.display()),
+            ExportError::Asset { path, error } => write!(f, "could not copy asset {}: {error}", path.display()),
+            ExportError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<GenerateError> for ExportError {
+    fn from(e: GenerateError) -> Self {
+        ExportError::Generate(e)
+    }
+}
+
+impl From<io::Error> for ExportError {
+    fn from(e: io::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+/// The source files of the exported project as (path relative to the project root, contents),
+/// without the assets
+pub fn cargo_project(project: &Project, name: &str) -> Result<Vec<(&'static str, String)>, GenerateError> {
+    let settings = CodegenSettings {
+        output: OutputMode::Module,
+        // main.rs lives outside the module and has to reach the generated items
+        visibility: match project.codegen.visibility {
+            Visibility::Private => Visibility::PubCrate,
+            vis => vis,
+        },
+        ..project.codegen.clone()
+    };
+    let ui = codegen::generate_with(project, &settings)?;
+    Ok(vec![
+        ("Cargo.toml", manifest(project, name)),
+        (".gitignore", "/target\n".into()),
+        ("src/main.rs", main_rs(&settings, codegen::has_events(project), Requirements::of(project).image_loaders, name)),
+        ("src/ui.rs", ui),
+    ])
+}
+
+/// Write the exported project to `dir`, which must not exist or be empty unless `overwrite` is set
+pub fn write_cargo_project(project: &Project, dir: impl AsRef<Path>, overwrite: bool) -> Result<(), ExportError> {
+    let dir = dir.as_ref();
+    if !overwrite && dir.read_dir().is_ok_and(|mut entries| entries.next().is_some()) {
+        return Err(ExportError::NotEmpty(dir.into()));
+    }
+    let files = cargo_project(project, &package_name(dir))?;
+    for (path, contents) in files {
+        let path = dir.join(path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // keep what was written in the protected regions of an earlier export
+        let contents = if path.extension().is_some_and(|ext| ext == "rs") {
+            regions::merge_with_file(&contents, &path)?
+        } else {
+            contents
+        };
+        std::fs::write(path, contents)?;
+    }
+    let assets = dir.join(ASSET_DIR);
+    for asset in &project.assets {
+        let copy = std::fs::create_dir_all(&assets).and_then(|()| std::fs::copy(&asset.path, assets.join(&asset.name)));
+        if let Err(error) = copy {
+            return Err(ExportError::Asset { path: asset.path.clone(), error });
+        }
+    }
+    Ok(())
+}
+
+/// A valid Cargo package name derived from the directory name
+pub fn package_name(dir: &Path) -> String {
+    let raw = dir.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    let name: String = raw
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let name = name.trim_matches('-');
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name.into()
+    } else {
+        "generated-ui".into()
+    }
+}
+
+fn manifest(project: &Project, name: &str) -> String {
+    let mut out = format!(
+        "[package]\n\
+         name = \"{name}\"\n\
+         version = \"0.1.0\"\n\
+         edition = \"2024\"\n\
+         \n\
+         [dependencies]\n"
+    );
+    for (krate, dep) in Requirements::of(project).crates {
+        if dep.features.is_empty() && dep.default_features {
+            out.push_str(&format!("{krate} = \"{}\"\n", dep.version));
+            continue;
+        }
+        let mut spec = format!("version = \"{}\"", dep.version);
+        if !dep.default_features {
+            spec.push_str(", default-features = false");
+        }
+        if !dep.features.is_empty() {
+            let features: Vec<String> = dep.features.iter().map(|f| format!("\"{f}\"")).collect();
+            spec.push_str(&format!(", features = [{}]", features.join(", ")));
+        }
+        out.push_str(&format!("{krate} = {{ {spec} }}\n"));
+    }
+    out
+}
+
+fn main_rs(settings: &CodegenSettings, events: bool, image_loaders: bool, name: &str) -> String {
+    let state = codegen::ident(&settings.state_name);
+    let ui_fn = codegen::ident(&settings.ui_fn_name);
+    // the handlers live in the protected regions of ui.rs
+    let call = if events {
+        quote! {
+            for event in ui::#ui_fn(ctx, &mut self.state) {
+                self.state.handle_event(event);
+            }
+        }
+    } else {
+        quote! { ui::#ui_fn(ctx, &mut self.state); }
+    };
+    let create = if image_loaders {
+        quote! {
+            |cc| {
+                egui_extras::install_image_loaders(&cc.egui_ctx);
+                Ok(Box::new(App::default()))
+            }
+        }
+    } else {
+        quote! { |_cc| Ok(Box::new(App::default())) }
+    };
+    codegen::format(quote! {
+        mod ui;
+
+        use eframe::egui;
+
+        #[derive(Default)]
+        struct App {
+            state: ui::#state,
+        }
+
+        impl eframe::App for App {
+            fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+                #call
+            }
+        }
+
+        fn main() -> eframe::Result<()> {
+            eframe::run_native(
+                #name,
+                eframe::NativeOptions::default(),
+                Box::new(#create),
+            )
+        }
+    })
+}
@@ -0,0 +1,270 @@
+//! Smart guides: snap a dragged rect to the edges, centers and spacing of its neighbours.
+//!
+//! All rects are in the local coordinates of one [`DockArea`](crate::widget::DockArea),
+//! the returned guide lines too.
+
+use egui::{Pos2, Rect, Vec2, pos2};
+
+/// How close (in points) an edge has to be before it snaps
+pub(crate) const SNAP_DISTANCE: f32 = 6.0;
+
+/// Which edges of the dragged rect may snap
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Edges {
+    /// Moving: left/right/top/bottom edges and centers
+    All,
+    /// Resizing from the bottom-right handle: only the right and bottom edges
+    MaxOnly,
+}
+
+#[derive(Default)]
+pub(crate) struct Snap {
+    /// Correction to add to the dragged rect, zero on axes that didn't snap
+    pub(crate) delta: Vec2,
+    pub(crate) snapped_x: bool,
+    pub(crate) snapped_y: bool,
+    /// Line segments to draw while the snap is active
+    pub(crate) guides: Vec<[Pos2; 2]>,
+}
+
+/// What a candidate snap on one axis lines up with
+enum Target {
+    /// An edge or center of a neighbour at `at`, spanning `across` on the other axis
+    Line { at: f32, across: (f32, f32) },
+    /// Equal spacing: the gaps (min, max) that end up the same size, and where to draw them
+    Spacing { gaps: Vec<(f32, f32)>, across: f32 },
+}
+
+/// Find the closest snap for `moving` against `others` on both axes
+pub(crate) fn snap(moving: Rect, others: &[Rect], edges: Edges) -> Snap {
+    let mut result = Snap::default();
+    for axis in 0..2 {
+        let Some((delta, target)) = snap_axis(moving, others, edges, axis) else {
+            continue;
+        };
+        let snapped = translate(moving, axis, delta, edges);
+        match target {
+            Target::Line { at, across } => {
+                let (lo, hi) = range(snapped, 1 - axis);
+                let (lo, hi) = (lo.min(across.0), hi.max(across.1));
+                result.guides.push([point(axis, at, lo), point(axis, at, hi)]);
+            }
+            Target::Spacing { gaps, across } => {
+                for (lo, hi) in gaps {
+                    result.guides.push([point(1 - axis, across, lo), point(1 - axis, across, hi)]);
+                }
+            }
+        }
+        if axis == 0 {
+            result.delta.x = delta;
+            result.snapped_x = true;
+        } else {
+            result.delta.y = delta;
+            result.snapped_y = true;
+        }
+    }
+    result
+}
+
+fn snap_axis(moving: Rect, others: &[Rect], edges: Edges, axis: usize) -> Option<(f32, Target)> {
+    let (min, max) = range(moving, axis);
+    let sources: Vec<f32> = match edges {
+        Edges::All => vec![min, center(moving, axis), max],
+        Edges::MaxOnly => vec![max],
+    };
+
+    let mut best: Option<(f32, Target)> = None;
+    let mut consider = |delta: f32, target: Target| {
+        if delta.abs() <= SNAP_DISTANCE && best.as_ref().is_none_or(|(d, _)| delta.abs() < d.abs()) {
+            best = Some((delta, target));
+        }
+    };
+
+    for other in others {
+        for line in lines(*other, axis) {
+            for source in &sources {
+                let across = range(*other, 1 - axis);
+                consider(line - source, Target::Line { at: line, across });
+            }
+        }
+    }
+
+    if edges == Edges::All {
+        // neighbours sharing a row (or column) with the dragged rect
+        let mut row: Vec<Rect> = others
+            .iter()
+            .copied()
+            .filter(|o| overlaps(range(*o, 1 - axis), range(moving, 1 - axis)))
+            .collect();
+        row.sort_by(|a, b| range(*a, axis).0.total_cmp(&range(*b, axis).0));
+        let across = center(moving, 1 - axis);
+        let size = max - min;
+        for pair in row.windows(2) {
+            let (a, b) = (range(pair[0], axis), range(pair[1], axis));
+            let gap = b.0 - a.1;
+            if gap <= 0.0 {
+                continue;
+            }
+            // continue the series after `b`
+            consider(
+                b.1 + gap - min,
+                Target::Spacing { gaps: vec![(a.1, b.0), (b.1, b.1 + gap)], across },
+            );
+            // continue the series before `a`
+            consider(
+                a.0 - gap - max,
+                Target::Spacing { gaps: vec![(a.0 - gap, a.0), (a.1, b.0)], across },
+            );
+            // sit centered in the gap between them
+            if gap > size {
+                let space = (gap - size) * 0.5;
+                consider(
+                    a.1 + space - min,
+                    Target::Spacing {
+                        gaps: vec![(a.1, a.1 + space), (b.0 - space, b.0)],
+                        across,
+                    },
+                );
+            }
+        }
+    }
+    best
+}
+
+/// Edges and center of `r` along `axis`
+fn lines(r: Rect, axis: usize) -> [f32; 3] {
+    let (min, max) = range(r, axis);
+    [min, (min + max) * 0.5, max]
+}
+
+fn range(r: Rect, axis: usize) -> (f32, f32) {
+    if axis == 0 {
+        (r.min.x, r.max.x)
+    } else {
+        (r.min.y, r.max.y)
+    }
+}
+
+fn center(r: Rect, axis: usize) -> f32 {
+    let (min, max) = range(r, axis);
+    (min + max) * 0.5
+}
+
+fn overlaps(a: (f32, f32), b: (f32, f32)) -> bool {
+    a.0 < b.1 && b.0 < a.1
+}
+
+/// Point with `along` on `axis` and `across` on the other one
+fn point(axis: usize, along: f32, across: f32) -> Pos2 {
+    if axis == 0 {
+        pos2(along, across)
+    } else {
+        pos2(across, along)
+    }
+}
+
+fn translate(r: Rect, axis: usize, delta: f32, edges: Edges) -> Rect {
+    let v = if axis == 0 {
+        Vec2::new(delta, 0.0)
+    } else {
+        Vec2::new(0.0, delta)
+    };
+    match edges {
+        Edges::All => r.translate(v),
+        Edges::MaxOnly => Rect::from_min_max(r.min, r.max + v),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32, w: f32, h: f32) -> Rect {
+        Rect::from_min_size(pos2(x, y), Vec2::new(w, h))
+    }
+
+    #[test]
+    fn snaps_to_a_neighbours_edge() {
+        let snap = snap(rect(103.0, 200.0, 20.0, 20.0), &[rect(100.0, 100.0, 60.0, 20.0)], Edges::All);
+        assert!(snap.snapped_x && !snap.snapped_y);
+        assert_eq!(snap.delta, Vec2::new(-3.0, 0.0));
+        // spanning both rects
+        assert_eq!(snap.guides, vec![[pos2(100.0, 100.0), pos2(100.0, 220.0)]]);
+    }
+
+    #[test]
+    fn snaps_centers() {
+        let snap = snap(rect(122.0, 200.0, 20.0, 20.0), &[rect(100.0, 100.0, 60.0, 20.0)], Edges::All);
+        assert_eq!(snap.delta.x, -2.0);
+    }
+
+    #[test]
+    fn snaps_on_both_axes() {
+        let snap = snap(rect(2.0, 105.0, 20.0, 20.0), &[rect(0.0, 0.0, 20.0, 100.0)], Edges::All);
+        assert!(snap.snapped_x && snap.snapped_y);
+        assert_eq!(snap.delta, Vec2::new(-2.0, -5.0));
+        assert_eq!(snap.guides.len(), 2);
+    }
+
+    #[test]
+    fn only_snaps_within_the_snap_distance() {
+        let other = [rect(100.0, 100.0, 60.0, 20.0)];
+        let near = snap(rect(100.0 + SNAP_DISTANCE, 200.0, 10.0, 20.0), &other, Edges::All);
+        assert_eq!(near.delta.x, -SNAP_DISTANCE);
+        let far = snap(rect(100.0 + SNAP_DISTANCE + 1.0, 200.0, 10.0, 20.0), &other, Edges::All);
+        assert!(!far.snapped_x && far.guides.is_empty());
+    }
+
+    #[test]
+    fn picks_the_closest_candidate() {
+        let others = [rect(96.0, 300.0, 200.0, 20.0), rect(102.0, 400.0, 200.0, 20.0)];
+        let snap = snap(rect(100.0, 0.0, 10.0, 20.0), &others, Edges::All);
+        assert_eq!(snap.delta.x, 2.0);
+    }
+
+    #[test]
+    fn resizing_only_snaps_the_far_edges() {
+        let other = [rect(100.0, 100.0, 50.0, 20.0)];
+        let moving = rect(103.0, 300.0, 40.0, 10.0);
+        assert!(snap(moving, &other, Edges::All).snapped_x);
+        assert!(!snap(moving, &other, Edges::MaxOnly).snapped_x);
+
+        let snap = snap(rect(103.0, 300.0, 44.0, 10.0), &other, Edges::MaxOnly);
+        assert_eq!(snap.delta.x, 3.0);
+        // from the neighbour down to the grown rect
+        assert_eq!(snap.guides, vec![[pos2(150.0, 100.0), pos2(150.0, 310.0)]]);
+    }
+
+    #[test]
+    fn continues_equal_spacing_after_a_row() {
+        let others = [rect(0.0, 0.0, 20.0, 20.0), rect(40.0, 0.0, 20.0, 20.0)];
+        let snap = snap(rect(83.0, 0.0, 20.0, 20.0), &others, Edges::All);
+        assert_eq!(snap.delta.x, -3.0);
+        assert!(snap.guides.contains(&[pos2(20.0, 10.0), pos2(40.0, 10.0)]));
+        assert!(snap.guides.contains(&[pos2(60.0, 10.0), pos2(80.0, 10.0)]));
+    }
+
+    #[test]
+    fn continues_equal_spacing_before_a_row() {
+        let others = [rect(100.0, 0.0, 20.0, 20.0), rect(140.0, 0.0, 20.0, 20.0)];
+        let snap = snap(rect(57.0, 0.0, 20.0, 20.0), &others, Edges::All);
+        assert_eq!(snap.delta.x, 3.0);
+        assert!(snap.guides.contains(&[pos2(80.0, 10.0), pos2(100.0, 10.0)]));
+    }
+
+    #[test]
+    fn centers_in_the_gap_between_neighbours() {
+        let others = [rect(0.0, 0.0, 20.0, 20.0), rect(100.0, 0.0, 20.0, 20.0)];
+        let snap = snap(rect(53.0, 0.0, 20.0, 20.0), &others, Edges::All);
+        assert_eq!(snap.delta.x, -3.0);
+        assert!(snap.guides.contains(&[pos2(20.0, 10.0), pos2(50.0, 10.0)]));
+        assert!(snap.guides.contains(&[pos2(70.0, 10.0), pos2(100.0, 10.0)]));
+    }
+
+    #[test]
+    fn spacing_needs_a_shared_row() {
+        // the pair sits below the dragged rect, so their gap doesn't apply to it
+        let others = [rect(0.0, 100.0, 20.0, 20.0), rect(40.0, 100.0, 20.0, 20.0)];
+        assert!(!snap(rect(83.0, 0.0, 20.0, 20.0), &others, Edges::All).snapped_x);
+    }
+}
@@ -0,0 +1,210 @@
+//! Undo/redo history for edits made to the [`Project`].
+//!
+//! Rather than instrumenting every place that mutates the project, the history
+//! compares the project against the last committed snapshot once per frame.
+//! Changes are only committed once the user's interaction has settled (no pointer
+//! button held, no text field focused), so a continuous drag or a burst of typing
+//! collapses into a single entry.
+
+use crate::{project::Project, widget::Widget};
+use std::collections::HashMap;
+
+/// Maximum number of undo steps kept in memory
+const MAX_ENTRIES: usize = 200;
+
+struct Entry {
+    /// Human readable description, e.g. "Move Button"
+    label: String,
+    /// The project as it was before this edit
+    before: Project,
+}
+
+#[derive(Default)]
+pub(crate) struct History {
+    undo: Vec<Entry>,
+    redo: Vec<Entry>,
+    /// The project as of the last committed edit, `None` until the first observation
+    baseline: Option<Project>,
+}
+
+impl History {
+    /// Forget all entries and start tracking from `project`
+    pub(crate) fn reset(&mut self, project: &Project) {
+        self.undo.clear();
+        self.redo.clear();
+        self.baseline = Some(project.clone());
+    }
+
+    /// Called once per frame; commits pending changes once `settled` is true
+    pub(crate) fn observe(&mut self, project: &Project, settled: bool) {
+        let Some(baseline) = &self.baseline else {
+            self.baseline = Some(project.clone());
+            return;
+        };
+        if !settled || baseline == project {
+            return;
+        }
+        match describe(baseline, project) {
+            Some(label) => self.push(label, project),
+            // nothing the user would call an edit (e.g. z-sorting), absorb silently
+            None => self.baseline = Some(project.clone()),
+        }
+    }
+
+    /// Commit the current state immediately under an explicit label. Call
+    /// [`commit_pending`](Self::commit_pending) before making the edit, so changes still
+    /// waiting to settle get an entry of their own instead of being folded into this one.
+    pub(crate) fn record(&mut self, label: impl Into<String>, project: &Project) {
+        if self.baseline.as_ref() != Some(project) {
+            self.push(label.into(), project);
+        }
+    }
+
+    fn push(&mut self, label: String, project: &Project) {
+        let before = self.baseline.replace(project.clone()).unwrap_or_default();
+        self.undo.push(Entry { label, before });
+        if self.undo.len() > MAX_ENTRIES {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// Commit anything still pending so it can be undone on its own
+    pub(crate) fn commit_pending(&mut self, project: &Project) {
+        self.observe(project, true);
+    }
+
+    pub(crate) fn undo(&mut self, project: &mut Project) -> bool {
+        self.commit_pending(project);
+        let Some(entry) = self.undo.pop() else {
+            return false;
+        };
+        let after = std::mem::replace(project, entry.before);
+        self.baseline = Some(project.clone());
+        self.redo.push(Entry {
+            label: entry.label,
+            before: after,
+        });
+        true
+    }
+
+    pub(crate) fn redo(&mut self, project: &mut Project) -> bool {
+        self.commit_pending(project);
+        let Some(entry) = self.redo.pop() else {
+            return false;
+        };
+        let before = std::mem::replace(project, entry.before);
+        self.baseline = Some(project.clone());
+        self.undo.push(Entry {
+            label: entry.label,
+            before,
+        });
+        true
+    }
+
+    pub(crate) fn undo_label(&self) -> Option<&str> {
+        self.undo.last().map(|e| e.label.as_str())
+    }
+
+    pub(crate) fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|e| e.label.as_str())
+    }
+}
+
+/// Name the edit that turned `before` into `after`, `None` if it isn't user visible
+fn describe(before: &Project, after: &Project) -> Option<String> {
+    let old: HashMap<_, &Widget> = before.widgets.iter().map(|w| (w.id, w)).collect();
+    let new: HashMap<_, &Widget> = after.widgets.iter().map(|w| (w.id, w)).collect();
+
+    let added: Vec<&Widget> = after.widgets.iter().filter(|w| !old.contains_key(&w.id)).collect();
+    let removed: Vec<&Widget> = before.widgets.iter().filter(|w| !new.contains_key(&w.id)).collect();
+
+    match (added.as_slice(), removed.as_slice()) {
+        ([w], []) => return Some(format!("Add {:?}", w.kind)),
+        ([], [w]) => return Some(format!("Delete {:?}", w.kind)),
+        ([_, ..], []) => return Some(format!("Add {} widgets", added.len())),
+        ([], [_, ..]) => return Some(format!("Delete {} widgets", removed.len())),
+        ([_, ..], [_, ..]) => return Some("Replace widgets".into()),
+        ([], []) => {}
+    }
+
+    let changed: Vec<(&Widget, &Widget)> = after
+        .widgets
+        .iter()
+        .map(|w| (old[&w.id], w))
+        .filter(|(a, b)| a != b)
+        .collect();
+    let subject = match changed.as_slice() {
+        [] => {
+            return (before.canvas_size != after.canvas_size
+                || before.panel_top_enabled != after.panel_top_enabled
+                || before.panel_bottom_enabled != after.panel_bottom_enabled
+                || before.panel_left_enabled != after.panel_left_enabled
+                || before.panel_right_enabled != after.panel_right_enabled
+                || before.codegen != after.codegen)
+                .then(|| "Edit project settings".into());
+        }
+        [(_, w)] => format!("{:?}", w.kind),
+        _ => format!("{} widgets", changed.len()),
+    };
+
+    let verb = if changed.iter().any(|(a, b)| a.area != b.area) {
+        "Change dock area of"
+    } else if changed.iter().any(|(a, b)| a.props != b.props || a.events != b.events) {
+        "Edit"
+    } else if changed.iter().any(|(a, b)| a.name != b.name) {
+        "Rename"
+    } else if changed.iter().any(|(a, b)| a.size != b.size) {
+        "Resize"
+    } else if changed.iter().any(|(a, b)| a.pos != b.pos) {
+        "Move"
+    } else {
+        "Reorder"
+    };
+    Some(format!("{verb} {subject}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{DockArea, WidgetId, WidgetKind, WidgetProps};
+    use egui::{pos2, vec2};
+
+    fn button(id: u64) -> Widget {
+        Widget {
+            id: WidgetId::new(id),
+            kind: WidgetKind::Button,
+            pos: pos2(0.0, 0.0),
+            size: vec2(80.0, 24.0),
+            z: id as i32,
+            area: DockArea::Free,
+            parent: None,
+            props: WidgetProps::default(),
+            name: String::new(),
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn pending_edits_are_not_folded_into_labelled_ones() {
+        let mut project = Project { widgets: vec![button(1)], ..Project::default() };
+        let mut history = History::default();
+        history.observe(&project, true);
+
+        // a drag that hasn't settled yet
+        project.widgets[0].pos = pos2(40.0, 0.0);
+        history.observe(&project, false);
+        let moved = project.clone();
+
+        history.commit_pending(&project);
+        project.widgets.push(button(2));
+        history.record("Paste", &project);
+
+        assert_eq!(history.undo_label(), Some("Paste"));
+        assert!(history.undo(&mut project));
+        assert_eq!(project, moved);
+        assert_eq!(history.undo_label(), Some("Move Button"));
+        assert!(history.undo(&mut project));
+        assert_eq!(project.widgets, vec![button(1)]);
+    }
+}
@@ -0,0 +1,1241 @@
+//! Rebuild a [`Project`] from code written by the generator, for when the design file
+//! is lost but the generated `.rs` survives.
+//!
+//! Only the shapes the generator emits are understood: widgets placed with
+//! `ui.scope_builder(… egui::Rect::from_min_size(…) …)` inside the generated panels,
+//! with their initial values taken from the `Default` impl of the state struct.
+//! Everything else is reported as [`Unrecognized`] instead of being dropped silently.
+//! Code in protected regions is the user's own and is skipped, it stays in the `.rs`
+//! file and is merged back in whenever the file is regenerated.
+
+use crate::{
+    codegen::{self, AssetMode, OutputMode, Requirements, Visibility, regions::{self, RegionError}},
+    project::{ASSET_DIR, Asset, Project},
+    widget::{
+        ColumnAlign, ColumnType, ColumnWidth, DockArea, ImageFit, PlotSeries, SeriesStyle, TableColumn, Widget,
+        WidgetEvent, WidgetId, WidgetKind, WidgetProps,
+    },
+};
+use egui::{Align, Color32, Vec2, pos2, vec2};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    ops::{Neg, RangeInclusive},
+    path::Path,
+    str::FromStr,
+};
+use syn::{
+    BinOp, Block, Expr, FnArg, ImplItem, Item, ItemFn, ItemImpl, ItemStruct, Lit, Macro, Member, Pat, ReturnType,
+    Stmt, Type, UnOp, UseTree,
+    punctuated::Punctuated,
+    spanned::Spanned,
+    visit::{self, Visit},
+};
+
+/// The project found in a generated file
+#[derive(Debug)]
+pub struct Imported {
+    pub project: Project,
+    /// Code that was left out of the project because it isn't understood
+    pub unrecognized: Vec<Unrecognized>,
+}
+
+/// A piece of code the importer doesn't understand
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Unrecognized {
+    /// 1-based line number
+    pub line: usize,
+    /// The first line of the code
+    pub code: String,
+}
+
+impl fmt::Display for Unrecognized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.code)
+    }
+}
+
+/// Why a file couldn't be imported at all
+#[derive(Debug)]
+pub enum ImportError {
+    /// Not valid Rust
+    Syntax(syn::Error),
+    /// The protected regions are malformed
+    Regions(RegionError),
+    /// There is no function with the signature of a generated UI function
+    NoUiFunction,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Syntax(e) => write!(f, "line {}: {e}", e.span().start().line),
+            ImportError::Regions(e) => write!(f, "{e}"),
+            ImportError::NoUiFunction => write!(
+                f,
+                "no generated UI function found, expected `fn …(ctx: &egui::Context, state: &mut …)`"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<syn::Error> for ImportError {
+    fn from(e: syn::Error) -> Self {
+        ImportError::Syntax(e)
+    }
+}
+
+impl From<RegionError> for ImportError {
+    fn from(e: RegionError) -> Self {
+        ImportError::Regions(e)
+    }
+}
+
+/// Rebuild the project `source` was generated from
+pub fn from_rust(source: &str) -> Result<Imported, ImportError> {
+    let file = syn::parse_file(source)?;
+    let mut importer = Importer {
+        source,
+        regions: regions::line_ranges(source)?,
+        project: Project::default(),
+        unrecognized: Vec::new(),
+        found: Vec::new(),
+    };
+
+    // the UI function names the state struct and event enum
+    let (ui_fn, state_name, event_enum) = file
+        .items
+        .iter()
+        .find_map(|item| match item {
+            Item::Fn(f) => ui_fn_signature(f).map(|(state, events)| (f, state, events)),
+            _ => None,
+        })
+        .ok_or(ImportError::NoUiFunction)?;
+    let settings = &mut importer.project.codegen;
+    settings.ui_fn_name = ui_fn.sig.ident.to_string();
+    settings.state_name = state_name.clone();
+    if let Some(name) = &event_enum {
+        settings.event_enum_name = name.clone();
+    }
+    settings.visibility = match &ui_fn.vis {
+        syn::Visibility::Public(_) => Visibility::Pub,
+        syn::Visibility::Restricted(_) => Visibility::PubCrate,
+        syn::Visibility::Inherited => Visibility::Private,
+    };
+    settings.output = OutputMode::Module;
+
+    let mut fields = Vec::new();
+    let mut defaults = HashMap::new();
+    let mut imports = Vec::new();
+    let mut variants = Vec::new();
+    // the row structs of tables, known once the tables are found
+    let mut row_structs = Vec::new();
+    let mut row_impls = Vec::new();
+    for item in &file.items {
+        if importer.in_region(item) {
+            continue;
+        }
+        let known = match item {
+            Item::Use(u) => {
+                imports.push((use_path(&u.tree), item));
+                true
+            }
+            Item::Fn(f) if std::ptr::eq(f, ui_fn) => {
+                importer.ui_fn_body(&f.block);
+                true
+            }
+            Item::Struct(s) if s.ident == state_name => {
+                for field in &s.fields {
+                    if let Some(ident) = &field.ident
+                        && !ident.to_string().starts_with("enable_")
+                        && !importer.in_region(field)
+                    {
+                        fields.push((ident.to_string(), line(field)));
+                    }
+                }
+                true
+            }
+            Item::Impl(i) if type_name(&i.self_ty).as_deref() == Some(state_name.as_str()) => {
+                match &i.trait_ {
+                    Some((_, path, _)) if path.is_ident("Default") => importer.state_defaults(i, &mut defaults),
+                    // handle_event, its arms are protected regions
+                    Some(_) => importer.report(item),
+                    None => {
+                        for inner in &i.items {
+                            let handler = matches!(inner, ImplItem::Fn(f) if f.sig.ident == "handle_event");
+                            if !handler && !importer.in_region(inner) {
+                                importer.report(inner);
+                            }
+                        }
+                    }
+                }
+                true
+            }
+            Item::Enum(e) if event_enum.as_ref().is_some_and(|name| e.ident == name) => {
+                variants.extend(e.variants.iter().map(|v| v.ident.to_string()));
+                true
+            }
+            // helpers
+            Item::Struct(s) if s.ident == "GenTreeNode" || s.ident == "GeneratedApp" => true,
+            Item::Struct(s) => {
+                row_structs.push(s);
+                true
+            }
+            Item::Fn(f) if f.sig.ident == "main" => {
+                importer.project.codegen.output = OutputMode::App;
+                true
+            }
+            Item::Fn(f) => f.sig.ident == "gen_show_tree" || f.sig.ident == "gen_sort_header",
+            Item::Impl(i) if type_name(&i.self_ty).as_deref() == Some("GeneratedApp") => true,
+            Item::Impl(i) => {
+                row_impls.push(i);
+                true
+            }
+            _ => false,
+        };
+        if !known {
+            importer.report(item);
+        }
+    }
+    if importer.project.codegen.output == OutputMode::App {
+        // visibility doesn't show in an app, keep the default
+        importer.project.codegen.visibility = Visibility::default();
+    }
+
+    importer.row_structs(&row_structs, &row_impls);
+    let names: Vec<String> = fields.iter().map(|(name, _)| name.clone()).collect();
+    let used = importer.finish(defaults, &names, &variants);
+    for (field, line) in fields {
+        if !used.contains(&field) {
+            importer.report_line(line);
+        }
+    }
+    let needed = Requirements::of(&importer.project).imports;
+    for (path, item) in imports {
+        if !path.is_some_and(|p| needed.contains(p.as_str())) {
+            importer.report(item);
+        }
+    }
+    importer.unrecognized.sort_by_key(|u| u.line);
+    Ok(Imported { project: importer.project, unrecognized: importer.unrecognized })
+}
+
+/// A widget found in the UI function, before ids are handed out
+struct Found {
+    widget: Widget,
+    /// The state field it uses
+    field: Option<String>,
+    /// State fields named after the widget's own, see [`Widget::derived_fields`]
+    derived: Vec<String>,
+    /// The id spelled out in the code, e.g. by a `response_{id}` variable
+    id: Option<u64>,
+    /// Index of the container it is placed in
+    parent: Option<usize>,
+    /// `response_{id}` variable holding the widget's response
+    response: Option<String>,
+    /// Reported events with the variant reporting them
+    events: Vec<(WidgetEvent, String)>,
+}
+
+struct Importer<'a> {
+    source: &'a str,
+    regions: Vec<RangeInclusive<usize>>,
+    project: Project,
+    unrecognized: Vec<Unrecognized>,
+    found: Vec<Found>,
+}
+
+impl Importer<'_> {
+    fn in_region(&self, node: &impl Spanned) -> bool {
+        let line = line(node);
+        self.regions.iter().any(|r| r.contains(&line))
+    }
+
+    fn report(&mut self, node: &impl Spanned) {
+        self.report_line(line(node));
+    }
+
+    fn report_line(&mut self, line: usize) {
+        let code = self.source.lines().nth(line.saturating_sub(1)).unwrap_or_default().trim();
+        let code = match code.char_indices().nth(80) {
+            Some((end, _)) => format!("{}…", &code[..end]),
+            None => code.to_owned(),
+        };
+        self.unrecognized.push(Unrecognized { line, code });
+    }
+
+    /// The panel flags and initial values of the state fields
+    fn state_defaults(&mut self, imp: &syn::ItemImpl, defaults: &mut HashMap<String, Expr>) {
+        let body = imp.items.iter().find_map(|item| match item {
+            ImplItem::Fn(f) if f.sig.ident == "default" => f.block.stmts.last(),
+            _ => None,
+        });
+        let Some(Stmt::Expr(Expr::Struct(init), None)) = body else {
+            self.report(imp);
+            return;
+        };
+        for field in &init.fields {
+            let Member::Named(name) = &field.member else { continue };
+            if self.in_region(field) {
+                continue;
+            }
+            let flag = match name.to_string().as_str() {
+                "enable_top" => &mut self.project.panel_top_enabled,
+                "enable_bottom" => &mut self.project.panel_bottom_enabled,
+                "enable_left" => &mut self.project.panel_left_enabled,
+                "enable_right" => &mut self.project.panel_right_enabled,
+                name => {
+                    defaults.insert(name.to_owned(), field.expr.clone());
+                    continue;
+                }
+            };
+            *flag = boolean(&field.expr).unwrap_or_default();
+        }
+    }
+
+    fn ui_fn_body(&mut self, block: &Block) {
+        for stmt in &block.stmts {
+            if self.in_region(stmt) {
+                continue;
+            }
+            let known = match stmt {
+                Stmt::Local(local) => pat_ident(&local.pat).as_deref() == Some("events"),
+                // the returned events
+                Stmt::Expr(Expr::Path(p), None) => p.path.is_ident("events"),
+                // `if state.enable_top { … }`
+                Stmt::Expr(Expr::If(check), _) if check.else_branch.is_none() => {
+                    check.then_branch.stmts.iter().all(|s| matches!(s, Stmt::Expr(e, _) if self.panel(e)))
+                }
+                Stmt::Expr(e, _) => self.panel(e),
+                _ => false,
+            };
+            if !known {
+                self.report(stmt);
+            }
+        }
+    }
+
+    /// `egui::TopBottomPanel::top("gen_top")….show(ctx, |ui| { … })`
+    fn panel(&mut self, expr: &Expr) -> bool {
+        let Expr::MethodCall(show) = expr else { return false };
+        let Some(Expr::Closure(closure)) = show.args.iter().nth(1) else { return false };
+        let Expr::Block(body) = &*closure.body else { return false };
+        let mut root = &*show.receiver;
+        while let Expr::MethodCall(call) = root {
+            root = &call.receiver;
+        }
+        let Expr::Call(call) = root else { return false };
+        let area = match path_name(&call.func).as_deref() {
+            Some("TopBottomPanel::top") => DockArea::Top,
+            Some("TopBottomPanel::bottom") => DockArea::Bottom,
+            Some("SidePanel::left") => DockArea::Left,
+            Some("SidePanel::right") => DockArea::Right,
+            // Free and Center widgets are generated alike, the builder puts widgets
+            // dropped on the canvas in Center
+            Some("CentralPanel::default") => DockArea::Center,
+            _ => return false,
+        };
+        self.panel_body(&body.block.stmts, area, None);
+        true
+    }
+
+    /// The widgets of a panel, or of the container at index `parent` of the found widgets
+    fn panel_body<'s>(&mut self, stmts: impl IntoIterator<Item = &'s Stmt>, area: DockArea, parent: Option<usize>) {
+        for stmt in stmts {
+            let known = match stmt {
+                Stmt::Local(local) => {
+                    let init = local.init.as_ref().map(|init| &*init.expr);
+                    match (pat_ident(&local.pat).as_deref(), init) {
+                        (Some(name), Some(_)) if name.starts_with("before_") => true,
+                        (Some("_"), Some(Expr::MethodCall(call))) => call.method == "allocate_painter",
+                        (Some("canvas"), Some(init)) => {
+                            let calls = Calls::of(init);
+                            match calls.arg("Rect::from_min_size", 1).and_then(vector) {
+                                Some(size) => {
+                                    self.project.canvas_size = size;
+                                    true
+                                }
+                                None => false,
+                            }
+                        }
+                        (Some(name), Some(Expr::Field(inner))) if name.starts_with("response_") => {
+                            matches!(&inner.member, Member::Named(m) if m == "inner")
+                                && self.widget(&inner.base, area, Some(name), parent)
+                        }
+                        _ => false,
+                    }
+                }
+                Stmt::Expr(Expr::If(check), _) => self.event_check(check),
+                Stmt::Expr(e, _) => self.widget(e, area, None, parent),
+                _ => false,
+            };
+            if !known {
+                self.report(stmt);
+            }
+        }
+    }
+
+    /// `if response_3.clicked() { events.push(…) }` or `if state.f != before_f { events.push(…) }`
+    fn event_check(&mut self, check: &syn::ExprIf) -> bool {
+        let [Stmt::Expr(Expr::MethodCall(push), _)] = check.then_branch.stmts.as_slice() else { return false };
+        let (Some(Expr::Path(variant)), true) = (push.args.first(), push.method == "push") else { return false };
+        let Some(variant) = variant.path.segments.last().map(|s| s.ident.to_string()) else { return false };
+        let (event, target) = match &*check.cond {
+            Expr::MethodCall(call) => {
+                let event = match call.method.to_string().as_str() {
+                    "clicked" => WidgetEvent::Clicked,
+                    "double_clicked" => WidgetEvent::DoubleClicked,
+                    "lost_focus" => WidgetEvent::LostFocus,
+                    _ => return false,
+                };
+                let Expr::Path(response) = &*call.receiver else { return false };
+                let response = response.path.get_ident().map(|i| i.to_string());
+                (event, self.found.iter_mut().rev().find(|f| f.response.is_some() && f.response == response))
+            }
+            Expr::Binary(cmp) if matches!(cmp.op, BinOp::Ne(_)) => {
+                let field = state_field(&cmp.left);
+                (WidgetEvent::Changed, self.found.iter_mut().rev().find(|f| f.field.is_some() && f.field == field))
+            }
+            _ => return false,
+        };
+        match target {
+            Some(found) => {
+                found.events.push((event, variant));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// `ui.scope_builder(egui::UiBuilder::new().max_rect(…), |ui| …)`, or
+    /// `ui.allocate_ui(…, |ui| …)` in a layout
+    fn widget(&mut self, expr: &Expr, area: DockArea, response: Option<&str>, parent: Option<usize>) -> bool {
+        let Expr::MethodCall(scope) = expr else { return false };
+        let (Some(place), Some(Expr::Closure(closure))) = (scope.args.first(), scope.args.iter().nth(1)) else {
+            return false;
+        };
+        let (pos, size) = match scope.method.to_string().as_str() {
+            "scope_builder" => {
+                let rect = Calls::of(place);
+                let pos = match rect.arg("Rect::from_min_size", 0) {
+                    Some(Expr::Binary(add)) => vector(&add.right),
+                    _ => None,
+                };
+                match (pos, rect.arg("Rect::from_min_size", 1).and_then(vector)) {
+                    (Some(pos), Some(size)) => (pos, size),
+                    _ => return false,
+                }
+            }
+            "allocate_ui" => match vector(place) {
+                Some(size) => (Vec2::ZERO, size),
+                None => return false,
+            },
+            _ => return false,
+        };
+        let (kind, field, props, mut id, children) = match container(&closure.body) {
+            Some(((kind, field, props, id), children)) => (kind, field, props, id, children),
+            None => match classify(&closure.body) {
+                Some((kind, field, props, id)) => (kind, field, props, id, Vec::new()),
+                None => return false,
+            },
+        };
+        if kind.state_prefix().is_some() && field.is_none() {
+            return false;
+        }
+
+        let mut widget = Widget {
+            id: WidgetId::new(0),
+            kind,
+            pos: pos2(pos.x, pos.y),
+            size,
+            z: 0,
+            area,
+            parent: None,
+            props,
+            name: String::new(),
+            events: Vec::new(),
+        };
+        if let (Some(field), Some(prefix)) = (&field, widget.kind.state_prefix()) {
+            match field.strip_prefix(prefix).and_then(|f| f.strip_prefix('_')).and_then(|n| n.parse().ok()) {
+                Some(n) => id = Some(n),
+                None => widget.name = field.clone(),
+            }
+        }
+        if widget.uses_image() {
+            // the files are expected where the generated code looks for them
+            let name = &widget.props.image;
+            if self.project.asset(name).is_none() {
+                let path = Path::new(ASSET_DIR).join(name);
+                self.project.assets.push(Asset { name: name.clone(), path });
+            }
+            if Calls::of(&closure.body).has("Image::new") {
+                self.project.codegen.assets = AssetMode::Load;
+            }
+        }
+        let response = response.map(str::to_owned);
+        if let Some(n) = response.as_ref().and_then(|r| r["response_".len()..].parse().ok()) {
+            id = Some(n);
+        }
+        // named after the widget's field, which may still be spelled with this id
+        widget.id = WidgetId::new(id.unwrap_or_default());
+        let derived = widget.derived_fields();
+        self.found.push(Found { widget, field, derived, id, parent, response, events: Vec::new() });
+        let container = self.found.len() - 1;
+        self.panel_body(children, area, Some(container));
+        true
+    }
+
+    /// Take the column types of the tables from their row structs and report the
+    /// structs and impls that belong to no table
+    fn row_structs(&mut self, structs: &[&ItemStruct], impls: &[&ItemImpl]) {
+        let mut claimed = HashSet::new();
+        for found in &mut self.found {
+            let Some(name) = found.widget.row_struct() else { continue };
+            let Some(row) = structs.iter().find(|s| s.ident == name) else { continue };
+            for (column, field) in found.widget.props.columns.iter_mut().zip(&row.fields) {
+                column.ty = match type_name(&field.ty).as_deref() {
+                    Some("i64") => ColumnType::Integer,
+                    Some("f64") => ColumnType::Number,
+                    Some("bool") => ColumnType::Bool,
+                    _ => ColumnType::Text,
+                };
+            }
+            claimed.insert(name);
+        }
+        for row in structs {
+            if !claimed.contains(&row.ident.to_string()) {
+                self.report(*row);
+            }
+        }
+        for imp in impls {
+            // the comparison used for sorting
+            let sorting = imp.trait_.is_none()
+                && imp.items.iter().all(|item| matches!(item, ImplItem::Fn(f) if f.sig.ident == "cmp_by"));
+            if !(sorting && type_name(&imp.self_ty).is_some_and(|name| claimed.contains(&name))) {
+                self.report(*imp);
+            }
+        }
+    }
+
+    /// Hand out ids, apply the initial values and add the widgets to the project in
+    /// the order they were designed in. Returns the state fields used by widgets.
+    fn finish(&mut self, mut defaults: HashMap<String, Expr>, fields: &[String], variants: &[String]) -> HashSet<String> {
+        let mut taken = HashSet::new();
+        for found in &mut self.found {
+            if let Some(id) = found.id
+                && !taken.insert(id)
+            {
+                found.id = None;
+            }
+        }
+        let mut next = taken.iter().max().map_or(1, |max| max + 1);
+
+        let order = project_order(&self.found, fields, variants);
+        // handed out up front, containers may come after what they hold
+        let mut ids = vec![WidgetId::new(0); self.found.len()];
+        for &i in &order {
+            ids[i] = WidgetId::new(self.found[i].id.unwrap_or_else(|| {
+                next += 1;
+                next - 1
+            }));
+        }
+        let mut found: Vec<Option<Found>> = std::mem::take(&mut self.found).into_iter().map(Some).collect();
+
+        let mut used = HashSet::new();
+        for i in order {
+            let Some(found) = found[i].take() else { continue };
+            let mut w = found.widget;
+            w.id = ids[i];
+            w.parent = found.parent.map(|p| ids[p]);
+            w.z = w.id.as_z();
+            if let Some(field) = found.field {
+                if let Some(init) = defaults.remove(&field) {
+                    initial_value(&mut w, &init);
+                }
+                used.insert(field);
+            }
+            for (i, field) in found.derived.into_iter().enumerate() {
+                if let (Some(series), Some(points)) =
+                    (w.props.series.get_mut(i), defaults.remove(&field).as_ref().and_then(points))
+                {
+                    series.points = points;
+                }
+                used.insert(field);
+            }
+            let mut events = found.events;
+            // the Changed check comes after the response checks, the enum keeps the real order
+            events.sort_by_key(|(_, variant)| variants.iter().position(|v| v == variant));
+            for (event, variant) in events {
+                // stateless widgets only show their name in the event variants
+                if w.name.is_empty()
+                    && w.state_field().is_none()
+                    && w.event_variant(event) != variant
+                    && let Some(base) = variant.strip_suffix(&format!("{event:?}"))
+                {
+                    w.name = codegen::snake_case(base);
+                }
+                w.events.push(event);
+            }
+            self.project.widgets.push(w);
+        }
+        used
+    }
+}
+
+/// Indices into `found` in project order. The generator keeps that order in each
+/// panel, in the state fields and in the event variants; whatever those leave open
+/// is decided by the ids, which follow the order widgets were added in.
+fn project_order(found: &[Found], fields: &[String], variants: &[String]) -> Vec<usize> {
+    let mut after: Vec<Vec<usize>> = vec![Vec::new(); found.len()];
+    let mut waiting = vec![0usize; found.len()];
+    let mut chain = |sequence: Vec<usize>| {
+        for pair in sequence.windows(2) {
+            after[pair[0]].push(pair[1]);
+            waiting[pair[1]] += 1;
+        }
+    };
+    // siblings in a panel or container
+    for parent in std::iter::once(None).chain((0..found.len()).map(Some)) {
+        for area in [DockArea::Top, DockArea::Bottom, DockArea::Left, DockArea::Right, DockArea::Center] {
+            chain((0..found.len()).filter(|&i| found[i].widget.area == area && found[i].parent == parent).collect());
+        }
+    }
+    let uses = |f: &Found, name: &String| f.field.as_ref() == Some(name) || f.derived.contains(name);
+    let mut by_field: Vec<usize> = fields.iter().filter_map(|name| found.iter().position(|f| uses(f, name))).collect();
+    by_field.dedup();
+    chain(by_field);
+    let mut by_variant: Vec<usize> = variants
+        .iter()
+        .filter_map(|v| found.iter().position(|f| f.events.iter().any(|(_, variant)| variant == v)))
+        .collect();
+    by_variant.dedup();
+    chain(by_variant);
+
+    // widgets without an id sort with the one before them in their panel
+    let mut keys = Vec::with_capacity(found.len());
+    for (i, f) in found.iter().enumerate() {
+        let previous = (0..i).rev().find(|&j| found[j].widget.area == f.widget.area).map_or(0, |j| keys[j]);
+        keys.push(f.id.unwrap_or(previous));
+    }
+
+    let mut order = Vec::with_capacity(found.len());
+    let mut done = vec![false; found.len()];
+    while order.len() < found.len() {
+        // a cycle means the code was edited by hand, fall back to the order of appearance
+        let next = (0..found.len())
+            .filter(|&i| !done[i] && waiting[i] == 0)
+            .min_by_key(|&i| (keys[i], i))
+            .or_else(|| (0..found.len()).find(|&i| !done[i]))
+            .unwrap_or_default();
+        done[next] = true;
+        order.push(next);
+        for &i in &after[next] {
+            waiting[i] = waiting[i].saturating_sub(1);
+        }
+    }
+    order
+}
+
+/// The state struct and event enum of `fn ui(ctx: &egui::Context, state: &mut State) -> Vec<Event>`
+fn ui_fn_signature(f: &ItemFn) -> Option<(String, Option<String>)> {
+    let inputs: Vec<_> = f.sig.inputs.iter().collect();
+    let [FnArg::Typed(ctx), FnArg::Typed(state)] = inputs.as_slice() else { return None };
+    let (Type::Reference(ctx), Type::Reference(state)) = (&*ctx.ty, &*state.ty) else { return None };
+    if ctx.mutability.is_some() || state.mutability.is_none() || type_name(&ctx.elem).as_deref() != Some("Context") {
+        return None;
+    }
+    let state = type_name(&state.elem)?;
+    let events = match &f.sig.output {
+        ReturnType::Default => None,
+        ReturnType::Type(_, ty) => {
+            let Type::Path(vec) = &**ty else { return None };
+            let syn::PathArguments::AngleBracketed(args) = &vec.path.segments.last()?.arguments else { return None };
+            match args.args.first() {
+                Some(syn::GenericArgument::Type(ty)) => Some(type_name(ty)?),
+                _ => return None,
+            }
+        }
+    };
+    Some((state, events))
+}
+
+/// A recognized widget with its state field, properties and the id it spells out
+type Classified = (WidgetKind, Option<String>, WidgetProps, Option<u64>);
+
+/// Recognize the widget drawn by the `scope_builder` closure `body`, with its state
+/// field, properties and the id it spells out (the combo box id)
+fn classify(body: &Expr) -> Option<Classified> {
+    let calls = Calls::of(body);
+    let mut props = WidgetProps::default();
+    let text = |name: &str, i: usize| calls.arg(name, i).and_then(string);
+    let field = |name: &str, i: usize| calls.arg(name, i).and_then(state_field);
+    let items = || -> Option<Vec<String>> { calls.args("vec!")?.iter().map(string).collect() };
+    let mut id = None;
+
+    let (kind, field) = if calls.has("Plot::new") {
+        plot_props(&calls, &mut props)?;
+        (WidgetKind::Plot, text("Plot::new", 0))
+    } else if calls.has("TableBuilder::new") {
+        table_props(&calls, &mut props)?;
+        let field = match calls.arg(".rows", 1) {
+            Some(Expr::MethodCall(len)) => state_field(&len.receiver),
+            _ => None,
+        };
+        (WidgetKind::Table, field)
+    } else if calls.has("gen_show_tree") {
+        let Expr::Block(block) = body else { return None };
+        let nodes = block.block.stmts.iter().find_map(|s| match s {
+            Stmt::Local(local) if pat_ident(&local.pat).as_deref() == Some("nodes") => local.init.as_ref(),
+            _ => None,
+        })?;
+        tree_items(&nodes.expr, 0, &mut props.items)?;
+        (WidgetKind::Tree, None)
+    } else if calls.has("DatePickerButton::new") {
+        props.text = text(".label", 0)?;
+        (WidgetKind::DatePicker, field("DatePickerButton::new", 0))
+    } else if let Some(combo) = ["ComboBox::from_id_source", "ComboBox::from_id_salt"].into_iter().find(|c| calls.has(c)) {
+        props.items = items()?;
+        id = calls.arg(combo, 0).and_then(integer);
+        (WidgetKind::ComboBox, calls.arg(".selectable_value", 0).and_then(state_field))
+    } else if calls.has(".menu_button") {
+        props.text = text(".menu_button", 0)?;
+        props.items = items()?;
+        (WidgetKind::MenuButton, calls.assigned_field())
+    } else if calls.has("RadioButton::new") {
+        props.items = items()?;
+        (WidgetKind::RadioGroup, calls.assigned_field())
+    } else if calls.has("CollapsingHeader::new") {
+        props.text = text("CollapsingHeader::new", 0)?;
+        (WidgetKind::CollapsingHeader, field(".default_open", 0))
+    } else if calls.has("Slider::new") {
+        let Some(Expr::Range(range)) = calls.arg("Slider::new", 1) else { return None };
+        props.min = number(range.start.as_deref()?)?;
+        props.max = number(range.end.as_deref()?)?;
+        props.text = text(".text", 0)?;
+        let kind = if calls.has(".suffix") { WidgetKind::AngleSelector } else { WidgetKind::Slider };
+        (kind, field("Slider::new", 0))
+    } else if calls.has("ProgressBar::new") {
+        (WidgetKind::ProgressBar, field("ProgressBar::new", 0))
+    } else if calls.has(".checkbox") {
+        props.text = text(".checkbox", 1)?;
+        (WidgetKind::Checkbox, field(".checkbox", 0))
+    } else if calls.has("Button::selectable") {
+        props.text = text("Button::selectable", 1)?;
+        (WidgetKind::SelectableLabel, field("Button::selectable", 0))
+    } else if calls.has("TextEdit::singleline") {
+        let kind = if calls.has(".password") {
+            WidgetKind::Password
+        } else {
+            props.text = text(".hint_text", 0)?;
+            WidgetKind::TextEdit
+        };
+        (kind, field("TextEdit::singleline", 0))
+    } else if calls.has(".hyperlink_to") {
+        props.text = text(".hyperlink_to", 0)?;
+        props.url = text(".hyperlink_to", 1)?;
+        (WidgetKind::Hyperlink, None)
+    } else if let Some(link) = [".link", "Link::new"].into_iter().find(|l| calls.has(l)) {
+        props.text = text(link, 0)?;
+        (WidgetKind::Link, None)
+    } else if calls.has("Button::image_and_text") {
+        image_props(&calls, &mut props)?;
+        props.text = text("Button::image_and_text", 1)?;
+        (WidgetKind::ImageTextButton, None)
+    } else if calls.has("Image::from_bytes") || calls.has("Image::new") {
+        image_props(&calls, &mut props)?;
+        (WidgetKind::Image, None)
+    } else if calls.has("format!") && calls.has("Button::new") {
+        props.icon = text("format!", 1)?;
+        props.text = text("format!", 2)?;
+        (WidgetKind::ImageTextButton, None)
+    } else if calls.has("Button::new") {
+        props.text = text("Button::new", 0)?;
+        (WidgetKind::Button, None)
+    } else if calls.has(".separator") {
+        (WidgetKind::Separator, None)
+    } else if calls.has(".label") {
+        props.text = text(".label", 0)?;
+        (WidgetKind::Label, None)
+    } else {
+        return None;
+    };
+    Some((kind, field, props, id))
+}
+
+/// Recognize a container drawn by the `scope_builder` closure `body`, with its state
+/// field, properties, id and the statements of the widgets placed in it
+fn container(body: &Expr) -> Option<(Classified, Vec<&Stmt>)> {
+    let Expr::Block(block) = body else { return None };
+    let mut props = WidgetProps::default();
+    // layouts set `ui.spacing_mut().item_spacing = …` first
+    let stmts = match block.block.stmts.as_slice() {
+        [Stmt::Expr(Expr::Assign(spacing), _), rest @ ..] => {
+            let Expr::Field(target) = &*spacing.left else { return None };
+            if !matches!(&target.member, Member::Named(m) if m == "item_spacing") {
+                return None;
+            }
+            props.spacing = Some(vector(&spacing.right)?);
+            rest
+        }
+        stmts => stmts,
+    };
+    let [Stmt::Expr(Expr::MethodCall(call), _)] = stmts else { return None };
+    let Some(Expr::Closure(closure)) = call.args.last() else { return None };
+    let Expr::Block(contents) = &*closure.body else { return None };
+    let mut stmts: Vec<&Stmt> = contents.block.stmts.iter().collect();
+    let calls = Calls::of(&call.receiver);
+    let mut field = None;
+    let mut id = None;
+    let kind = match call.method.to_string().as_str() {
+        "horizontal" | "vertical" if calls.has("StripBuilder::new") => {
+            props.vertical = call.method == "vertical";
+            // `strip.cell(|ui| …)` holds each widget
+            let mut cells = Vec::new();
+            for stmt in stmts {
+                let Stmt::Expr(Expr::MethodCall(cell), _) = stmt else { return None };
+                let (Some(Expr::Closure(closure)), true) = (cell.args.first(), cell.method == "cell") else {
+                    return None;
+                };
+                let Expr::Block(body) = &*closure.body else { return None };
+                cells.extend(&body.block.stmts);
+            }
+            stmts = cells;
+            WidgetKind::Strip
+        }
+        "horizontal" => WidgetKind::Horizontal,
+        "vertical" => WidgetKind::Vertical,
+        "with_layout" => {
+            let layout = Calls::of(call.args.first()?);
+            if layout.has("Layout::left_to_right") {
+                props.align = Some(alignment(layout.arg("Layout::left_to_right", 0)?)?);
+                WidgetKind::Horizontal
+            } else if layout.has("Layout::top_down") {
+                props.align = Some(alignment(layout.arg("Layout::top_down", 0)?)?);
+                WidgetKind::Vertical
+            } else {
+                return None;
+            }
+        }
+        "show" => {
+            if calls.has("Frame::group") {
+                // `ui.set_min_size(ui.available_size())` comes first
+                let Some(Stmt::Expr(Expr::MethodCall(fill), _)) = stmts.first() else { return None };
+                if fill.method != "set_min_size" {
+                    return None;
+                }
+                stmts.remove(0);
+                WidgetKind::Frame
+            } else if calls.has("ScrollArea::both") {
+                id = calls.arg(".id_salt", 0).and_then(integer);
+                WidgetKind::ScrollArea
+            } else if calls.has("CollapsingHeader::new") {
+                props.text = calls.arg("CollapsingHeader::new", 0).and_then(string)?;
+                field = calls.arg(".default_open", 0).and_then(state_field);
+                WidgetKind::CollapsingHeader
+            } else if calls.has("Grid::new") {
+                id = calls.arg("Grid::new", 0).and_then(integer);
+                props.grid_columns = calls.arg(".num_columns", 0).and_then(integer)? as usize;
+                props.striped = calls.has(".striped");
+                // `ui.end_row()` after each row
+                stmts.retain(|stmt| !matches!(stmt, Stmt::Expr(Expr::MethodCall(end), _) if end.method == "end_row"));
+                WidgetKind::Grid
+            } else {
+                return None;
+            }
+        }
+        _ => return None,
+    };
+    // date pickers and older collapsing headers hold plain widgets instead
+    let placed = stmts.iter().all(|stmt| match stmt {
+        Stmt::Local(_) | Stmt::Expr(Expr::If(_), _) => true,
+        Stmt::Expr(Expr::MethodCall(scope), _) => scope.method == "scope_builder" || scope.method == "allocate_ui",
+        _ => false,
+    });
+    placed.then_some(((kind, field, props, id), stmts))
+}
+
+/// `egui::Align::Center`
+fn alignment(expr: &Expr) -> Option<Align> {
+    match path_name(expr)?.rsplit("::").next()? {
+        "Min" => Some(Align::Min),
+        "Center" => Some(Align::Center),
+        "Max" => Some(Align::Max),
+        _ => None,
+    }
+}
+
+/// The asset and style of an image built like [`WidgetProps::style_image`] does it
+fn image_props(calls: &Calls, props: &mut WidgetProps) -> Option<()> {
+    // `bytes://assets/logo.png` when embedded, `file://assets/logo.png` when loaded
+    let uri = ["Image::from_bytes", "Image::new"].into_iter().find_map(|c| calls.arg(c, 0)).and_then(string)?;
+    let file = uri.strip_prefix("bytes://").or_else(|| uri.strip_prefix("file://"))?;
+    props.image = file.strip_prefix(ASSET_DIR)?.strip_prefix('/')?.to_owned();
+    props.fit = if let Some(scale) = calls.arg(".fit_to_original_size", 0) {
+        props.scale = number(scale)?;
+        ImageFit::Original
+    } else if calls.has(".maintain_aspect_ratio") {
+        ImageFit::Stretch
+    } else {
+        ImageFit::Contain
+    };
+    if let Some(args) = calls.args("Color32::from_rgba_unmultiplied") {
+        let rgba: Vec<u8> = args.iter().map(|a| integer(a)?.try_into().ok()).collect::<Option<_>>()?;
+        let [r, g, b, a] = rgba[..] else { return None };
+        props.tint = Color32::from_rgba_unmultiplied(r, g, b, a);
+    }
+    if let Some(rounding) = calls.arg(".corner_radius", 0) {
+        props.rounding = integer(rounding)?.try_into().ok()?;
+    }
+    Some(())
+}
+
+/// The options and series of an `egui_plot::Plot`, the series data is in their state fields
+fn plot_props(calls: &Calls, props: &mut WidgetProps) -> Option<()> {
+    let label = |method: &str| calls.arg(method, 0).map_or(Some(String::new()), string);
+    props.x_label = label(".x_axis_label")?;
+    props.y_label = label(".y_axis_label")?;
+    props.legend = calls.has(".legend");
+    props.grid = !calls.has(".show_grid");
+    props.zoom = !calls.has(".allow_zoom");
+    props.drag = !calls.has(".allow_drag");
+    for (call, args) in &calls.calls {
+        let style = match call.as_str() {
+            "Line::new" => SeriesStyle::Line,
+            "BarChart::new" => SeriesStyle::Bar,
+            "Points::new" => SeriesStyle::Scatter,
+            _ => continue,
+        };
+        let name = string(args.first()?)?;
+        props.series.push(PlotSeries { name, style, points: Vec::new() });
+    }
+    Some(())
+}
+
+/// The options and columns of an `egui_extras::TableBuilder`, the column types are in the
+/// row struct and the rows in the state field
+fn table_props(calls: &Calls, props: &mut WidgetProps) -> Option<()> {
+    props.striped = calls.has(".striped");
+    props.sticky_header = calls.has(".header");
+    props.sortable = calls.has("gen_sort_header");
+    // the outermost call of the builder chain comes first
+    let widths = calls.calls.iter().rev().filter(|(call, _)| call == ".column");
+    // without a sticky header it's the first row of the body
+    let header = calls.arg(".header", 1).or_else(|| calls.arg(".row", 1))?;
+    let headers = Calls::of(header);
+    let cells = headers.calls.iter().filter(|(call, _)| call == ".col");
+    for ((_, width), (_, cell)) in widths.zip(cells) {
+        let width = Calls::of(width.first()?);
+        let cell = Calls::of(cell.first()?);
+        let size = |call: &str| width.arg(call, 0).and_then(number);
+        let header = cell.arg(".strong", 0).or_else(|| cell.arg("gen_sort_header", 1)).and_then(string)?;
+        props.columns.push(TableColumn {
+            header,
+            ty: ColumnType::Text,
+            width: if width.has("Column::remainder") {
+                ColumnWidth::Remainder
+            } else if let Some(w) = size("Column::initial") {
+                ColumnWidth::Initial(w)
+            } else if let Some(w) = size("Column::exact") {
+                ColumnWidth::Exact(w)
+            } else {
+                ColumnWidth::Auto
+            },
+            resizable: width.has(".resizable"),
+            align: if cell.has("Layout::right_to_left") {
+                ColumnAlign::Right
+            } else if cell.has("Layout::top_down") {
+                ColumnAlign::Center
+            } else {
+                ColumnAlign::Left
+            },
+        });
+    }
+    Some(())
+}
+
+/// Set the property `w` keeps in its state field from the field's initializer
+fn initial_value(w: &mut Widget, init: &Expr) {
+    let props = &mut w.props;
+    match w.kind {
+        WidgetKind::TextEdit | WidgetKind::Password => {
+            if let Some(text) = string(init) {
+                props.text = text;
+            }
+        }
+        WidgetKind::Checkbox | WidgetKind::SelectableLabel | WidgetKind::CollapsingHeader => {
+            props.checked = boolean(init).unwrap_or(props.checked);
+        }
+        WidgetKind::Slider | WidgetKind::ProgressBar | WidgetKind::AngleSelector => {
+            props.value = number(init).unwrap_or(props.value);
+        }
+        WidgetKind::RadioGroup | WidgetKind::ComboBox | WidgetKind::MenuButton => {
+            props.selected = integer(init).map_or(props.selected, |n| n as usize);
+        }
+        WidgetKind::DatePicker => {
+            let calls = Calls::of(init);
+            let part = |i| calls.arg("NaiveDate::from_ymd_opt", i).and_then(signed);
+            if let (Some(y), Some(m), Some(d)) = (part(0), part(1), part(2)) {
+                props.year = y as i32;
+                props.month = m as u32;
+                props.day = d as u32;
+            }
+        }
+        WidgetKind::Table => {
+            if let Some(rows) = table_rows(w, init) {
+                w.props.rows = rows;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The cells of `Vec::from([Row { … }, …])` in the column order of table `w`
+fn table_rows(w: &Widget, init: &Expr) -> Option<Vec<Vec<String>>> {
+    let Expr::Call(from) = init else { return None };
+    let (Some("Vec::from"), Some(Expr::Array(rows))) = (path_name(&from.func).as_deref(), from.args.first()) else {
+        return None;
+    };
+    rows.elems
+        .iter()
+        .map(|row| {
+            let Expr::Struct(row) = row else { return None };
+            w.props
+                .columns
+                .iter()
+                .enumerate()
+                .map(|(i, column)| {
+                    let name = w.column_field(i)?;
+                    let value = &row.fields.iter().find(|f| matches!(&f.member, Member::Named(m) if *m == name))?.expr;
+                    match column.ty {
+                        ColumnType::Text => string(value),
+                        ColumnType::Integer => signed(value).map(|n| n.to_string()),
+                        ColumnType::Number => number::<f64>(value).map(|n| n.to_string()),
+                        ColumnType::Bool => boolean(value).map(|b| b.to_string()),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// `Vec::from([[x, y], …])`
+fn points(expr: &Expr) -> Option<Vec<[f64; 2]>> {
+    let Expr::Call(from) = expr else { return None };
+    let (Some("Vec::from"), Some(Expr::Array(points))) = (path_name(&from.func).as_deref(), from.args.first()) else {
+        return None;
+    };
+    points
+        .elems
+        .iter()
+        .map(|point| match point {
+            Expr::Array(xy) => match xy.elems.iter().collect::<Vec<_>>()[..] {
+                [x, y] => Some([number(x)?, number(y)?]),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Indented lines of the tree items from nested `GenTreeNode` literals
+fn tree_items(expr: &Expr, depth: usize, out: &mut Vec<String>) -> Option<()> {
+    let Expr::Macro(vec) = expr else { return None };
+    for node in macro_args(&vec.mac)? {
+        let Expr::Struct(node) = node else { return None };
+        let field = |name: &str| {
+            node.fields
+                .iter()
+                .find(|f| matches!(&f.member, Member::Named(m) if m == name))
+                .map(|f| &f.expr)
+        };
+        out.push(format!("{}{}", "  ".repeat(depth), string(field("label")?)?));
+        tree_items(field("children")?, depth + 1, out)?;
+    }
+    Some(())
+}
+
+/// Every call in an expression by name: `.method`, `Type::function` or `macro!`
+#[derive(Default)]
+struct Calls {
+    calls: Vec<(String, Vec<Expr>)>,
+}
+
+impl Calls {
+    fn of(expr: &Expr) -> Self {
+        let mut calls = Self::default();
+        calls.visit_expr(expr);
+        calls
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.args(name).is_some()
+    }
+
+    /// Arguments of the first call to `name`
+    fn args(&self, name: &str) -> Option<&[Expr]> {
+        self.calls.iter().find(|(n, _)| n == name).map(|(_, args)| args.as_slice())
+    }
+
+    fn arg(&self, name: &str, i: usize) -> Option<&Expr> {
+        self.args(name)?.get(i)
+    }
+
+    /// The field of `state.field = i` in the item loops of menus and radio groups
+    fn assigned_field(&self) -> Option<String> {
+        self.args("=")?.first().and_then(state_field)
+    }
+}
+
+impl<'ast> Visit<'ast> for Calls {
+    fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
+        if let Some(name) = path_name(&call.func) {
+            self.calls.push((name, call.args.iter().cloned().collect()));
+        }
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        self.calls.push((format!(".{}", call.method), call.args.iter().cloned().collect()));
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_assign(&mut self, assign: &'ast syn::ExprAssign) {
+        self.calls.push(("=".into(), vec![(*assign.left).clone()]));
+        visit::visit_expr_assign(self, assign);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let Some(name) = mac.path.get_ident() else { return };
+        let Some(args) = macro_args(mac) else { return };
+        self.calls.push((format!("{name}!"), args.clone()));
+        for arg in &args {
+            self.visit_expr(arg);
+        }
+    }
+}
+
+/// The comma separated arguments of `vec![…]`, `format!(…)` and the like
+fn macro_args(mac: &Macro) -> Option<Vec<Expr>> {
+    mac.parse_body_with(Punctuated::<Expr, syn::Token![,]>::parse_terminated)
+        .ok()
+        .map(|args| args.into_iter().collect())
+}
+
+fn line(node: &impl Spanned) -> usize {
+    node.span().start().line
+}
+
+/// `Rect::from_min_size` for `egui::Rect::from_min_size`, the last two path segments
+fn path_name(expr: &Expr) -> Option<String> {
+    let Expr::Path(path) = expr else { return None };
+    let segments: Vec<String> = path.path.segments.iter().map(|s| s.ident.to_string()).collect();
+    Some(segments[segments.len().saturating_sub(2)..].join("::"))
+}
+
+/// The last segment of a type path, `Context` for `&egui::Context`
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    }
+}
+
+fn pat_ident(pat: &Pat) -> Option<String> {
+    match pat {
+        Pat::Ident(ident) => Some(ident.ident.to_string()),
+        Pat::Type(typed) => pat_ident(&typed.pat),
+        Pat::Wild(_) => Some("_".into()),
+        _ => None,
+    }
+}
+
+/// `eframe::egui` for `use eframe::egui;`
+fn use_path(tree: &UseTree) -> Option<String> {
+    match tree {
+        UseTree::Path(path) => Some(format!("{}::{}", path.ident, use_path(&path.tree)?)),
+        UseTree::Name(name) => Some(name.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// The field of `state.field` or `&mut state.field`
+fn state_field(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Reference(r) => state_field(&r.expr),
+        Expr::Field(f) => match (&*f.base, &f.member) {
+            (Expr::Path(base), Member::Named(name)) if base.path.is_ident("state") => Some(name.to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A string literal, also with `.to_string()` or `.to_owned()`
+fn string(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Str(s) => Some(s.value()),
+            _ => None,
+        },
+        Expr::MethodCall(call) if call.method == "to_string" || call.method == "to_owned" => string(&call.receiver),
+        Expr::Paren(p) => string(&p.expr),
+        _ => None,
+    }
+}
+
+fn number<N>(expr: &Expr) -> Option<N>
+where
+    N: FromStr + Neg<Output = N>,
+    N::Err: fmt::Display,
+{
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Float(f) => f.base10_parse().ok(),
+            Lit::Int(i) => i.base10_parse().ok(),
+            _ => None,
+        },
+        Expr::Unary(neg) if matches!(neg.op, UnOp::Neg(_)) => number::<N>(&neg.expr).map(|n| -n),
+        Expr::Paren(p) => number(&p.expr),
+        _ => None,
+    }
+}
+
+fn integer(expr: &Expr) -> Option<u64> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Int(i) => i.base10_parse().ok(),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn signed(expr: &Expr) -> Option<i64> {
+    match expr {
+        Expr::Unary(neg) if matches!(neg.op, UnOp::Neg(_)) => integer(&neg.expr).map(|n| -(n as i64)),
+        _ => integer(expr).map(|n| n as i64),
+    }
+}
+
+fn boolean(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Bool(b) => Some(b.value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `egui::vec2(x, y)`
+fn vector(expr: &Expr) -> Option<Vec2> {
+    let Expr::Call(call) = expr else { return None };
+    if path_name(&call.func)?.rsplit("::").next() != Some("vec2") {
+        return None;
+    }
+    let args: Vec<_> = call.args.iter().collect();
+    let [x, y] = args.as_slice() else { return None };
+    Some(vec2(number(x)?, number(y)?))
+}
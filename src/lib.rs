@@ -0,0 +1,31 @@
+//! The project model and code generator behind the egui RAD GUI Builder.
+//!
+//! Designs saved from the builder are plain JSON. This crate loads them into a
+//! [`Project`] and turns that into Rust source for an egui app, so the same
+//! generator the GUI uses can run from a `build.rs` or your own tooling.
+//!
+//! ```no_run
+//! let project = egui_rad_builder::Project::load("design.json")?;
+//! let code = egui_rad_builder::generate(&project)?;
+//! std::fs::write("src/ui.rs", code)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! To compile a design straight into an app on every build, see [`build`].
+//!
+//! Build without the default features (`default-features = false`) to leave out
+//! the GUI and its dependencies.
+
+pub mod build;
+pub mod codegen;
+pub mod export;
+pub mod import;
+pub mod project;
+pub mod widget;
+
+pub use codegen::{AssetMode, CodegenSettings, GenerateError, OutputMode, Visibility, generate, generate_module};
+pub use project::{ASSET_DIR, Asset, FORMAT_VERSION, ImageFormat, Project, ProjectError};
+pub use widget::{
+    ColumnAlign, ColumnType, ColumnWidth, DockArea, ImageFit, PlotSeries, SeriesStyle, TableColumn, Widget, WidgetId,
+    WidgetKind, WidgetProps,
+};
@@ -1,10 +1,15 @@
 //! A lightweight RAD GUI builder for `egui` written in Rust.
 
+mod align;
 mod app;
-mod project;
-mod widget;
+mod cli;
+mod clipboard;
+mod guides;
+mod history;
+mod preview;
 
 use crate::app::RadBuilderApp;
+use egui_rad_builder::{codegen, export, import, project, widget};
 
 use eframe::egui;
 
@@ -22,14 +27,18 @@ fn initial_inner_size() -> egui::Vec2 {
     // Left palette is open by default in RadBuilderApp::default()
     w += 220.0;
 
-    // Small padding for menubar + side padding
-    h += 40.0;
+    // Small padding for menubar + toolbar + side padding
+    h += 64.0;
     w += 16.0;
 
     egui::vec2(w, h)
 }
 
 fn main() -> eframe::Result<()> {
+    if let Some(code) = cli::run() {
+        std::process::exit(code);
+    }
+
     let mut native_options = eframe::NativeOptions::default();
     let size = initial_inner_size();
 
@@ -43,6 +52,8 @@ fn main() -> eframe::Result<()> {
         "egui RAD GUI Builder",
         native_options,
         Box::new(|cc| Ok(Box::new({
+            // for previews of the project's image assets
+            egui_extras::install_image_loaders(&cc.egui_ctx);
             #[cfg(not(feature = "persistence"))]
             return RadBuilderApp::default();
             #[cfg(feature = "persistence")]
@@ -0,0 +1,21 @@
+//! Preview mode: the design runs as the generated app would, without editing chrome.
+//!
+//! Widgets are driven by a copy of their properties instead of the design itself,
+//! the same way the generated code drives them from the fields of its state struct,
+//! so clicking and typing around in the preview never changes the project.
+
+use crate::widget::{Widget, WidgetId, WidgetProps};
+use std::collections::HashMap;
+
+/// Live values of the previewed widgets, what the generated state struct holds
+#[derive(Default)]
+pub(crate) struct PreviewState {
+    props: HashMap<WidgetId, WidgetProps>,
+}
+
+impl PreviewState {
+    /// The live properties of `w`, starting out as the generated `Default` impl sets them
+    pub(crate) fn props(&mut self, w: &Widget) -> &mut WidgetProps {
+        self.props.entry(w.id).or_insert_with(|| w.props.clone())
+    }
+}
@@ -0,0 +1,60 @@
+//! Step by step upgrades of saved project JSON to the current format.
+//!
+//! Every change to the saved shape of [`Project`](super::Project) that serde can't
+//! absorb on its own (renamed fields or variants, restructured data) bumps
+//! [`FORMAT_VERSION`] and appends a migration here. New fields only need
+//! `#[serde(default)]`, older builds skip them. New widget kinds do bump the version,
+//! with a migration that leaves the document alone, so older builds refuse such files
+//! as too new instead of failing to parse them.
+
+use super::ProjectError;
+use serde_json::Value;
+
+/// Format version written by this build
+pub const FORMAT_VERSION: u64 = 6;
+
+/// Upgrades a document by one version in place
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+/// `MIGRATIONS[n]` upgrades a document from version `n` to `n + 1`
+const MIGRATIONS: [Migration; FORMAT_VERSION as usize] = [
+    v0_to_v1,
+    // Image
+    unchanged,
+    // Plot
+    unchanged,
+    // Table
+    unchanged,
+    // containers, and the `parent` of the widgets in them
+    unchanged,
+    // Grid and Strip layouts
+    unchanged,
+];
+
+/// Upgrade `doc` from `version` to [`FORMAT_VERSION`]
+pub(super) fn upgrade(doc: &mut Value, version: u64) -> Result<(), ProjectError> {
+    if version > FORMAT_VERSION {
+        return Err(ProjectError::TooNew { found: version });
+    }
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(doc).map_err(|message| ProjectError::Migration {
+            from: from as u64,
+            message,
+        })?;
+    }
+    Ok(())
+}
+
+/// Version 0 is the unversioned format, which only needs to be a JSON object
+fn v0_to_v1(doc: &mut Value) -> Result<(), String> {
+    if doc.is_object() {
+        Ok(())
+    } else {
+        Err("expected a JSON object".into())
+    }
+}
+
+/// For versions that only add to the format
+fn unchanged(_: &mut Value) -> Result<(), String> {
+    Ok(())
+}
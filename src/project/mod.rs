@@ -0,0 +1,247 @@
+mod migrate;
+
+use crate::{
+    codegen::CodegenSettings,
+    widget::{Widget, WidgetId},
+};
+use egui::{Vec2, vec2};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeSet,
+    fmt, io,
+    path::{Path, PathBuf},
+};
+
+pub use migrate::FORMAT_VERSION;
+
+/// Key holding the format version in saved project JSON
+const VERSION_KEY: &str = "format_version";
+
+/// Directory of the generated crate the generated code expects the assets in
+pub const ASSET_DIR: &str = "assets";
+
+/// A complete design: the widgets and the window they are laid out in
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Project {
+    // todo make into struct that auto increments next_id on push
+    pub widgets: Vec<Widget>,
+    /// fixed logical canvas, the designs size
+    pub canvas_size: Vec2,
+    pub panel_top_enabled: bool,
+    pub panel_bottom_enabled: bool,
+    pub panel_left_enabled: bool,
+    pub panel_right_enabled: bool,
+    /// How code is generated for this design
+    pub codegen: CodegenSettings,
+    /// Image files used by the widgets
+    pub assets: Vec<Asset>,
+}
+
+/// An image file of the project, `assets/<name>` in the generated crate
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Asset {
+    /// File name the widgets and the generated code refer to it by
+    pub name: String,
+    /// Where the builder reads the file from, relative paths are relative to the design file
+    pub path: PathBuf,
+}
+
+/// Image formats the generated code can load
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Svg,
+}
+
+impl ImageFormat {
+    /// File extensions offered when adding an asset
+    pub const EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "svg"];
+
+    /// The format of a file by its extension
+    pub fn of(name: &str) -> Option<Self> {
+        let (_, ext) = name.rsplit_once('.')?;
+        match ext.to_ascii_lowercase().as_str() {
+            "png" => Some(ImageFormat::Png),
+            "jpg" | "jpeg" => Some(ImageFormat::Jpeg),
+            "svg" => Some(ImageFormat::Svg),
+            _ => None,
+        }
+    }
+}
+
+impl Asset {
+    /// Why `name` can't be used as the file name of an asset
+    pub fn name_problem(name: &str) -> Option<String> {
+        if name.is_empty() || name == "." || name == ".." {
+            Some("asset names can't be empty, `.` or `..`".into())
+        } else if name.contains(['/', '\\']) || name.chars().any(char::is_control) {
+            Some(format!("`{}` is not a plain file name", name.escape_debug()))
+        } else if ImageFormat::of(name).is_none() {
+            Some(format!("`{name}` is not a PNG, JPEG or SVG file"))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Project {
+    fn default() -> Self {
+        Self {
+            widgets: Vec::new(),
+            canvas_size: vec2(700.0, 600.0),
+            panel_top_enabled: false,
+            panel_bottom_enabled: false,
+            panel_left_enabled: false,
+            panel_right_enabled: false,
+            codegen: CodegenSettings::default(),
+            assets: Vec::new(),
+        }
+    }
+}
+
+/// Failure to read or write a project file
+#[derive(Debug)]
+pub enum ProjectError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// Saved by a newer builder than this one
+    TooNew { found: u64 },
+    /// An older document couldn't be upgraded
+    Migration { from: u64, message: String },
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectError::Io(e) => write!(f, "file error: {e}"),
+            ProjectError::Json(e) => write!(f, "invalid project JSON: {e}"),
+            ProjectError::TooNew { found } => write!(
+                f,
+                "this project uses format version {found}, but this build of egui-rad-builder \
+                 only understands up to version {FORMAT_VERSION}; please update egui-rad-builder"
+            ),
+            ProjectError::Migration { from, message } => {
+                write!(f, "could not upgrade project from format version {from}: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProjectError {}
+
+impl From<io::Error> for ProjectError {
+    fn from(e: io::Error) -> Self {
+        ProjectError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for ProjectError {
+    fn from(e: serde_json::Error) -> Self {
+        ProjectError::Json(e)
+    }
+}
+
+impl Project {
+    /// Parse a saved project of any known format version, upgrading it as needed
+    pub fn from_json(json: &str) -> Result<Self, ProjectError> {
+        let mut doc: serde_json::Value = serde_json::from_str(json)?;
+        let version = match doc.get(VERSION_KEY) {
+            // documents from before versioning
+            None => 0,
+            Some(v) => v.as_u64().ok_or_else(|| ProjectError::Migration {
+                from: 0,
+                message: format!("`{VERSION_KEY}` must be a number, found {v}"),
+            })?,
+        };
+        migrate::upgrade(&mut doc, version)?;
+        if let Some(obj) = doc.as_object_mut() {
+            obj.remove(VERSION_KEY);
+        }
+        Ok(serde_json::from_value(doc)?)
+    }
+
+    /// Serialize with the current format version
+    pub fn to_json(&self) -> Result<String, ProjectError> {
+        let mut doc = serde_json::to_value(self)?;
+        if let Some(obj) = doc.as_object_mut() {
+            obj.insert(VERSION_KEY.into(), FORMAT_VERSION.into());
+        }
+        Ok(serde_json::to_string_pretty(&doc)?)
+    }
+
+    /// Read a project file saved by the builder, with its asset paths resolved
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ProjectError> {
+        let path = path.as_ref();
+        let mut project = Self::from_json(&std::fs::read_to_string(path)?)?;
+        project.resolve_assets(path.parent().unwrap_or(Path::new("")));
+        Ok(project)
+    }
+
+    /// Make relative asset paths relative to `dir`, where the design they were saved with lives
+    pub fn resolve_assets(&mut self, dir: &Path) {
+        for asset in &mut self.assets {
+            asset.path = dir.join(&asset.path);
+        }
+    }
+
+    /// Write the project as pretty printed JSON, assets next to it by relative paths
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ProjectError> {
+        let path = path.as_ref();
+        let mut project = self.clone();
+        let dir = path.parent().and_then(|dir| dir.canonicalize().ok());
+        for asset in &mut project.assets {
+            let relative = dir.as_ref().and_then(|dir| asset.path.canonicalize().ok()?.strip_prefix(dir).ok().map(PathBuf::from));
+            if let Some(relative) = relative {
+                asset.path = relative;
+            }
+        }
+        let mut json = project.to_json()?;
+        json.push('\n');
+        Ok(std::fs::write(path, json)?)
+    }
+
+    /// The asset called `name`
+    pub fn asset(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|a| a.name == name)
+    }
+
+    /// The first widget id greater than every id in use
+    pub fn next_free_id(&self) -> u64 {
+        self.widgets.iter().map(|w| w.id.value() + 1).max().unwrap_or(1)
+    }
+
+    /// The widget with id `id`
+    pub fn widget(&self, id: WidgetId) -> Option<&Widget> {
+        self.widgets.iter().find(|w| w.id == id)
+    }
+
+    /// The widgets placed directly in container `id`, in project order
+    pub fn children(&self, id: WidgetId) -> impl Iterator<Item = &Widget> {
+        self.widgets.iter().filter(move |w| w.parent == Some(id))
+    }
+
+    /// Whether `id` is `container` or placed in it, however deeply
+    pub fn is_within(&self, id: WidgetId, container: WidgetId) -> bool {
+        let mut current = Some(id);
+        // a hand edited file could have a cycle
+        for _ in 0..=self.widgets.len() {
+            match current {
+                Some(id) if id == container => return true,
+                Some(id) => current = self.widget(id).and_then(|w| w.parent),
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// `ids` and every widget placed in them
+    pub fn with_contents(&self, ids: &BTreeSet<WidgetId>) -> BTreeSet<WidgetId> {
+        self.widgets
+            .iter()
+            .filter(|w| ids.iter().any(|&id| self.is_within(w.id, id)))
+            .map(|w| w.id)
+            .collect()
+    }
+}
@@ -1,12 +1,19 @@
-use egui::{Pos2, Vec2, pos2};
+use crate::{
+    codegen::{AssetMode, float, ident, is_identifier},
+    project::ASSET_DIR,
+};
+use egui::{Align, Color32, Image, Pos2, Vec2, pos2, vec2};
+use proc_macro2::{Ident, Literal, TokenStream};
+use quote::{format_ident, quote};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
-pub(crate) struct WidgetId(u64);
+/// Unique id of a widget within a [`Project`](crate::project::Project)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct WidgetId(u64);
 
 impl WidgetId {
-    pub(crate) const fn new(id: u64) -> Self {
+    pub const fn new(id: u64) -> Self {
         Self(id)
     }
 
@@ -14,7 +21,7 @@ impl WidgetId {
         self.0 as i32
     }
 
-    pub(crate) const fn value(&self) -> u64 {
+    pub const fn value(&self) -> u64 {
         self.0
     }
 }
@@ -25,8 +32,10 @@ impl fmt::Display for WidgetId {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
-pub(crate) enum DockArea {
+/// Which part of the generated window a widget is placed in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DockArea {
+    #[default]
     Free,
     Top,
     Bottom,
@@ -35,30 +44,94 @@ pub(crate) enum DockArea {
     Center,
 }
 
-impl Default for DockArea {
-    fn default() -> Self {
-        DockArea::Free
+impl DockArea {
+    pub const ALL: [DockArea; 6] = [
+        DockArea::Free,
+        DockArea::Top,
+        DockArea::Bottom,
+        DockArea::Left,
+        DockArea::Right,
+        DockArea::Center,
+    ];
+
+    /// Whether widgets in both areas are drawn on the same surface (Free lives on the center canvas)
+    pub fn shares_surface(self, other: DockArea) -> bool {
+        let surface = |a| if a == DockArea::Free { DockArea::Center } else { a };
+        surface(self) == surface(other)
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub(crate) struct Widget {
-    pub(crate) id: WidgetId,
-    pub(crate) kind: WidgetKind,
-    pub(crate) pos: Pos2,  // Top-left relative to canvas
-    pub(crate) size: Vec2, // Desired size on canvas
-    pub(crate) z: i32,     // draw order
-    pub(crate) area: DockArea,
-    pub(crate) props: WidgetProps,
+/// A widget placed on the canvas
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Widget {
+    pub id: WidgetId,
+    pub kind: WidgetKind,
+    pub pos: Pos2,  // Top-left relative to canvas, or to the content of its container
+    pub size: Vec2, // Desired size on canvas
+    #[serde(default)]
+    pub z: i32, // draw order
+    #[serde(default)]
+    pub area: DockArea,
+    /// The container this widget is placed in, it shares the container's area
+    #[serde(default)]
+    pub parent: Option<WidgetId>,
+    #[serde(default)]
+    pub props: WidgetProps,
+    /// Name of the generated state field, blank to derive one from the kind and id
+    #[serde(default)]
+    pub name: String,
+    /// Events reported by the generated UI function
+    #[serde(default)]
+    pub events: Vec<WidgetEvent>,
+}
+
+/// Something a user does with a widget that the generated code reports back
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum WidgetEvent {
+    Clicked,
+    DoubleClicked,
+    /// The widget's state field changed
+    Changed,
+    LostFocus,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+impl WidgetEvent {
+    pub const ALL: [WidgetEvent; 4] = [
+        WidgetEvent::Clicked,
+        WidgetEvent::DoubleClicked,
+        WidgetEvent::Changed,
+        WidgetEvent::LostFocus,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WidgetEvent::Clicked => "Clicked",
+            WidgetEvent::DoubleClicked => "Double-clicked",
+            WidgetEvent::Changed => "Changed",
+            WidgetEvent::LostFocus => "Lost focus",
+        }
+    }
+
+    /// The `egui::Response` method that detects the event, `None` for [`WidgetEvent::Changed`]
+    fn response_check(self) -> Option<&'static str> {
+        match self {
+            WidgetEvent::Clicked => Some("clicked"),
+            WidgetEvent::DoubleClicked => Some("double_clicked"),
+            WidgetEvent::Changed => None,
+            WidgetEvent::LostFocus => Some("lost_focus"),
+        }
+    }
+}
+
+/// The egui widget a [`Widget`] turns into
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "t", content = "c")]
-pub(crate) enum WidgetKind {
+pub enum WidgetKind {
     MenuButton,
     Label,
     Button,
     ImageTextButton,
+    Image,
     Checkbox,
     TextEdit,
     Slider,
@@ -74,25 +147,393 @@ pub(crate) enum WidgetKind {
     AngleSelector,
     Password,
     Tree,
+    Plot,
+    Table,
+    /// A framed group of widgets placed inside it
+    Frame,
+    /// Scrolls the widgets placed inside it when they don't fit
+    ScrollArea,
+    /// Widgets side by side, in their order
+    Horizontal,
+    /// Widgets one below the other, in their order
+    Vertical,
+    /// Widgets in rows of a fixed number of columns, in their order
+    Grid,
+    /// Widgets splitting its space evenly (egui_extras), side by side or stacked
+    Strip,
+}
+
+impl WidgetKind {
+    pub const ALL: [WidgetKind; 28] = [
+        WidgetKind::MenuButton,
+        WidgetKind::Label,
+        WidgetKind::Button,
+        WidgetKind::ImageTextButton,
+        WidgetKind::Image,
+        WidgetKind::Checkbox,
+        WidgetKind::TextEdit,
+        WidgetKind::Slider,
+        WidgetKind::ProgressBar,
+        WidgetKind::RadioGroup,
+        WidgetKind::Link,
+        WidgetKind::Hyperlink,
+        WidgetKind::SelectableLabel,
+        WidgetKind::ComboBox,
+        WidgetKind::Separator,
+        WidgetKind::CollapsingHeader,
+        WidgetKind::DatePicker,
+        WidgetKind::AngleSelector,
+        WidgetKind::Password,
+        WidgetKind::Tree,
+        WidgetKind::Plot,
+        WidgetKind::Table,
+        WidgetKind::Frame,
+        WidgetKind::ScrollArea,
+        WidgetKind::Horizontal,
+        WidgetKind::Vertical,
+        WidgetKind::Grid,
+        WidgetKind::Strip,
+    ];
+
+    /// Prefix of the generated state field for kinds that keep state, e.g. `text` for `text_{id}`;
+    /// plots keep one field per series named after the plot's field, tables their rows
+    pub fn state_prefix(&self) -> Option<&'static str> {
+        match self {
+            WidgetKind::TextEdit => Some("text"),
+            WidgetKind::Checkbox => Some("checked"),
+            WidgetKind::Slider => Some("value"),
+            WidgetKind::ProgressBar => Some("progress"),
+            WidgetKind::SelectableLabel
+            | WidgetKind::RadioGroup
+            | WidgetKind::ComboBox
+            | WidgetKind::MenuButton => Some("sel"),
+            WidgetKind::CollapsingHeader => Some("open"),
+            WidgetKind::DatePicker => Some("date"),
+            WidgetKind::Password => Some("pass"),
+            WidgetKind::AngleSelector => Some("angle"),
+            WidgetKind::Plot => Some("plot"),
+            WidgetKind::Table => Some("table"),
+            _ => None,
+        }
+    }
+
+    /// Events the generated code can report for this kind
+    pub fn events(&self) -> &'static [WidgetEvent] {
+        use WidgetEvent::*;
+        match self {
+            WidgetKind::Button
+            | WidgetKind::ImageTextButton
+            | WidgetKind::Link
+            | WidgetKind::Hyperlink => &[Clicked, DoubleClicked],
+            WidgetKind::TextEdit | WidgetKind::Password => &[Changed, LostFocus],
+            WidgetKind::Checkbox
+            | WidgetKind::Slider
+            | WidgetKind::SelectableLabel
+            | WidgetKind::RadioGroup
+            | WidgetKind::ComboBox
+            | WidgetKind::MenuButton
+            | WidgetKind::DatePicker
+            | WidgetKind::AngleSelector => &[Changed],
+            _ => &[],
+        }
+    }
+
+    /// Whether the inspector offers a "Text" field for this kind
+    pub fn has_text(&self) -> bool {
+        match self {
+            WidgetKind::Label
+            | WidgetKind::Button
+            | WidgetKind::ImageTextButton
+            | WidgetKind::TextEdit
+            | WidgetKind::Checkbox
+            | WidgetKind::Slider
+            | WidgetKind::Link
+            | WidgetKind::Hyperlink
+            | WidgetKind::SelectableLabel
+            | WidgetKind::CollapsingHeader
+            | WidgetKind::Password
+            | WidgetKind::AngleSelector
+            | WidgetKind::DatePicker
+            | WidgetKind::MenuButton => true,
+            WidgetKind::ProgressBar
+            | WidgetKind::RadioGroup
+            | WidgetKind::ComboBox
+            | WidgetKind::Tree
+            | WidgetKind::Separator
+            | WidgetKind::Image
+            | WidgetKind::Plot
+            | WidgetKind::Table
+            | WidgetKind::Frame
+            | WidgetKind::ScrollArea
+            | WidgetKind::Horizontal
+            | WidgetKind::Vertical
+            | WidgetKind::Grid
+            | WidgetKind::Strip => false,
+        }
+    }
+
+    /// Whether widgets can be placed inside this kind
+    pub fn is_container(&self) -> bool {
+        matches!(
+            self,
+            WidgetKind::Frame
+                | WidgetKind::ScrollArea
+                | WidgetKind::CollapsingHeader
+                | WidgetKind::Horizontal
+                | WidgetKind::Vertical
+                | WidgetKind::Grid
+                | WidgetKind::Strip
+        )
+    }
+
+    /// Whether this container lays its children out one after another, ignoring their positions
+    pub fn flows(&self) -> bool {
+        matches!(self, WidgetKind::Horizontal | WidgetKind::Vertical | WidgetKind::Grid | WidgetKind::Strip)
+    }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub(crate) struct WidgetProps {
-    pub(crate) text: String,  // label/button/textedit placeholder
-    pub(crate) checked: bool, // checkbox
-    pub(crate) value: f32,    // slider/progress
-    pub(crate) min: f32,
-    pub(crate) max: f32,
+/// Kind specific properties, each kind only reads the ones it needs
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WidgetProps {
+    pub text: String,  // label/button/textedit placeholder
+    pub checked: bool, // checkbox
+    pub value: f32,    // slider/progress
+    pub min: f32,
+    pub max: f32,
     // lists (for radio/combobox)
-    pub(crate) items: Vec<String>,
-    pub(crate) selected: usize,
+    pub items: Vec<String>,
+    pub selected: usize,
     // hyperlinks
-    pub(crate) url: String,
+    pub url: String,
     // date (stored as y/m/d to avoid chrono serde feature requirements)
-    pub(crate) year: i32,
-    pub(crate) month: u32,
-    pub(crate) day: u32,
-    pub(crate) icon: String,
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub icon: String,
+    // images
+    /// Name of the project asset shown, for image+text buttons instead of the icon
+    pub image: String,
+    pub fit: ImageFit,
+    /// Scale of [`ImageFit::Original`]
+    pub scale: f32,
+    pub tint: Color32,
+    pub rounding: u8,
+    // plots
+    pub series: Vec<PlotSeries>,
+    pub x_label: String,
+    pub y_label: String,
+    pub legend: bool,
+    pub grid: bool,
+    /// Whether the plot can be zoomed with the mouse wheel and a box drawn with the secondary button
+    pub zoom: bool,
+    /// Whether the plot can be panned by dragging and scrolling
+    pub drag: bool,
+    // tables
+    pub columns: Vec<TableColumn>,
+    /// Sample rows, the text of their cells by column
+    pub rows: Vec<Vec<String>>,
+    pub striped: bool,
+    /// Whether the header stays in view while the rows scroll
+    pub sticky_header: bool,
+    /// Whether clicking a header sorts the rows by its column
+    pub sortable: bool,
+    // layouts
+    /// Space between the widgets a layout holds, egui's default when `None`
+    pub spacing: Option<Vec2>,
+    /// How a horizontal or vertical layout lines its widgets up across its direction,
+    /// egui's default when `None`
+    pub align: Option<Align>,
+    /// Number of columns of a grid
+    pub grid_columns: usize,
+    /// Whether a strip stacks its widgets instead of placing them side by side
+    pub vertical: bool,
+}
+
+/// How an image fills the space of its widget
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageFit {
+    /// As large as fits, keeping the aspect ratio
+    #[default]
+    Contain,
+    /// Exactly the widget's size
+    Stretch,
+    /// The image's own size times the scale
+    Original,
+}
+
+impl ImageFit {
+    pub const ALL: [ImageFit; 3] = [ImageFit::Contain, ImageFit::Stretch, ImageFit::Original];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ImageFit::Contain => "Fit, keep aspect ratio",
+            ImageFit::Stretch => "Stretch to fill",
+            ImageFit::Original => "Original size",
+        }
+    }
+}
+
+/// How a plot series is drawn
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SeriesStyle {
+    #[default]
+    Line,
+    Bar,
+    Scatter,
+}
+
+impl SeriesStyle {
+    pub const ALL: [SeriesStyle; 3] = [SeriesStyle::Line, SeriesStyle::Bar, SeriesStyle::Scatter];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SeriesStyle::Line => "Line",
+            SeriesStyle::Bar => "Bars",
+            SeriesStyle::Scatter => "Scatter",
+        }
+    }
+}
+
+/// Radius of the markers of [`SeriesStyle::Scatter`]
+pub const SCATTER_RADIUS: f32 = 3.0;
+
+/// A data series of a plot
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlotSeries {
+    /// Shown in the legend, also names the generated state field
+    pub name: String,
+    pub style: SeriesStyle,
+    /// Sample data for the canvas, the initial value of the state field
+    pub points: Vec<[f64; 2]>,
+}
+
+impl PlotSeries {
+    /// A series with made-up data that suits its style
+    pub fn sample(name: &str, style: SeriesStyle) -> Self {
+        let points = match style {
+            SeriesStyle::Line => (0..=10)
+                .map(|x| [x as f64, ((x as f64 * 0.6).sin() * 100.0).round() / 100.0])
+                .collect(),
+            SeriesStyle::Bar => [3.0, 5.0, 2.0, 6.0, 4.0].iter().zip(1..).map(|(&y, x)| [x as f64, y]).collect(),
+            SeriesStyle::Scatter => vec![[1.0, 2.1], [2.0, 3.9], [3.0, 3.2], [4.0, 5.8], [5.0, 5.1], [6.0, 7.4]],
+        };
+        Self { name: name.into(), style, points }
+    }
+}
+
+/// Height of the header row of tables
+pub const TABLE_HEADER_HEIGHT: f32 = 20.0;
+/// Height of the other rows of tables
+pub const TABLE_ROW_HEIGHT: f32 = 18.0;
+
+/// A column of a table
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TableColumn {
+    /// Shown in the header, also names the field of the row struct
+    pub header: String,
+    pub ty: ColumnType,
+    pub width: ColumnWidth,
+    pub resizable: bool,
+    pub align: ColumnAlign,
+}
+
+impl Default for TableColumn {
+    fn default() -> Self {
+        Self {
+            header: "Column".into(),
+            ty: ColumnType::Text,
+            width: ColumnWidth::Auto,
+            resizable: true,
+            align: ColumnAlign::Left,
+        }
+    }
+}
+
+/// Type of the values of a table column in the generated row struct
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnType {
+    #[default]
+    Text,
+    Integer,
+    Number,
+    Bool,
+}
+
+impl ColumnType {
+    pub const ALL: [ColumnType; 4] = [ColumnType::Text, ColumnType::Integer, ColumnType::Number, ColumnType::Bool];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ColumnType::Text => "Text (String)",
+            ColumnType::Integer => "Integer (i64)",
+            ColumnType::Number => "Number (f64)",
+            ColumnType::Bool => "Yes/no (bool)",
+        }
+    }
+
+    /// Why `cell` can't be a value of this type
+    pub fn parse_problem(self, cell: &str) -> Option<String> {
+        let cell = cell.trim();
+        let valid = match self {
+            ColumnType::Text => true,
+            // blank cells are the type's default
+            _ if cell.is_empty() => true,
+            ColumnType::Integer => cell.parse::<i64>().is_ok(),
+            ColumnType::Number => cell.parse::<f64>().is_ok_and(f64::is_finite),
+            ColumnType::Bool => cell.parse::<bool>().is_ok(),
+        };
+        (!valid).then(|| format!("`{cell}` is not a valid {}", self.label()))
+    }
+}
+
+/// How the width of a table column is chosen, see `egui_extras::Column`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum ColumnWidth {
+    /// Wide enough for the content
+    #[default]
+    Auto,
+    /// Starts out this wide
+    Initial(f32),
+    /// Always this wide
+    Exact(f32),
+    /// Takes up the remaining width
+    Remainder,
+}
+
+impl ColumnWidth {
+    pub fn label(self) -> &'static str {
+        match self {
+            ColumnWidth::Auto => "Fit content",
+            ColumnWidth::Initial(_) => "Initial width",
+            ColumnWidth::Exact(_) => "Fixed width",
+            ColumnWidth::Remainder => "Fill remaining",
+        }
+    }
+}
+
+/// Horizontal alignment of the cells of a table column
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColumnAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+impl ColumnAlign {
+    pub const ALL: [ColumnAlign; 3] = [ColumnAlign::Left, ColumnAlign::Center, ColumnAlign::Right];
+
+    /// The layout of the cells, `None` for egui's default left to right
+    pub fn layout(self) -> Option<egui::Layout> {
+        match self {
+            ColumnAlign::Left => None,
+            ColumnAlign::Center => Some(egui::Layout::top_down(egui::Align::Center)),
+            ColumnAlign::Right => Some(egui::Layout::right_to_left(egui::Align::Center)),
+        }
+    }
 }
 
 impl Default for WidgetProps {
@@ -110,221 +551,489 @@ impl Default for WidgetProps {
             month: 1,
             day: 1,
             icon: "🖼️".into(),
+            image: String::new(),
+            fit: ImageFit::Contain,
+            scale: 1.0,
+            tint: Color32::WHITE,
+            rounding: 0,
+            series: vec![],
+            x_label: String::new(),
+            y_label: String::new(),
+            legend: true,
+            grid: true,
+            zoom: true,
+            drag: true,
+            columns: vec![],
+            rows: vec![],
+            striped: true,
+            sticky_header: true,
+            sortable: false,
+            spacing: None,
+            align: None,
+            grid_columns: 2,
+            vertical: false,
         }
     }
 }
 
-pub(crate) fn snap_pos_with_grid(p: Pos2, grid: f32) -> Pos2 {
-    pos2((p.x / grid).round() * grid, (p.y / grid).round() * grid)
+impl WidgetProps {
+    /// `image` sized to `size` and styled the way the generated code does it
+    pub fn style_image<'a>(&self, image: Image<'a>, size: Vec2) -> Image<'a> {
+        let image = match self.fit {
+            ImageFit::Contain => image.fit_to_exact_size(size),
+            ImageFit::Stretch => image.fit_to_exact_size(size).maintain_aspect_ratio(false),
+            ImageFit::Original => image.fit_to_original_size(self.scale),
+        };
+        image.tint(self.tint).corner_radius(self.rounding)
+    }
+
+    /// The builder calls of [`style_image`](Self::style_image) as generated code
+    fn image_style_tokens(&self, size: &TokenStream) -> TokenStream {
+        let mut tokens = match self.fit {
+            ImageFit::Contain => quote! { .fit_to_exact_size(#size) },
+            ImageFit::Stretch => quote! { .fit_to_exact_size(#size).maintain_aspect_ratio(false) },
+            ImageFit::Original => {
+                let scale = float(self.scale, 3);
+                quote! { .fit_to_original_size(#scale) }
+            }
+        };
+        if self.tint != Color32::WHITE {
+            let [r, g, b, a] = self.tint.to_srgba_unmultiplied().map(Literal::u8_unsuffixed);
+            tokens.extend(quote! { .tint(egui::Color32::from_rgba_unmultiplied(#r, #g, #b, #a)) });
+        }
+        if self.rounding > 0 {
+            let rounding = Literal::u8_unsuffixed(self.rounding);
+            tokens.extend(quote! { .corner_radius(#rounding) });
+        }
+        tokens
+    }
+
+    /// Copy every field that differs between `before` and `after` onto `self`
+    pub fn apply_edits(&mut self, before: &Self, after: &Self) {
+        macro_rules! copy_edited {
+            ($($field:ident),* $(,)?) => {
+                $(if before.$field != after.$field {
+                    self.$field = after.$field.clone();
+                })*
+            };
+        }
+        copy_edited!(
+            text, checked, value, min, max, items, selected, url, year, month, day, icon, image, fit, scale,
+            tint, rounding, series, x_label, y_label, legend, grid, zoom, drag, columns, rows, striped,
+            sticky_header, sortable, spacing, align, grid_columns, vertical,
+        );
+    }
+}
+
+/// `egui::Align::Center` for `Align::Center`
+fn align_tokens(align: Align) -> TokenStream {
+    match align {
+        Align::Min => quote! { egui::Align::Min },
+        Align::Center => quote! { egui::Align::Center },
+        Align::Max => quote! { egui::Align::Max },
+    }
 }
 
-pub(crate) fn escape(s: &str) -> String {
-    s.replace('\\', "\\\\").replace('"', "\\\"")
+/// `Unit price (€)` -> `unit_price`, the ASCII letters and digits of `name` in snake case
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('_') {
+            slug.push('_');
+        }
+    }
+    slug.trim_end_matches('_').to_owned()
+}
+
+/// `login_button` -> `LoginButton`
+fn upper_camel(name: &str) -> String {
+    name.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or(String::new(), |c| c.to_ascii_uppercase().to_string() + chars.as_str())
+        })
+        .collect()
+}
+
+/// Round `p` to the nearest grid point
+pub fn snap_pos_with_grid(p: Pos2, grid: f32) -> Pos2 {
+    pos2((p.x / grid).round() * grid, (p.y / grid).round() * grid)
 }
 
 impl Widget {
-    /// Emit a widget block at rect (origin + local pos)
-    pub(crate) fn emit_widget(&self, out: &mut String, origin: &str) {
-        let w = self;
+    /// Apply the inspector edits that turned `before` into `after` to this widget
+    pub fn apply_edits(&mut self, before: &Widget, after: &Widget) {
+        if before.pos.x != after.pos.x {
+            self.pos.x = after.pos.x;
+        }
+        if before.pos.y != after.pos.y {
+            self.pos.y = after.pos.y;
+        }
+        if before.size.x != after.size.x {
+            self.size.x = after.size.x;
+        }
+        if before.size.y != after.size.y {
+            self.size.y = after.size.y;
+        }
+        if before.area != after.area {
+            self.area = after.area;
+        }
+        if before.name != after.name {
+            self.name = after.name.clone();
+        }
+        if before.events != after.events {
+            self.events = after.events.clone();
+        }
+        self.props.apply_edits(&before.props, &after.props);
+    }
 
-        let pos = w.pos;
-        let size = w.size;
+    /// Name of the generated field holding this widget's state, `None` for stateless kinds
+    pub fn state_field(&self) -> Option<String> {
+        let prefix = self.kind.state_prefix()?;
+        Some(if self.name.is_empty() {
+            format!("{prefix}_{}", self.id)
+        } else {
+            self.name.clone()
+        })
+    }
 
-        match w.kind {
-            WidgetKind::MenuButton => {
-                let items_code = if w.props.items.is_empty() {
-                    "\"Item\".to_string()".to_owned()
-                } else {
-                    w.props.items.iter().map(|s| format!("\"{}\".to_string()", escape(s))).collect::<Vec<_>>().join(", ")
-                };
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({x:.1},{y:.1}), egui::vec2({w:.1},{h:.1}))), |ui| {{\n",
-                    x=w.pos.x, y=w.pos.y, w=w.size.x, h=w.size.y
-                ));
-                out.push_str(&format!("        let items = vec![{items}];\n", items=items_code));
-                out.push_str(&format!(
-                    "        ui.menu_button(\"{}\", |ui| {{\n", escape(&w.props.text)
-                ));
-                out.push_str(&format!(
-                    "            for (i, it) in items.iter().enumerate() {{ if ui.button(it).clicked() {{ state.sel_{id} = i; ui.close_kind(egui::UiKind::Menu); }} }}\n",
-                    id = w.id
-                ));
-                out.push_str("        });\n");
-                out.push_str("    });\n");
+    /// The identifier generated code knows the widget by: its name, or else its state field
+    pub fn ident(&self) -> Option<String> {
+        if self.name.is_empty() {
+            self.state_field()
+        } else {
+            Some(self.name.clone())
+        }
+    }
+
+    /// The events enabled on this widget that its kind supports
+    pub fn active_events(&self) -> impl Iterator<Item = WidgetEvent> + '_ {
+        self.events.iter().copied().filter(|e| self.kind.events().contains(e))
+    }
+
+    /// Name of the event enum variant reporting `event` on this widget, e.g. `LoginClicked`
+    pub fn event_variant(&self, event: WidgetEvent) -> String {
+        let base = self.ident().unwrap_or_else(|| format!("{:?}_{}", self.kind, self.id));
+        let mut variant = upper_camel(&base);
+        variant.push_str(&format!("{event:?}"));
+        variant
+    }
+
+    /// Name of the state field holding series `i` of a plot, e.g. `plot_3_sales` for "Sales"
+    pub fn series_field(&self, i: usize) -> Option<String> {
+        let base = self.ident()?;
+        let slug = slug(&self.props.series.get(i)?.name);
+        Some(match slug.as_str() {
+            "" => format!("{base}_series{}", i + 1),
+            slug => format!("{base}_{slug}"),
+        })
+    }
+
+    /// The state fields of all series of a plot, empty for other kinds
+    pub fn series_fields(&self) -> Vec<String> {
+        if self.kind != WidgetKind::Plot {
+            return Vec::new();
+        }
+        (0..self.props.series.len()).filter_map(|i| self.series_field(i)).collect()
+    }
+
+    /// Name of the field of the row struct holding column `i` of a table, e.g. `unit_price`
+    pub fn column_field(&self, i: usize) -> Option<String> {
+        let slug = slug(&self.props.columns.get(i)?.header);
+        Some(if slug.is_empty() || slug.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("column{}", i + 1)
+        } else if is_identifier(&slug) {
+            slug
+        } else {
+            // keywords like `type`
+            format!("{slug}_")
+        })
+    }
+
+    /// Name of the generated struct of a table's rows, e.g. `OrdersRow` for `orders`
+    pub fn row_struct(&self) -> Option<String> {
+        if self.kind != WidgetKind::Table {
+            return None;
+        }
+        Some(upper_camel(&self.ident()?) + "Row")
+    }
+
+    /// Name of the state field with the column and direction a table is sorted by
+    pub fn sort_field(&self) -> Option<String> {
+        if self.kind != WidgetKind::Table || !self.props.sortable {
+            return None;
+        }
+        Some(format!("{}_sort", self.ident()?))
+    }
+
+    /// State fields named after this widget's own: the series of a plot, the sort order of a table
+    pub fn derived_fields(&self) -> Vec<String> {
+        let mut fields = self.series_fields();
+        fields.extend(self.sort_field());
+        fields
+    }
+
+    /// Whether the widget shows an image asset
+    pub fn uses_image(&self) -> bool {
+        match self.kind {
+            WidgetKind::Image => true,
+            WidgetKind::ImageTextButton => !self.props.image.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Size the image of an image widget or image+text button is fitted to
+    pub fn image_size(&self) -> Vec2 {
+        match self.kind {
+            WidgetKind::ImageTextButton => {
+                // leave room for the button frame
+                let side = (self.size.y - 8.0).max(0.0);
+                vec2(side, side)
             }
-            WidgetKind::Label => out.push_str(&format!(
-                "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ ui.label(\"{}\"); }});\n",
-                pos.x,pos.y,size.x,size.y,escape(&w.props.text)
-            )),
-            WidgetKind::Button => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ ui.add_sized(egui::vec2({:.1},{:.1}), egui::Button::new(\"{}\")); }});\n",
-                    pos.x, pos.y, size.x, size.y, size.x, size.y, escape(&w.props.text)
-                ));
+            _ => self.size,
+        }
+    }
+
+    /// Statements drawing the widget at rect (origin + local pos), or in the next spot of a
+    /// layout without `origin`; containers draw the statements of their `children` inside. Enabled events are pushed onto a `Vec` named `events` as
+    /// variants of the enum `event_enum`, images are read from [`ASSET_DIR`] as `assets` says
+    pub fn emit_widget(
+        &self,
+        origin: Option<&TokenStream>,
+        children: &[TokenStream],
+        event_enum: &Ident,
+        assets: AssetMode,
+    ) -> TokenStream {
+        let w = self;
+        let field = w.state_field().map(|f| ident(&f));
+        let events: Vec<WidgetEvent> = w.active_events().collect();
+        let (x, y) = (float(w.pos.x, 1), float(w.pos.y, 1));
+        let (width, height) = (float(w.size.x, 1), float(w.size.y, 1));
+        let size = quote! { egui::vec2(#width, #height) };
+        let scope = |body: TokenStream| match origin {
+            Some(origin) => quote! {
+                ui.scope_builder(
+                    egui::UiBuilder::new().max_rect(egui::Rect::from_min_size(#origin + egui::vec2(#x, #y), #size)),
+                    |ui| #body
+                )
+            },
+            // in layouts the widget goes where the layout is at, taking as much of its size as it uses
+            None => quote! { ui.allocate_ui(#size, |ui| #body) },
+        };
+        // set before the layout is made, so it and what it holds use it
+        let spacing = w.props.spacing.map(|spacing| {
+            let (x, y) = (float(spacing.x, 1), float(spacing.y, 1));
+            quote! { ui.spacing_mut().item_spacing = egui::vec2(#x, #y); }
+        });
+        // the closure holding the children of a container
+        let contents = |setup: TokenStream| {
+            let ui = ident(if children.is_empty() && setup.is_empty() { "_ui" } else { "ui" });
+            quote! { |#ui| { #setup #(#children)* } }
+        };
+        // widgets whose events need the `egui::Response` are emitted as a plain expression
+        let response = |expr: TokenStream| {
+            let checks: Vec<_> = events
+                .iter()
+                .filter_map(|e| Some((ident(e.response_check()?), ident(&w.event_variant(*e)))))
+                .collect();
+            if checks.is_empty() {
+                let scoped = scope(quote! {{ #expr; }});
+                return quote! { #scoped; };
             }
-            WidgetKind::ImageTextButton => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size(\
-                        {origin} + egui::vec2({x:.1},{y:.1}), \
-                        egui::vec2({w:.1},{h:.1}))), |ui| {{ \
-                        ui.add_sized(egui::vec2({w:.1},{h:.1}), \
-                            egui::Button::new(format!(\"{{}}  {{}}\", \"{icon}\", \"{text}\")) \
-                        ); \
-                    }});\n",
-                    x = pos.x,
-                    y = pos.y,
-                    w = size.x,
-                    h = size.y,
-                    icon = escape(&w.props.icon),
-                    text = escape(&w.props.text),
-                ));
+            let var = format_ident!("response_{}", w.id.value());
+            let scoped = scope(expr);
+            let checks = checks.iter().map(|(check, variant)| {
+                quote! { if #var.#check() { events.push(#event_enum::#variant); } }
+            });
+            quote! {
+                let #var = #scoped.inner;
+                #(#checks)*
             }
-            WidgetKind::Checkbox => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ ui.checkbox(&mut state.checked_{}, \"{}\"); }});\n",
-                    pos.x, pos.y, size.x, size.y, w.id, escape(&w.props.text)
-                ));
+        };
+        let block = |body: TokenStream| {
+            let scoped = scope(quote! {{ #body }});
+            quote! { #scoped; }
+        };
+        let text = Literal::string(&w.props.text);
+        let items = || {
+            let items = if w.props.items.is_empty() {
+                vec!["Item".to_owned()]
+            } else {
+                w.props.items.clone()
+            };
+            let items = items.iter().map(|s| Literal::string(s));
+            quote! { vec![#(#items.to_string()),*] }
+        };
+        let min = float(w.props.min, 3);
+        let max = float(w.props.max, 3);
+        let image = || {
+            let file = format!("{ASSET_DIR}/{}", w.props.image);
+            let source = match assets {
+                AssetMode::Embed => {
+                    let uri = Literal::string(&format!("bytes://{file}"));
+                    let path = Literal::string(&format!("/{file}"));
+                    quote! {
+                        egui::Image::from_bytes(#uri, include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), #path)))
+                    }
+                }
+                AssetMode::Load => {
+                    let uri = Literal::string(&format!("file://{file}"));
+                    quote! { egui::Image::new(#uri) }
+                }
+            };
+            let image_size = w.image_size();
+            let (width, height) = (float(image_size.x, 1), float(image_size.y, 1));
+            let style = w.props.image_style_tokens(&quote! { egui::vec2(#width, #height) });
+            quote! { #source #style }
+        };
+
+        let widget = match w.kind {
+            WidgetKind::MenuButton => {
+                let items = items();
+                block(quote! {
+                    let items = #items;
+                    ui.menu_button(#text, |ui| {
+                        for (i, it) in items.iter().enumerate() {
+                            if ui.button(it).clicked() {
+                                state.#field = i;
+                                ui.close_kind(egui::UiKind::Menu);
+                            }
+                        }
+                    });
+                })
             }
-            WidgetKind::TextEdit => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ ui.add_sized(egui::vec2({:.1},{:.1}), egui::TextEdit::singleline(&mut state.text_{}).hint_text(\"{}\")); }});\n",
-                    pos.x, pos.y, size.x, size.y, size.x, size.y, w.id, escape(&w.props.text)
-                ));
+            WidgetKind::Label => block(quote! { ui.label(#text); }),
+            WidgetKind::Button => response(quote! { ui.add_sized(#size, egui::Button::new(#text)) }),
+            WidgetKind::ImageTextButton if w.props.image.is_empty() => {
+                let icon = Literal::string(&w.props.icon);
+                response(quote! { ui.add_sized(#size, egui::Button::new(format!("{}  {}", #icon, #text))) })
             }
-            WidgetKind::Slider => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ ui.add_sized(egui::vec2({:.1},{:.1}), egui::Slider::new(&mut state.value_{}, {:.3}..={:.3}).text(\"{}\")); }});\n",
-                    pos.x, pos.y, size.x, size.y, size.x, size.y, w.id, w.props.min, w.props.max, escape(&w.props.text)
-                ));
+            WidgetKind::ImageTextButton => {
+                let image = image();
+                response(quote! { ui.add_sized(#size, egui::Button::image_and_text(#image, #text)) })
             }
-            WidgetKind::ProgressBar => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ ui.add_sized(egui::vec2({:.1},{:.1}), egui::ProgressBar::new(state.progress_{}).show_percentage()); }});\n",
-                    pos.x, pos.y, size.x, size.y, size.x, size.y, w.id
-                ));
+            WidgetKind::Image => {
+                let image = image();
+                block(quote! { ui.add_sized(#size, #image); })
             }
+            WidgetKind::Checkbox => block(quote! { ui.checkbox(&mut state.#field, #text); }),
+            WidgetKind::TextEdit => response(quote! {
+                ui.add_sized(#size, egui::TextEdit::singleline(&mut state.#field).hint_text(#text))
+            }),
+            WidgetKind::Slider => block(quote! {
+                ui.add_sized(#size, egui::Slider::new(&mut state.#field, #min..=#max).text(#text));
+            }),
+            WidgetKind::ProgressBar => block(quote! {
+                ui.add_sized(#size, egui::ProgressBar::new(state.#field).show_percentage());
+            }),
             WidgetKind::RadioGroup => {
-                let items_code = if w.props.items.is_empty() {
-                    "\"Item\".to_string()".to_owned()
-                } else {
-                    w.props
-                        .items
-                        .iter()
-                        .map(|s| format!("\"{}\".to_string()", escape(s)))
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                };
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{\n",
-                    pos.x, pos.y, size.x, size.y
-                ));
-                out.push_str(&format!("        let items = vec![{}];\n", items_code));
-                out.push_str(&format!(
-                    "        for (i, it) in items.iter().enumerate() {{ if ui.add(egui::RadioButton::new(state.sel_{} == i, it)).clicked() {{ state.sel_{} = i; }} }}\n",
-                    w.id, w.id
-                ));
-                out.push_str("    });\n");
-            }
-            WidgetKind::Link => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ ui.link(\"{}\"); }});\n",
-                    pos.x, pos.y, size.x, size.y, escape(&w.props.text)
-                ));
+                let items = items();
+                block(quote! {
+                    let items = #items;
+                    for (i, it) in items.iter().enumerate() {
+                        if ui.add(egui::RadioButton::new(state.#field == i, it)).clicked() {
+                            state.#field = i;
+                        }
+                    }
+                })
             }
+            WidgetKind::Link => response(quote! { ui.add(egui::Link::new(#text)) }),
             WidgetKind::Hyperlink => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ ui.hyperlink_to(\"{}\", \"{}\"); }});\n",
-                    pos.x, pos.y, size.x, size.y, escape(&w.props.text), escape(&w.props.url)
-                ));
-            }
-            WidgetKind::SelectableLabel => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ if ui.add(egui::Button::selectable(state.sel_{}, \"{}\")).clicked() {{ state.sel_{} = !state.sel_{}; }} }});\n",
-                    pos.x, pos.y, size.x, size.y, w.id, escape(&w.props.text), w.id, w.id
-                ));
+                let url = Literal::string(&w.props.url);
+                response(quote! { ui.hyperlink_to(#text, #url) })
             }
+            WidgetKind::SelectableLabel => block(quote! {
+                if ui.add(egui::Button::selectable(state.#field, #text)).clicked() {
+                    state.#field = !state.#field;
+                }
+            }),
             WidgetKind::ComboBox => {
-                let items_code = if w.props.items.is_empty() {
-                    "\"Item\".to_string()".to_owned()
-                } else {
-                    w.props
-                        .items
-                        .iter()
-                        .map(|s| format!("\"{}\".to_string()", escape(s)))
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                };
-
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({x:.1},{y:.1}), egui::vec2({w:.1},{h:.1}))), |ui| {{\n",
-                    x = pos.x, y = pos.y, w = size.x, h = size.y
-                ));
-                out.push_str(&format!(
-                    "        let items = vec![{items}];\n",
-                    items = items_code
-                ));
-                out.push_str(&format!(
-                    "        egui::ComboBox::from_id_source({id})\n",
-                    id = w.id
-                ));
-                out.push_str(&format!("            .width({:.1})\n", size.x));
-                out.push_str(&format!(
-                    "            .selected_text(items.get(state.sel_{id}).cloned().unwrap_or_else(|| \"\".to_string()))\n",
-                    id = w.id
-                ));
-                out.push_str("            .show_ui(ui, |ui| {\n");
-                out.push_str(&format!(
-                    "                for (i, it) in items.iter().enumerate() {{ ui.selectable_value(&mut state.sel_{id}, i, it.clone()); }}\n",
-                    id = w.id
-                ));
-                out.push_str("            });\n");
-                out.push_str("    });\n");
-            }
-            WidgetKind::Separator => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ ui.separator(); }});\n",
-                    pos.x, pos.y, size.x, size.y
-                ));
+                let items = items();
+                let id = Literal::u64_unsuffixed(w.id.value());
+                block(quote! {
+                    let items = #items;
+                    egui::ComboBox::from_id_salt(#id)
+                        .width(#width)
+                        .selected_text(items.get(state.#field).cloned().unwrap_or_default())
+                        .show_ui(ui, |ui| {
+                            for (i, it) in items.iter().enumerate() {
+                                ui.selectable_value(&mut state.#field, i, it.clone());
+                            }
+                        });
+                })
             }
+            WidgetKind::Separator => block(quote! { ui.separator(); }),
             WidgetKind::CollapsingHeader => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ egui::CollapsingHeader::new(\"{}\").default_open(state.open_{}).show(ui, |ui| {{ ui.label(\"… place your inner content here …\"); }}); }});\n",
-                    pos.x, pos.y, size.x, size.y, escape(&w.props.text), w.id
-                ));
+                let contents = contents(TokenStream::new());
+                block(quote! {
+                    egui::CollapsingHeader::new(#text).default_open(state.#field).show(ui, #contents);
+                })
+            }
+            WidgetKind::Frame => {
+                // the frame fills the widget's rect whatever it holds
+                let contents = contents(quote! { ui.set_min_size(ui.available_size()); });
+                block(quote! { egui::Frame::group(ui.style()).show(ui, #contents); })
+            }
+            WidgetKind::ScrollArea => {
+                let id = Literal::u64_unsuffixed(w.id.value());
+                let contents = contents(TokenStream::new());
+                block(quote! { egui::ScrollArea::both().id_salt(#id).auto_shrink(false).show(ui, #contents); })
             }
-            WidgetKind::DatePicker => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size({origin} + egui::vec2({:.1},{:.1}), egui::vec2({:.1},{:.1}))), |ui| {{ ui.horizontal(|ui| {{ ui.label(\"{}\"); ui.add(DatePickerButton::new(&mut state.date_{})); }}); }});\n",
-                    pos.x, pos.y, size.x, size.y, escape(&w.props.text), w.id
-                ));
+            WidgetKind::Horizontal | WidgetKind::Vertical => {
+                let contents = contents(TokenStream::new());
+                let horizontal = w.kind == WidgetKind::Horizontal;
+                let layout = match w.props.align {
+                    Some(align) => {
+                        let align = align_tokens(align);
+                        let layout = if horizontal {
+                            quote! { egui::Layout::left_to_right(#align) }
+                        } else {
+                            quote! { egui::Layout::top_down(#align) }
+                        };
+                        quote! { ui.with_layout(#layout, #contents) }
+                    }
+                    None if horizontal => quote! { ui.horizontal(#contents) },
+                    None => quote! { ui.vertical(#contents) },
+                };
+                block(quote! { #spacing #layout; })
             }
-            WidgetKind::Password => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size(\
-                        {origin} + egui::vec2({x:.1},{y:.1}), egui::vec2({w:.1},{h:.1}))), |ui| {{ \
-                        ui.add_sized(egui::vec2({w:.1},{h:.1}), \
-                            egui::TextEdit::singleline(&mut state.pass_{id}).password(true).hint_text(\"password\") \
-                        ); \
-                    }});\n",
-                    x = pos.x,
-                    y = pos.y,
-                    w = size.x,
-                    h = size.y,
-                    id = w.id,
-                ));
+            WidgetKind::Grid => {
+                let id = Literal::u64_unsuffixed(w.id.value());
+                let columns = w.props.grid_columns.max(1);
+                let num_columns = Literal::usize_unsuffixed(columns);
+                let striped = w.props.striped.then(|| quote! { .striped(true) });
+                let rows = children.chunks(columns).map(|row| quote! { #(#row)* ui.end_row(); });
+                let ui = ident(if children.is_empty() { "_ui" } else { "ui" });
+                block(quote! {
+                    #spacing
+                    egui::Grid::new(#id).num_columns(#num_columns) #striped .show(ui, |#ui| { #(#rows)* });
+                })
             }
-            WidgetKind::AngleSelector => {
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size(\
-                        {origin} + egui::vec2({x:.1},{y:.1}), egui::vec2({w:.1},{h:.1}))), |ui| {{ \
-                        ui.add_sized(egui::vec2({w:.1},{h:.1}), \
-                            egui::Slider::new(&mut state.angle_{id}, {min:.3}..={max:.3}).suffix(\"°\").text(\"{label}\") \
-                        ); \
-                    }});\n",
-                    x=pos.x,y=pos.y,w=size.x,h=size.y,id=w.id,
-                    min=w.props.min, max=w.props.max, label=escape(&w.props.text)
-                ));
+            WidgetKind::Strip => {
+                let cells = Literal::usize_unsuffixed(children.len());
+                let direction = ident(if w.props.vertical { "vertical" } else { "horizontal" });
+                let strip = if children.is_empty() { quote! { _strip } } else { quote! { mut strip } };
+                block(quote! {
+                    #spacing
+                    egui_extras::StripBuilder::new(ui)
+                        .sizes(egui_extras::Size::remainder(), #cells)
+                        .#direction(|#strip| { #(strip.cell(|ui| { #children });)* });
+                })
             }
+            WidgetKind::DatePicker => block(quote! {
+                ui.horizontal(|ui| {
+                    ui.label(#text);
+                    ui.add(DatePickerButton::new(&mut state.#field));
+                });
+            }),
+            WidgetKind::Password => response(quote! {
+                ui.add_sized(#size, egui::TextEdit::singleline(&mut state.#field).password(true).hint_text("password"))
+            }),
+            WidgetKind::AngleSelector => block(quote! {
+                ui.add_sized(#size, egui::Slider::new(&mut state.#field, #min..=#max).suffix("°").text(#text));
+            }),
             WidgetKind::Tree => {
                 // Helpers live only in the generator (not emitted), so we can use any Rust we want here:
                 #[derive(Clone)]
@@ -366,26 +1075,13 @@ impl Widget {
                     build(&mut it, 0)
                 }
 
-                fn nodes_to_literal(nodes: &[Node]) -> String {
-                    fn one(n: &Node) -> String {
-                        let kids = if n.children.is_empty() {
-                            "vec![]".to_string()
-                        } else {
-                            format!(
-                                "vec![{}]",
-                                n.children.iter().map(one).collect::<Vec<_>>().join(", ")
-                            )
-                        };
-                        format!(
-                            "GenTreeNode {{ label: \"{}\".to_string(), children: {} }}",
-                            crate::widget::escape(&n.label),
-                            kids
-                        )
-                    }
-                    format!(
-                        "vec![{}]",
-                        nodes.iter().map(one).collect::<Vec<_>>().join(", ")
-                    )
+                fn nodes_to_literal(nodes: &[Node]) -> TokenStream {
+                    let nodes = nodes.iter().map(|n| {
+                        let label = Literal::string(&n.label);
+                        let children = nodes_to_literal(&n.children);
+                        quote! { GenTreeNode { label: #label.to_string(), children: #children } }
+                    });
+                    quote! { vec![#(#nodes),*] }
                 }
 
                 let items = if w.props.items.is_empty() {
@@ -394,26 +1090,184 @@ impl Widget {
                     w.props.items.clone()
                 };
 
-                let nodes_literal = {
-                    let nodes = parse_nodes(&items);
-                    nodes_to_literal(&nodes)
+                let nodes = nodes_to_literal(&parse_nodes(&items));
+                block(quote! {
+                    let nodes: Vec<GenTreeNode> = #nodes;
+                    egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                        gen_show_tree(ui, &nodes);
+                    });
+                })
+            }
+            WidgetKind::Plot => {
+                let id = Literal::string(&w.ident().unwrap_or_default());
+                let mut options = TokenStream::new();
+                for (label, method) in [(&w.props.x_label, "x_axis_label"), (&w.props.y_label, "y_axis_label")] {
+                    if !label.is_empty() {
+                        let (method, label) = (ident(method), Literal::string(label));
+                        options.extend(quote! { .#method(#label) });
+                    }
+                }
+                // only what differs from egui_plot's defaults
+                if w.props.legend {
+                    options.extend(quote! { .legend(egui_plot::Legend::default()) });
+                }
+                if !w.props.grid {
+                    options.extend(quote! { .show_grid(false) });
+                }
+                if !w.props.zoom {
+                    options.extend(quote! { .allow_zoom(false).allow_boxed_zoom(false) });
+                }
+                if !w.props.drag {
+                    options.extend(quote! { .allow_drag(false).allow_scroll(false) });
+                }
+                let series: Vec<_> = w
+                    .props
+                    .series
+                    .iter()
+                    .zip(w.series_fields())
+                    .map(|(series, field)| {
+                        let (name, field) = (Literal::string(&series.name), ident(&field));
+                        match series.style {
+                            SeriesStyle::Line => quote! {
+                                plot_ui.line(egui_plot::Line::new(#name, state.#field.clone()));
+                            },
+                            SeriesStyle::Bar => quote! {
+                                plot_ui.bar_chart(egui_plot::BarChart::new(
+                                    #name,
+                                    state.#field.iter().map(|&[x, y]| egui_plot::Bar::new(x, y)).collect(),
+                                ));
+                            },
+                            SeriesStyle::Scatter => {
+                                let radius = float(SCATTER_RADIUS, 1);
+                                quote! {
+                                    plot_ui.points(egui_plot::Points::new(#name, state.#field.clone()).radius(#radius));
+                                }
+                            }
+                        }
+                    })
+                    .collect();
+                let plot_ui = ident(if series.is_empty() { "_plot_ui" } else { "plot_ui" });
+                block(quote! {
+                    egui_plot::Plot::new(#id)
+                        .width(#width)
+                        .height(#height)
+                        #options
+                        .show(ui, |#plot_ui| {
+                            #(#series)*
+                        });
+                })
+            }
+            WidgetKind::Table => {
+                let id = Literal::string(&w.ident().unwrap_or_default());
+                let (header_height, row_height) = (float(TABLE_HEADER_HEIGHT, 1), float(TABLE_ROW_HEIGHT, 1));
+                let scroll_height = w.size.y - if w.props.sticky_header { TABLE_HEADER_HEIGHT } else { 0.0 };
+                let scroll_height = float(scroll_height.max(0.0), 1);
+                let striped = w.props.striped.then(|| quote! { .striped(true) });
+                let columns = w.props.columns.iter().map(|column| {
+                    let width = match column.width {
+                        ColumnWidth::Auto => quote! { auto() },
+                        ColumnWidth::Initial(width) => {
+                            let width = float(width, 1);
+                            quote! { initial(#width) }
+                        }
+                        ColumnWidth::Exact(width) => {
+                            let width = float(width, 1);
+                            quote! { exact(#width) }
+                        }
+                        ColumnWidth::Remainder => quote! { remainder() },
+                    };
+                    let resizable = column.resizable.then(|| quote! { .resizable(true) });
+                    quote! { .column(egui_extras::Column::#width #resizable) }
+                });
+                let aligned = |align: ColumnAlign, content: TokenStream| match align {
+                    ColumnAlign::Left => content,
+                    ColumnAlign::Center => quote! {
+                        ui.with_layout(egui::Layout::top_down(egui::Align::Center), |ui| { #content });
+                    },
+                    ColumnAlign::Right => quote! {
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| { #content });
+                    },
+                };
+                let sort_field = w.sort_field().map(|f| ident(&f));
+                let headers = w.props.columns.iter().enumerate().map(|(i, column)| {
+                    let header = Literal::string(&column.header);
+                    let content = match &sort_field {
+                        Some(sort) => {
+                            let i = Literal::usize_unsuffixed(i);
+                            quote! { sort |= gen_sort_header(ui, #header, #i, &mut state.#sort); }
+                        }
+                        None => quote! { ui.strong(#header); },
+                    };
+                    let content = aligned(column.align, content);
+                    quote! { header.col(|ui| { #content }); }
+                });
+                let sorting = sort_field.as_ref().map(|sort| {
+                    (
+                        quote! { let mut sort = false; },
+                        quote! {
+                            if let (true, Some((column, ascending))) = (sort, state.#sort) {
+                                state.#field.sort_by(|a, b| if ascending { a.cmp_by(b, column) } else { b.cmp_by(a, column) });
+                            }
+                        },
+                    )
+                });
+                let (sort_flag, sort_rows) = sorting.unzip();
+                let header = quote! {
+                    |mut header| {
+                        #sort_flag
+                        #(#headers)*
+                        #sort_rows
+                    }
+                };
+                let cells = w.props.columns.iter().enumerate().map(|(i, column)| {
+                    let value = ident(&w.column_field(i).unwrap_or_default());
+                    let content = match column.ty {
+                        ColumnType::Text => quote! { ui.label(&item.#value); },
+                        ColumnType::Integer | ColumnType::Number => quote! { ui.label(item.#value.to_string()); },
+                        ColumnType::Bool => quote! { ui.label(if item.#value { "✔" } else { "" }); },
+                    };
+                    let content = aligned(column.align, content);
+                    quote! { row.col(|ui| { #content }); }
+                });
+                let rows = quote! {
+                    body.rows(#row_height, state.#field.len(), |mut row| {
+                        let item = &state.#field[row.index()];
+                        #(#cells)*
+                    });
+                };
+                let table = if w.props.sticky_header {
+                    quote! { .header(#header_height, #header).body(|body| { #rows }); }
+                } else {
+                    // the header is the first row of the body and scrolls away with it
+                    quote! {
+                        .body(|mut body| {
+                            body.row(#header_height, #header);
+                            #rows
+                        });
+                    }
                 };
+                block(quote! {
+                    egui_extras::TableBuilder::new(ui)
+                        .id_salt(#id)
+                        #striped
+                        .auto_shrink(false)
+                        .max_scroll_height(#scroll_height)
+                        #(#columns)*
+                        #table
+                })
+            }
+        };
 
-                out.push_str(&format!(
-                    "    ui.scope_builder(egui::UiBuilder::new().max_rect(egui::Rect::from_min_size(\
-                        {origin} + egui::vec2({x:.1},{y:.1}), egui::vec2({w:.1},{h:.1}))), |ui| {{ \
-                        let nodes: Vec<GenTreeNode> = {nodes}; \
-                        egui::ScrollArea::vertical().auto_shrink([false,false]).show(ui, |ui| {{ \
-                            gen_show_tree(ui, &nodes); \
-                        }}); \
-                    }});\n",
-                    x = pos.x,
-                    y = pos.y,
-                    w = size.x,
-                    h = size.y,
-                    nodes = nodes_literal,
-                ));
+        if let (true, Some(field)) = (events.contains(&WidgetEvent::Changed), &field) {
+            let before = format_ident!("before_{}", field);
+            let variant = ident(&w.event_variant(WidgetEvent::Changed));
+            quote! {
+                let #before = state.#field.clone();
+                #widget
+                if state.#field != #before { events.push(#event_enum::#variant); }
             }
+        } else {
+            widget
         }
     }
 }
@@ -0,0 +1,12 @@
+//! Design paths given to `build::compile` can't place the generated code outside
+//! `OUT_DIR`.
+
+use egui_rad_builder::build::{BuildError, compile};
+
+#[test]
+fn designs_outside_the_crate_are_rejected() {
+    for design in ["../x", "ui/../../x.json", "/tmp/x.json"] {
+        let result = compile(design);
+        assert!(matches!(result, Err(BuildError::InvalidDesign { .. })), "{design}: {result:?}");
+    }
+}
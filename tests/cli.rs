@@ -0,0 +1,84 @@
+//! Exit codes of the command line, which CI pipelines rely on.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const DESIGN: &str = r#"{"widgets": [{"id": 1, "kind": {"t": "Button"}, "pos": [20, 20], "size": [120, 28], "z": 1,
+    "area": "Center", "props": {"text": "OK"}}]}"#;
+
+/// Exit code of the builder run with `args` in `dir`
+fn run(dir: &Path, args: &[&str]) -> i32 {
+    let output = Command::new(env!("CARGO_BIN_EXE_egui-rad-builder"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    output.status.code().unwrap()
+}
+
+/// An empty directory of its own for `test`
+fn temp_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rad-cli-{test}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn check_passes_on_fresh_output_and_fails_on_stale() {
+    let dir = temp_dir("check");
+    fs::write(dir.join("design.json"), DESIGN).unwrap();
+    assert_eq!(run(&dir, &["generate", "design.json", "--check", "-o", "ui.rs"]), 1, "missing output");
+    assert_eq!(run(&dir, &["generate", "design.json", "-o", "ui.rs"]), 0);
+    assert_eq!(run(&dir, &["generate", "design.json", "--check", "-o", "ui.rs"]), 0);
+
+    fs::write(dir.join("design.json"), DESIGN.replace("\"OK\"", "\"Cancel\"")).unwrap();
+    let before = fs::read_to_string(dir.join("ui.rs")).unwrap();
+    assert_eq!(run(&dir, &["generate", "design.json", "--check", "-o", "ui.rs"]), 1);
+    assert_eq!(fs::read_to_string(dir.join("ui.rs")).unwrap(), before, "--check wrote the file");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn bad_arguments_are_usage_errors() {
+    let dir = temp_dir("usage");
+    assert_eq!(run(&dir, &["generate"]), 2);
+    assert_eq!(run(&dir, &["generate", "design.json", "--frobnicate"]), 2);
+    assert_eq!(run(&dir, &["generate", "design.json", "-o"]), 2);
+    assert_eq!(run(&dir, &["generate", "design.json", "--check"]), 2, "--check needs -o");
+    assert_eq!(run(&dir, &["frobnicate"]), 2);
+    assert_eq!(run(&dir, &["--help"]), 0);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unreadable_designs_are_parse_errors() {
+    let dir = temp_dir("parse");
+    assert_eq!(run(&dir, &["generate", "missing.json"]), 3);
+    fs::write(dir.join("broken.json"), "{ not json").unwrap();
+    assert_eq!(run(&dir, &["generate", "broken.json"]), 3);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn invalid_designs_are_generate_errors() {
+    let dir = temp_dir("generate");
+    let invalid = DESIGN.replace(r#""kind": {"t": "Button"}"#, r#""kind": {"t": "DatePicker"}"#).replace(
+        r#""props": {"text": "OK"}"#,
+        r#""props": {"text": "OK", "year": 999999}"#,
+    );
+    fs::write(dir.join("design.json"), invalid).unwrap();
+    assert_eq!(run(&dir, &["generate", "design.json"]), 4);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn unwritable_output_is_a_write_error() {
+    let dir = temp_dir("write");
+    fs::write(dir.join("design.json"), DESIGN).unwrap();
+    assert_eq!(run(&dir, &["generate", "design.json", "-o", "no/such/dir/ui.rs"]), 5);
+    fs::remove_dir_all(dir).unwrap();
+}
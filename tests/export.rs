@@ -0,0 +1,61 @@
+//! Exported Cargo projects have the expected files and only the dependencies the design needs.
+
+use egui_rad_builder::{Project, export};
+use std::{fs, path::Path};
+
+fn design(kind: &str) -> Project {
+    Project::from_json(&format!(
+        r#"{{"widgets": [{{"id": 1, "kind": {{"t": "{kind}"}}, "pos": [20, 20], "size": [120, 28], "z": 1,
+            "area": "Center", "props": {{"text": "OK"}}, "events": ["Clicked"]}}]}}"#
+    ))
+    .unwrap()
+}
+
+/// Paths of all files under `dir`, relative to it and sorted
+fn files(dir: &Path) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(next) = pending.pop() {
+        for entry in fs::read_dir(next).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                found.push(path.strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    found.sort();
+    found
+}
+
+#[test]
+fn exports_a_cargo_project() {
+    let dir = std::env::temp_dir().join(format!("rad-export-{}", std::process::id())).join("My App");
+    let _ = fs::remove_dir_all(&dir);
+    export::write_cargo_project(&design("Button"), &dir, false).unwrap();
+    assert_eq!(files(&dir), [".gitignore", "Cargo.toml", "src/main.rs", "src/ui.rs"]);
+
+    let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+    assert!(manifest.contains("name = \"my-app\""), "{manifest}");
+    assert!(manifest.contains("eframe = "), "{manifest}");
+    assert!(!manifest.contains("chrono"), "{manifest}");
+
+    // a second export needs permission and keeps the hand-written code
+    assert!(matches!(
+        export::write_cargo_project(&design("Button"), &dir, false),
+        Err(export::ExportError::NotEmpty(_))
+    ));
+    let ui = fs::read_to_string(dir.join("src/ui.rs")).unwrap();
+    let marker = "// rad:user-begin on_button1_clicked\n";
+    let filled = ui.replacen(marker, &format!("{marker}println!(\"clicked\");\n"), 1);
+    assert_ne!(filled, ui);
+    fs::write(dir.join("src/ui.rs"), &filled).unwrap();
+    fs::write(dir.join("Cargo.toml"), "// rad:user-end\n").unwrap();
+    export::write_cargo_project(&design("DatePicker"), &dir, true).unwrap();
+
+    assert!(fs::read_to_string(dir.join("src/ui.rs")).unwrap().contains("println!(\"clicked\");"));
+    let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+    assert!(manifest.contains("chrono"), "{manifest}");
+    fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,127 @@
+//! The code generated for the designs in `tests/fixtures` is compared against the
+//! golden files in `tests/golden`, and type-checked against the egui crates.
+//!
+//! After an intended change to the generator, rewrite the golden files with
+//!
+//! ```text
+//! UPDATE_GOLDEN=1 cargo test --test generated_code
+//! ```
+
+use egui_rad_builder::{DockArea, OutputMode, Project, WidgetKind, export, generate};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Every fixture design by file stem, sorted by name
+fn fixtures() -> Vec<(String, Project)> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(root().join("tests/fixtures"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let project = Project::load(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+            (name, project)
+        })
+        .collect()
+}
+
+#[test]
+fn fixtures_cover_every_kind_and_dock_area() {
+    let fixtures = fixtures();
+    let widgets = || fixtures.iter().flat_map(|(_, project)| &project.widgets);
+    for kind in WidgetKind::ALL {
+        assert!(widgets().any(|w| w.kind == kind), "no fixture has a {kind:?}");
+    }
+    for area in DockArea::ALL {
+        assert!(widgets().any(|w| w.area == area), "no fixture has a widget in {area:?}");
+    }
+    for output in OutputMode::ALL {
+        assert!(
+            fixtures.iter().any(|(_, project)| project.codegen.output == output),
+            "no fixture generates {output:?}"
+        );
+    }
+}
+
+#[test]
+fn generated_code_matches_golden_files() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let mut stale = Vec::new();
+    for (name, project) in fixtures() {
+        let code = generate(&project).unwrap();
+        let path = root().join("tests/golden").join(format!("{name}.rs"));
+        if update {
+            fs::write(&path, &code).unwrap();
+            continue;
+        }
+        let golden = fs::read_to_string(&path).unwrap_or_default();
+        if golden != code {
+            let line = golden.lines().zip(code.lines()).take_while(|(a, b)| a == b).count() + 1;
+            let got = code.lines().nth(line - 1).unwrap_or("<end of file>");
+            stale.push(format!("{}: line {line} is now `{got}`", path.display()));
+        }
+    }
+    assert!(
+        stale.is_empty(),
+        "generated code differs from the golden files, run with UPDATE_GOLDEN=1 if that's intended\n{}",
+        stale.join("\n")
+    );
+}
+
+/// Builds a workspace with one package per fixture next to the test binaries and runs
+/// `cargo check --offline` on it, so the crates have to be in the local registry
+/// already. The builder's own `Cargo.lock` pins the same egui versions it was built with.
+#[test]
+fn generated_code_compiles() {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("compile-check");
+    let mut members = Vec::new();
+    for (name, project) in fixtures() {
+        let package = dir.join(&name);
+        if package.exists() {
+            fs::remove_dir_all(&package).unwrap();
+        }
+        export::write_cargo_project(&project, &package, false).unwrap();
+        if project.codegen.output == OutputMode::App {
+            // the app is complete on its own, check it instead of the exported module
+            fs::remove_file(package.join("src/ui.rs")).unwrap();
+            fs::write(package.join("src/main.rs"), generate(&project).unwrap()).unwrap();
+        }
+        members.push(format!("\"{name}\""));
+    }
+    fs::write(
+        dir.join("Cargo.toml"),
+        format!("[workspace]\nresolver = \"3\"\nmembers = [{}]\n", members.join(", ")),
+    )
+    .unwrap();
+    let lock = root().join("Cargo.lock");
+    if lock.exists() && !dir.join("Cargo.lock").exists() {
+        fs::copy(lock, dir.join("Cargo.lock")).unwrap();
+    }
+
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args(["check", "--offline", "--workspace", "--quiet"])
+        .current_dir(&dir)
+        .env("CARGO_TARGET_DIR", dir.join("target"))
+        // deprecated egui API is a warning, and the generated code should have none
+        .env("RUSTFLAGS", "-D warnings")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "generated code in {} doesn't compile:\n{}",
+        dir.display(),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,766 @@
+// --- generated by egui RAD GUI Builder ---
+use eframe::egui;
+
+#[derive(Clone)]
+struct GenTreeNode {
+    label: String,
+    children: Vec<GenTreeNode>,
+}
+
+fn gen_show_tree(ui: &mut egui::Ui, nodes: &[GenTreeNode]) {
+    for n in nodes {
+        if n.children.is_empty() {
+            ui.label(&n.label);
+        } else {
+            ui.collapsing(&n.label, |ui| gen_show_tree(ui, &n.children));
+        }
+    }
+}
+
+fn gen_sort_header(
+    ui: &mut egui::Ui,
+    label: &str,
+    column: usize,
+    sort: &mut Option<(usize, bool)>,
+) -> bool {
+    let arrow = match *sort {
+        Some((c, true)) if c == column => " ⬆",
+        Some((c, false)) if c == column => " ⬇",
+        _ => "",
+    };
+    let header = egui::Label::new(
+        egui::RichText::new(format!("{label}{arrow}")).strong(),
+    );
+    if !ui.add(header.sense(egui::Sense::click())).clicked() {
+        return false;
+    }
+    *sort = match *sort {
+        Some((c, ascending)) if c == column => Some((column, !ascending)),
+        _ => Some((column, true)),
+    };
+    true
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct OrdersRow {
+    pub(crate) customer: String,
+    pub(crate) type_: String,
+    pub(crate) total: f64,
+    pub(crate) paid: bool,
+}
+
+impl OrdersRow {
+    fn cmp_by(&self, other: &Self, column: usize) -> std::cmp::Ordering {
+        match column {
+            0 => self.customer.cmp(&other.customer),
+            1 => self.type_.cmp(&other.type_),
+            2 => self.total.total_cmp(&other.total),
+            3 => self.paid.cmp(&other.paid),
+            _ => std::cmp::Ordering::Equal,
+        }
+    }
+}
+
+pub(crate) struct EditorState {
+    pub(crate) enable_top: bool,
+    pub(crate) enable_bottom: bool,
+    pub(crate) enable_left: bool,
+    pub(crate) enable_right: bool,
+    pub(crate) sel_1: usize,
+    pub(crate) progress_3: f32,
+    pub(crate) sel_6: usize,
+    pub(crate) zoom: f32,
+    pub(crate) checked_8: bool,
+    pub(crate) text_9: String,
+    pub(crate) cpu_load: Vec<[f64; 2]>,
+    pub(crate) orders: Vec<OrdersRow>,
+    pub(crate) orders_sort: Option<(usize, bool)>,
+    pub(crate) auto_refresh: bool,
+    pub(crate) open_20: bool,
+    pub(crate) sel_21: usize,
+    // rad:user-begin state_fields
+    // rad:user-end
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EditorEvent {
+    Sel1Changed,
+    Sel6Changed,
+    Text9Changed,
+    Button10Clicked,
+    ImageTextButton12Clicked,
+    Sel21Changed,
+    ExportClicked,
+}
+
+impl Default for EditorState {
+    fn default() -> Self {
+        Self {
+            enable_top: true,
+            enable_bottom: true,
+            enable_left: true,
+            enable_right: true,
+            sel_1: 0,
+            progress_3: 0.600,
+            sel_6: 0,
+            zoom: 100.000,
+            checked_8: true,
+            text_9: "Search".to_owned(),
+            cpu_load: Vec::from([[0.0, 12.5], [1.0, 40.0], [2.0, 33.25]]),
+            orders: Vec::from([
+                OrdersRow {
+                    customer: "Ada".to_owned(),
+                    type_: "web".to_owned(),
+                    total: 12.5,
+                    paid: true,
+                },
+                OrdersRow {
+                    customer: "Grace".to_owned(),
+                    type_: "phone".to_owned(),
+                    total: 7.0,
+                    paid: false,
+                },
+            ]),
+            orders_sort: None,
+            auto_refresh: true,
+            open_20: true,
+            sel_21: 0,
+            // rad:user-begin state_defaults
+            // rad:user-end
+        }
+    }
+}
+
+impl EditorState {
+    pub(crate) fn handle_event(&mut self, event: EditorEvent) {
+        match event {
+            EditorEvent::Sel1Changed => {
+                // rad:user-begin on_sel1_changed
+                // rad:user-end
+            }
+            EditorEvent::Sel6Changed => {
+                // rad:user-begin on_sel6_changed
+                // rad:user-end
+            }
+            EditorEvent::Text9Changed => {
+                // rad:user-begin on_text9_changed
+                // rad:user-end
+            }
+            EditorEvent::Button10Clicked => {
+                // rad:user-begin on_button10_clicked
+                // rad:user-end
+            }
+            EditorEvent::ImageTextButton12Clicked => {
+                // rad:user-begin on_image_text_button12_clicked
+                // rad:user-end
+            }
+            EditorEvent::Sel21Changed => {
+                // rad:user-begin on_sel21_changed
+                // rad:user-end
+            }
+            EditorEvent::ExportClicked => {
+                // rad:user-begin on_export_clicked
+                // rad:user-end
+            }
+        }
+    }
+}
+
+pub(crate) fn editor_ui(
+    ctx: &egui::Context,
+    state: &mut EditorState,
+) -> Vec<EditorEvent> {
+    let mut events: Vec<EditorEvent> = Vec::new();
+    if state.enable_top {
+        egui::TopBottomPanel::top("gen_top")
+            .resizable(true)
+            .show(
+                ctx,
+                |ui| {
+                    let before_sel_1 = state.sel_1.clone();
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(8.0, 4.0),
+                                    egui::vec2(120.0, 24.0),
+                                ),
+                            ),
+                        |ui| {
+                            let items = vec![
+                                "Open".to_string(), "Save".to_string(), "Quit".to_string()
+                            ];
+                            ui.menu_button(
+                                "File",
+                                |ui| {
+                                    for (i, it) in items.iter().enumerate() {
+                                        if ui.button(it).clicked() {
+                                            state.sel_1 = i;
+                                            ui.close_kind(egui::UiKind::Menu);
+                                        }
+                                    }
+                                },
+                            );
+                        },
+                    );
+                    if state.sel_1 != before_sel_1 {
+                        events.push(EditorEvent::Sel1Changed);
+                    }
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(140.0, 6.0),
+                                    egui::vec2(200.0, 20.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui.label("Untitled");
+                        },
+                    );
+                    let response_12 = ui
+                        .scope_builder(
+                            egui::UiBuilder::new()
+                                .max_rect(
+                                    egui::Rect::from_min_size(
+                                        ui.min_rect().min + egui::vec2(360.0, 2.0),
+                                        egui::vec2(120.0, 28.0),
+                                    ),
+                                ),
+                            |ui| {
+                                ui
+                                    .add_sized(
+                                        egui::vec2(120.0, 28.0),
+                                        egui::Button::image_and_text(
+                                            egui::Image::new("file://assets/icon.svg")
+                                                .fit_to_exact_size(egui::vec2(20.0, 20.0)),
+                                            "Help",
+                                        ),
+                                    )
+                            },
+                        )
+                        .inner;
+                    if response_12.clicked() {
+                        events.push(EditorEvent::ImageTextButton12Clicked);
+                    }
+                },
+            );
+    }
+    if state.enable_bottom {
+        egui::TopBottomPanel::bottom("gen_bottom")
+            .resizable(true)
+            .show(
+                ctx,
+                |ui| {
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(8.0, 4.0),
+                                    egui::vec2(300.0, 18.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui.add_sized(
+                                egui::vec2(300.0, 18.0),
+                                egui::ProgressBar::new(state.progress_3).show_percentage(),
+                            );
+                        },
+                    );
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(320.0, 4.0),
+                                    egui::vec2(160.0, 18.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui.label("Ready");
+                        },
+                    );
+                },
+            );
+    }
+    if state.enable_left {
+        egui::SidePanel::left("gen_left")
+            .resizable(true)
+            .show(
+                ctx,
+                |ui| {
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(8.0, 8.0),
+                                    egui::vec2(180.0, 200.0),
+                                ),
+                            ),
+                        |ui| {
+                            let nodes: Vec<GenTreeNode> = vec![
+                                GenTreeNode { label : "src".to_string(), children :
+                                vec![GenTreeNode { label : "main.rs".to_string(), children :
+                                vec![] }, GenTreeNode { label : "lib.rs".to_string(),
+                                children : vec![] }] }, GenTreeNode { label : "Cargo.toml"
+                                .to_string(), children : vec![] }
+                            ];
+                            egui::ScrollArea::vertical()
+                                .auto_shrink([false, false])
+                                .show(
+                                    ui,
+                                    |ui| {
+                                        gen_show_tree(ui, &nodes);
+                                    },
+                                );
+                        },
+                    );
+                    let before_sel_6 = state.sel_6.clone();
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(8.0, 220.0),
+                                    egui::vec2(180.0, 80.0),
+                                ),
+                            ),
+                        |ui| {
+                            let items = vec!["List".to_string(), "Grid".to_string()];
+                            for (i, it) in items.iter().enumerate() {
+                                if ui
+                                    .add(egui::RadioButton::new(state.sel_6 == i, it))
+                                    .clicked()
+                                {
+                                    state.sel_6 = i;
+                                }
+                            }
+                        },
+                    );
+                    if state.sel_6 != before_sel_6 {
+                        events.push(EditorEvent::Sel6Changed);
+                    }
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(8.0, 310.0),
+                                    egui::vec2(180.0, 80.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui.vertical(|ui| {
+                                ui.allocate_ui(
+                                    egui::vec2(180.0, 28.0),
+                                    |ui| {
+                                        ui.add_sized(
+                                            egui::vec2(180.0, 28.0),
+                                            egui::Button::new("Refresh"),
+                                        );
+                                    },
+                                );
+                                ui.allocate_ui(
+                                    egui::vec2(180.0, 24.0),
+                                    |ui| {
+                                        ui.checkbox(&mut state.auto_refresh, "Auto refresh");
+                                    },
+                                );
+                            });
+                        },
+                    );
+                },
+            );
+    }
+    if state.enable_right {
+        egui::SidePanel::right("gen_right")
+            .resizable(true)
+            .show(
+                ctx,
+                |ui| {
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(8.0, 8.0),
+                                    egui::vec2(180.0, 24.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui.add_sized(
+                                egui::vec2(180.0, 24.0),
+                                egui::Slider::new(&mut state.zoom, 10.000..=400.000)
+                                    .text("Zoom"),
+                            );
+                        },
+                    );
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(8.0, 40.0),
+                                    egui::vec2(180.0, 24.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui.checkbox(&mut state.checked_8, "Snap to grid");
+                        },
+                    );
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(8.0, 72.0),
+                                    egui::vec2(180.0, 100.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui.add_sized(
+                                egui::vec2(180.0, 100.0),
+                                egui::Image::new("file://assets/logo.png")
+                                    .fit_to_exact_size(egui::vec2(180.0, 100.0))
+                                    .maintain_aspect_ratio(false),
+                            );
+                        },
+                    );
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(8.0, 180.0),
+                                    egui::vec2(64.0, 64.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui.add_sized(
+                                egui::vec2(64.0, 64.0),
+                                egui::Image::new("file://assets/icon.svg")
+                                    .fit_to_original_size(2.000),
+                            );
+                        },
+                    );
+                    ui.scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    ui.min_rect().min + egui::vec2(8.0, 250.0),
+                                    egui::vec2(180.0, 120.0),
+                                ),
+                            ),
+                        |ui| {
+                            egui_plot::Plot::new("cpu")
+                                .width(180.0)
+                                .height(120.0)
+                                .show_grid(false)
+                                .allow_zoom(false)
+                                .allow_boxed_zoom(false)
+                                .allow_drag(false)
+                                .allow_scroll(false)
+                                .show(
+                                    ui,
+                                    |plot_ui| {
+                                        plot_ui
+                                            .line(
+                                                egui_plot::Line::new("Load (%)", state.cpu_load.clone()),
+                                            );
+                                    },
+                                );
+                        },
+                    );
+                },
+            );
+    }
+    egui::CentralPanel::default()
+        .show(
+            ctx,
+            |ui| {
+                let canvas = egui::Rect::from_min_size(
+                    ui.min_rect().min,
+                    egui::vec2(700.0, 600.0),
+                );
+                let _ = ui.allocate_painter(canvas.size(), egui::Sense::hover());
+                let before_text_9 = state.text_9.clone();
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 20.0),
+                                egui::vec2(300.0, 36.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.add_sized(
+                            egui::vec2(300.0, 36.0),
+                            egui::TextEdit::singleline(&mut state.text_9)
+                                .hint_text("Search"),
+                        );
+                    },
+                );
+                if state.text_9 != before_text_9 {
+                    events.push(EditorEvent::Text9Changed);
+                }
+                let response_10 = ui
+                    .scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    canvas.min + egui::vec2(330.0, 20.0),
+                                    egui::vec2(100.0, 36.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui
+                                .add_sized(
+                                    egui::vec2(100.0, 36.0),
+                                    egui::Button::new("Find"),
+                                )
+                        },
+                    )
+                    .inner;
+                if response_10.clicked() {
+                    events.push(EditorEvent::Button10Clicked);
+                }
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 130.0),
+                                egui::vec2(400.0, 160.0),
+                            ),
+                        ),
+                    |ui| {
+                        egui_extras::TableBuilder::new(ui)
+                            .id_salt("orders")
+                            .auto_shrink(false)
+                            .max_scroll_height(160.0)
+                            .column(egui_extras::Column::remainder().resizable(true))
+                            .column(egui_extras::Column::auto().resizable(true))
+                            .column(egui_extras::Column::exact(70.0))
+                            .column(egui_extras::Column::auto())
+                            .body(|mut body| {
+                                body.row(
+                                    20.0,
+                                    |mut header| {
+                                        let mut sort = false;
+                                        header
+                                            .col(|ui| {
+                                                sort
+                                                    |= gen_sort_header(
+                                                        ui,
+                                                        "Customer",
+                                                        0,
+                                                        &mut state.orders_sort,
+                                                    );
+                                            });
+                                        header
+                                            .col(|ui| {
+                                                sort
+                                                    |= gen_sort_header(ui, "Type", 1, &mut state.orders_sort);
+                                            });
+                                        header
+                                            .col(|ui| {
+                                                ui.with_layout(
+                                                    egui::Layout::right_to_left(egui::Align::Center),
+                                                    |ui| {
+                                                        sort
+                                                            |= gen_sort_header(ui, "Total", 2, &mut state.orders_sort);
+                                                    },
+                                                );
+                                            });
+                                        header
+                                            .col(|ui| {
+                                                ui.with_layout(
+                                                    egui::Layout::top_down(egui::Align::Center),
+                                                    |ui| {
+                                                        sort
+                                                            |= gen_sort_header(ui, "Paid", 3, &mut state.orders_sort);
+                                                    },
+                                                );
+                                            });
+                                        if let (true, Some((column, ascending))) = (
+                                            sort,
+                                            state.orders_sort,
+                                        ) {
+                                            state
+                                                .orders
+                                                .sort_by(|a, b| {
+                                                    if ascending {
+                                                        a.cmp_by(b, column)
+                                                    } else {
+                                                        b.cmp_by(a, column)
+                                                    }
+                                                });
+                                        }
+                                    },
+                                );
+                                body.rows(
+                                    18.0,
+                                    state.orders.len(),
+                                    |mut row| {
+                                        let item = &state.orders[row.index()];
+                                        row.col(|ui| {
+                                            ui.label(&item.customer);
+                                        });
+                                        row.col(|ui| {
+                                            ui.label(&item.type_);
+                                        });
+                                        row.col(|ui| {
+                                            ui.with_layout(
+                                                egui::Layout::right_to_left(egui::Align::Center),
+                                                |ui| {
+                                                    ui.label(item.total.to_string());
+                                                },
+                                            );
+                                        });
+                                        row.col(|ui| {
+                                            ui.with_layout(
+                                                egui::Layout::top_down(egui::Align::Center),
+                                                |ui| {
+                                                    ui.label(if item.paid { "✔" } else { "" });
+                                                },
+                                            );
+                                        });
+                                    },
+                                );
+                            });
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(440.0, 20.0),
+                                egui::vec2(240.0, 100.0),
+                            ),
+                        ),
+                    |ui| {
+                        egui::CollapsingHeader::new("Filters")
+                            .default_open(state.open_20)
+                            .show(
+                                ui,
+                                |ui| {
+                                    let before_sel_21 = state.sel_21.clone();
+                                    ui.scope_builder(
+                                        egui::UiBuilder::new()
+                                            .max_rect(
+                                                egui::Rect::from_min_size(
+                                                    ui.min_rect().min + egui::vec2(0.0, 4.0),
+                                                    egui::vec2(200.0, 24.0),
+                                                ),
+                                            ),
+                                        |ui| {
+                                            let items = vec![
+                                                "Any".to_string(), "Open".to_string(), "Paid".to_string()
+                                            ];
+                                            egui::ComboBox::from_id_salt(21)
+                                                .width(200.0)
+                                                .selected_text(
+                                                    items.get(state.sel_21).cloned().unwrap_or_default(),
+                                                )
+                                                .show_ui(
+                                                    ui,
+                                                    |ui| {
+                                                        for (i, it) in items.iter().enumerate() {
+                                                            ui.selectable_value(&mut state.sel_21, i, it.clone());
+                                                        }
+                                                    },
+                                                );
+                                        },
+                                    );
+                                    if state.sel_21 != before_sel_21 {
+                                        events.push(EditorEvent::Sel21Changed);
+                                    }
+                                },
+                            );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(440.0, 130.0),
+                                egui::vec2(240.0, 160.0),
+                            ),
+                        ),
+                    |ui| {
+                        egui::ScrollArea::both()
+                            .id_salt(22)
+                            .auto_shrink(false)
+                            .show(
+                                ui,
+                                |ui| {
+                                    ui.scope_builder(
+                                        egui::UiBuilder::new()
+                                            .max_rect(
+                                                egui::Rect::from_min_size(
+                                                    ui.min_rect().min + egui::vec2(4.0, 4.0),
+                                                    egui::vec2(220.0, 300.0),
+                                                ),
+                                            ),
+                                        |ui| {
+                                            ui.label("A long note that needs scrolling");
+                                        },
+                                    );
+                                },
+                            );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 300.0),
+                                egui::vec2(400.0, 120.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.spacing_mut().item_spacing = egui::vec2(0.0, 8.0);
+                        egui_extras::StripBuilder::new(ui)
+                            .sizes(egui_extras::Size::remainder(), 2)
+                            .vertical(|mut strip| {
+                                strip
+                                    .cell(|ui| {
+                                        ui.allocate_ui(
+                                            egui::vec2(200.0, 20.0),
+                                            |ui| {
+                                                ui.label("Summary");
+                                            },
+                                        );
+                                    });
+                                strip
+                                    .cell(|ui| {
+                                        let response_26 = ui
+                                            .allocate_ui(
+                                                egui::vec2(120.0, 28.0),
+                                                |ui| {
+                                                    ui
+                                                        .add_sized(
+                                                            egui::vec2(120.0, 28.0),
+                                                            egui::Button::new("Export"),
+                                                        )
+                                                },
+                                            )
+                                            .inner;
+                                        if response_26.clicked() {
+                                            events.push(EditorEvent::ExportClicked);
+                                        }
+                                    });
+                            });
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 80.0),
+                                egui::vec2(200.0, 20.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.hyperlink_to("Documentation", "https://docs.rs/egui");
+                    },
+                );
+            },
+        );
+    events
+}
+
+// rad:user-begin items
+// rad:user-end
@@ -0,0 +1,53 @@
+// --- generated by egui RAD GUI Builder ---
+use eframe::egui;
+
+struct GeneratedState {
+    enable_top: bool,
+    enable_bottom: bool,
+    enable_left: bool,
+    enable_right: bool,
+    // rad:user-begin state_fields
+    // rad:user-end
+}
+
+impl Default for GeneratedState {
+    fn default() -> Self {
+        Self {
+            enable_top: false,
+            enable_bottom: false,
+            enable_left: false,
+            enable_right: false,
+            // rad:user-begin state_defaults
+            // rad:user-end
+        }
+    }
+}
+
+fn generated_ui(ctx: &egui::Context, state: &mut GeneratedState) {
+    if state.enable_top {
+        egui::TopBottomPanel::top("gen_top").resizable(true).show(ctx, |_ui| {});
+    }
+    if state.enable_bottom {
+        egui::TopBottomPanel::bottom("gen_bottom").resizable(true).show(ctx, |_ui| {});
+    }
+    if state.enable_left {
+        egui::SidePanel::left("gen_left").resizable(true).show(ctx, |_ui| {});
+    }
+    if state.enable_right {
+        egui::SidePanel::right("gen_right").resizable(true).show(ctx, |_ui| {});
+    }
+    egui::CentralPanel::default()
+        .show(
+            ctx,
+            |ui| {
+                let canvas = egui::Rect::from_min_size(
+                    ui.min_rect().min,
+                    egui::vec2(700.0, 600.0),
+                );
+                let _ = ui.allocate_painter(canvas.size(), egui::Sense::hover());
+            },
+        );
+}
+
+// rad:user-begin items
+// rad:user-end
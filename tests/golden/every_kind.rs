@@ -0,0 +1,1007 @@
+// --- generated by egui RAD GUI Builder ---
+use chrono::NaiveDate;
+use eframe::egui;
+use egui_extras::DatePickerButton;
+
+#[derive(Clone)]
+struct GenTreeNode {
+    label: String,
+    children: Vec<GenTreeNode>,
+}
+
+fn gen_show_tree(ui: &mut egui::Ui, nodes: &[GenTreeNode]) {
+    for n in nodes {
+        if n.children.is_empty() {
+            ui.label(&n.label);
+        } else {
+            ui.collapsing(&n.label, |ui| gen_show_tree(ui, &n.children));
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Table22Row {
+    item: String,
+    qty: i64,
+    price: f64,
+    in_stock: bool,
+}
+
+struct GeneratedState {
+    enable_top: bool,
+    enable_bottom: bool,
+    enable_left: bool,
+    enable_right: bool,
+    sel_1: usize,
+    checked_5: bool,
+    title: String,
+    value_7: f32,
+    progress_8: f32,
+    sel_9: usize,
+    sel_12: bool,
+    sel_13: usize,
+    open_15: bool,
+    date_16: NaiveDate,
+    angle_17: f32,
+    pass_18: String,
+    plot_21_visitors: Vec<[f64; 2]>,
+    plot_21_sign_ups: Vec<[f64; 2]>,
+    plot_21_series3: Vec<[f64; 2]>,
+    table_22: Vec<Table22Row>,
+    value_29: f32,
+    notes: String,
+    user: String,
+    checked_38: bool,
+    // rad:user-begin state_fields
+    // rad:user-end
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum UiEvent {
+    SaveButtonClicked,
+    SaveButtonDoubleClicked,
+    Checked5Changed,
+    TitleChanged,
+    TitleLostFocus,
+    Hyperlink11Clicked,
+    Sel13Changed,
+    ApplyButtonClicked,
+    NotesLostFocus,
+    OkClicked,
+}
+
+impl Default for GeneratedState {
+    fn default() -> Self {
+        Self {
+            enable_top: false,
+            enable_bottom: false,
+            enable_left: false,
+            enable_right: false,
+            sel_1: 0,
+            checked_5: true,
+            title: "Type here".to_owned(),
+            value_7: 42.000,
+            progress_8: 0.250,
+            sel_9: 1,
+            sel_12: false,
+            sel_13: 2,
+            open_15: false,
+            date_16: NaiveDate::from_ymd_opt(2025, 6, 15).unwrap(),
+            angle_17: 90.000,
+            pass_18: "hunter2".to_owned(),
+            plot_21_visitors: Vec::from([
+                [0.0, 0.0],
+                [1.0, 0.56],
+                [2.0, 0.93],
+                [3.0, 0.97],
+                [4.0, 0.68],
+                [5.0, 0.14],
+                [6.0, -0.44],
+                [7.0, -0.87],
+                [8.0, -1.0],
+                [9.0, -0.77],
+                [10.0, -0.28],
+            ]),
+            plot_21_sign_ups: Vec::from([[1.0, 3.0], [2.0, 5.0], [3.0, 2.0]]),
+            plot_21_series3: Vec::from([[1.0, 2.1], [2.5, -0.5]]),
+            table_22: Vec::from([
+                Table22Row {
+                    item: "Apples".to_owned(),
+                    qty: 3,
+                    price: 1.2,
+                    in_stock: true,
+                },
+                Table22Row {
+                    item: "Pears".to_owned(),
+                    qty: -1,
+                    price: 0.8,
+                    in_stock: false,
+                },
+                Table22Row {
+                    item: "Plums".to_owned(),
+                    qty: 0,
+                    price: 0.0,
+                    in_stock: false,
+                },
+            ]),
+            value_29: 3.000,
+            notes: "Notes".to_owned(),
+            user: "guest".to_owned(),
+            checked_38: true,
+            // rad:user-begin state_defaults
+            // rad:user-end
+        }
+    }
+}
+
+impl GeneratedState {
+    fn handle_event(&mut self, event: UiEvent) {
+        match event {
+            UiEvent::SaveButtonClicked => {
+                // rad:user-begin on_save_button_clicked
+                // rad:user-end
+            }
+            UiEvent::SaveButtonDoubleClicked => {
+                // rad:user-begin on_save_button_double_clicked
+                // rad:user-end
+            }
+            UiEvent::Checked5Changed => {
+                // rad:user-begin on_checked5_changed
+                // rad:user-end
+            }
+            UiEvent::TitleChanged => {
+                // rad:user-begin on_title_changed
+                // rad:user-end
+            }
+            UiEvent::TitleLostFocus => {
+                // rad:user-begin on_title_lost_focus
+                // rad:user-end
+            }
+            UiEvent::Hyperlink11Clicked => {
+                // rad:user-begin on_hyperlink11_clicked
+                // rad:user-end
+            }
+            UiEvent::Sel13Changed => {
+                // rad:user-begin on_sel13_changed
+                // rad:user-end
+            }
+            UiEvent::ApplyButtonClicked => {
+                // rad:user-begin on_apply_button_clicked
+                // rad:user-end
+            }
+            UiEvent::NotesLostFocus => {
+                // rad:user-begin on_notes_lost_focus
+                // rad:user-end
+            }
+            UiEvent::OkClicked => {
+                // rad:user-begin on_ok_clicked
+                // rad:user-end
+            }
+        }
+    }
+}
+
+fn generated_ui(ctx: &egui::Context, state: &mut GeneratedState) -> Vec<UiEvent> {
+    let mut events: Vec<UiEvent> = Vec::new();
+    if state.enable_top {
+        egui::TopBottomPanel::top("gen_top").resizable(true).show(ctx, |_ui| {});
+    }
+    if state.enable_bottom {
+        egui::TopBottomPanel::bottom("gen_bottom").resizable(true).show(ctx, |_ui| {});
+    }
+    if state.enable_left {
+        egui::SidePanel::left("gen_left").resizable(true).show(ctx, |_ui| {});
+    }
+    if state.enable_right {
+        egui::SidePanel::right("gen_right").resizable(true).show(ctx, |_ui| {});
+    }
+    egui::CentralPanel::default()
+        .show(
+            ctx,
+            |ui| {
+                let canvas = egui::Rect::from_min_size(
+                    ui.min_rect().min,
+                    egui::vec2(700.0, 940.0),
+                );
+                let _ = ui.allocate_painter(canvas.size(), egui::Sense::hover());
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 20.0),
+                                egui::vec2(180.0, 28.0),
+                            ),
+                        ),
+                    |ui| {
+                        let items = vec![
+                            "First".to_string(), "Second".to_string(), "Third"
+                            .to_string()
+                        ];
+                        ui.menu_button(
+                            "Menu",
+                            |ui| {
+                                for (i, it) in items.iter().enumerate() {
+                                    if ui.button(it).clicked() {
+                                        state.sel_1 = i;
+                                        ui.close_kind(egui::UiKind::Menu);
+                                    }
+                                }
+                            },
+                        );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(320.0, 20.0),
+                                egui::vec2(140.0, 24.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.label("Label");
+                    },
+                );
+                let response_3 = ui
+                    .scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    canvas.min + egui::vec2(20.0, 76.0),
+                                    egui::vec2(160.0, 32.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui
+                                .add_sized(
+                                    egui::vec2(160.0, 32.0),
+                                    egui::Button::new("Button"),
+                                )
+                        },
+                    )
+                    .inner;
+                if response_3.clicked() {
+                    events.push(UiEvent::SaveButtonClicked);
+                }
+                if response_3.double_clicked() {
+                    events.push(UiEvent::SaveButtonDoubleClicked);
+                }
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(320.0, 76.0),
+                                egui::vec2(200.0, 36.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.add_sized(
+                            egui::vec2(200.0, 36.0),
+                            egui::Button::new(
+                                format!("{}  {}", "🖼\u{fe0f}", "Button"),
+                            ),
+                        );
+                    },
+                );
+                let before_checked_5 = state.checked_5.clone();
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 132.0),
+                                egui::vec2(160.0, 28.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.checkbox(&mut state.checked_5, "Checkbox");
+                    },
+                );
+                if state.checked_5 != before_checked_5 {
+                    events.push(UiEvent::Checked5Changed);
+                }
+                let before_title = state.title.clone();
+                let response_6 = ui
+                    .scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    canvas.min + egui::vec2(320.0, 132.0),
+                                    egui::vec2(220.0, 36.0),
+                                ),
+                            ),
+                        |ui| {
+                            ui
+                                .add_sized(
+                                    egui::vec2(220.0, 36.0),
+                                    egui::TextEdit::singleline(&mut state.title)
+                                        .hint_text("Type here"),
+                                )
+                        },
+                    )
+                    .inner;
+                if response_6.lost_focus() {
+                    events.push(UiEvent::TitleLostFocus);
+                }
+                if state.title != before_title {
+                    events.push(UiEvent::TitleChanged);
+                }
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 188.0),
+                                egui::vec2(220.0, 24.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.add_sized(
+                            egui::vec2(220.0, 24.0),
+                            egui::Slider::new(&mut state.value_7, 0.000..=100.000)
+                                .text("Value"),
+                        );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(320.0, 188.0),
+                                egui::vec2(220.0, 20.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.add_sized(
+                            egui::vec2(220.0, 20.0),
+                            egui::ProgressBar::new(state.progress_8).show_percentage(),
+                        );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 244.0),
+                                egui::vec2(200.0, 80.0),
+                            ),
+                        ),
+                    |ui| {
+                        let items = vec![
+                            "Option A".to_string(), "Option B".to_string(), "Option C"
+                            .to_string()
+                        ];
+                        for (i, it) in items.iter().enumerate() {
+                            if ui
+                                .add(egui::RadioButton::new(state.sel_9 == i, it))
+                                .clicked()
+                            {
+                                state.sel_9 = i;
+                            }
+                        }
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(320.0, 244.0),
+                                egui::vec2(160.0, 20.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.add(egui::Link::new("Link text"));
+                    },
+                );
+                let response_11 = ui
+                    .scope_builder(
+                        egui::UiBuilder::new()
+                            .max_rect(
+                                egui::Rect::from_min_size(
+                                    canvas.min + egui::vec2(20.0, 300.0),
+                                    egui::vec2(200.0, 20.0),
+                                ),
+                            ),
+                        |ui| ui.hyperlink_to("Open website", "https://example.com"),
+                    )
+                    .inner;
+                if response_11.clicked() {
+                    events.push(UiEvent::Hyperlink11Clicked);
+                }
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(320.0, 300.0),
+                                egui::vec2(180.0, 24.0),
+                            ),
+                        ),
+                    |ui| {
+                        if ui
+                            .add(egui::Button::selectable(state.sel_12, "Selectable"))
+                            .clicked()
+                        {
+                            state.sel_12 = !state.sel_12;
+                        }
+                    },
+                );
+                let before_sel_13 = state.sel_13.clone();
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 356.0),
+                                egui::vec2(200.0, 28.0),
+                            ),
+                        ),
+                    |ui| {
+                        let items = vec![
+                            "Option A".to_string(), "Option B".to_string(), "Option C"
+                            .to_string()
+                        ];
+                        egui::ComboBox::from_id_salt(13)
+                            .width(200.0)
+                            .selected_text(
+                                items.get(state.sel_13).cloned().unwrap_or_default(),
+                            )
+                            .show_ui(
+                                ui,
+                                |ui| {
+                                    for (i, it) in items.iter().enumerate() {
+                                        ui.selectable_value(&mut state.sel_13, i, it.clone());
+                                    }
+                                },
+                            );
+                    },
+                );
+                if state.sel_13 != before_sel_13 {
+                    events.push(UiEvent::Sel13Changed);
+                }
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(320.0, 356.0),
+                                egui::vec2(200.0, 8.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.separator();
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 412.0),
+                                egui::vec2(220.0, 28.0),
+                            ),
+                        ),
+                    |ui| {
+                        egui::CollapsingHeader::new("Details")
+                            .default_open(state.open_15)
+                            .show(
+                                ui,
+                                |ui| {
+                                    ui.scope_builder(
+                                        egui::UiBuilder::new()
+                                            .max_rect(
+                                                egui::Rect::from_min_size(
+                                                    ui.min_rect().min + egui::vec2(0.0, 4.0),
+                                                    egui::vec2(200.0, 20.0),
+                                                ),
+                                            ),
+                                        |ui| {
+                                            ui.label("Only seen when open");
+                                        },
+                                    );
+                                },
+                            );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(320.0, 412.0),
+                                egui::vec2(200.0, 28.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Label");
+                            ui.add(DatePickerButton::new(&mut state.date_16));
+                        });
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 468.0),
+                                egui::vec2(200.0, 24.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.add_sized(
+                            egui::vec2(200.0, 24.0),
+                            egui::Slider::new(&mut state.angle_17, 0.000..=360.000)
+                                .suffix("°")
+                                .text("Angle"),
+                        );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(320.0, 468.0),
+                                egui::vec2(220.0, 36.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.add_sized(
+                            egui::vec2(220.0, 36.0),
+                            egui::TextEdit::singleline(&mut state.pass_18)
+                                .password(true)
+                                .hint_text("password"),
+                        );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 524.0),
+                                egui::vec2(240.0, 140.0),
+                            ),
+                        ),
+                    |ui| {
+                        let nodes: Vec<GenTreeNode> = vec![
+                            GenTreeNode { label : "Root".to_string(), children :
+                            vec![GenTreeNode { label : "Child".to_string(), children :
+                            vec![GenTreeNode { label : "Leaf".to_string(), children :
+                            vec![] }] }, GenTreeNode { label : "Sibling".to_string(),
+                            children : vec![] }] }, GenTreeNode { label : "Second root"
+                            .to_string(), children : vec![] }
+                        ];
+                        egui::ScrollArea::vertical()
+                            .auto_shrink([false, false])
+                            .show(
+                                ui,
+                                |ui| {
+                                    gen_show_tree(ui, &nodes);
+                                },
+                            );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(320.0, 524.0),
+                                egui::vec2(160.0, 120.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.add_sized(
+                            egui::vec2(160.0, 120.0),
+                            egui::Image::from_bytes(
+                                    "bytes://assets/logo.png",
+                                    include_bytes!(
+                                        concat!(env!("CARGO_MANIFEST_DIR"), "/assets/logo.png")
+                                    ),
+                                )
+                                .fit_to_exact_size(egui::vec2(160.0, 120.0))
+                                .tint(
+                                    egui::Color32::from_rgba_unmultiplied(255, 200, 200, 255),
+                                )
+                                .corner_radius(8),
+                        );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(380.0, 380.0),
+                                egui::vec2(300.0, 200.0),
+                            ),
+                        ),
+                    |ui| {
+                        egui_plot::Plot::new("plot_21")
+                            .width(300.0)
+                            .height(200.0)
+                            .x_axis_label("Day")
+                            .y_axis_label("Visitors")
+                            .legend(egui_plot::Legend::default())
+                            .show(
+                                ui,
+                                |plot_ui| {
+                                    plot_ui
+                                        .line(
+                                            egui_plot::Line::new(
+                                                "Visitors",
+                                                state.plot_21_visitors.clone(),
+                                            ),
+                                        );
+                                    plot_ui
+                                        .bar_chart(
+                                            egui_plot::BarChart::new(
+                                                "Sign-ups",
+                                                state
+                                                    .plot_21_sign_ups
+                                                    .iter()
+                                                    .map(|&[x, y]| egui_plot::Bar::new(x, y))
+                                                    .collect(),
+                                            ),
+                                        );
+                                    plot_ui
+                                        .points(
+                                            egui_plot::Points::new("", state.plot_21_series3.clone())
+                                                .radius(3.0),
+                                        );
+                                },
+                            );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(550.0, 20.0),
+                                egui::vec2(140.0, 300.0),
+                            ),
+                        ),
+                    |ui| {
+                        egui_extras::TableBuilder::new(ui)
+                            .id_salt("table_22")
+                            .striped(true)
+                            .auto_shrink(false)
+                            .max_scroll_height(280.0)
+                            .column(egui_extras::Column::auto().resizable(true))
+                            .column(egui_extras::Column::exact(40.0))
+                            .column(egui_extras::Column::initial(60.0).resizable(true))
+                            .column(egui_extras::Column::remainder())
+                            .header(
+                                20.0,
+                                |mut header| {
+                                    header
+                                        .col(|ui| {
+                                            ui.strong("Item");
+                                        });
+                                    header
+                                        .col(|ui| {
+                                            ui.with_layout(
+                                                egui::Layout::right_to_left(egui::Align::Center),
+                                                |ui| {
+                                                    ui.strong("Qty");
+                                                },
+                                            );
+                                        });
+                                    header
+                                        .col(|ui| {
+                                            ui.with_layout(
+                                                egui::Layout::right_to_left(egui::Align::Center),
+                                                |ui| {
+                                                    ui.strong("Price (€)");
+                                                },
+                                            );
+                                        });
+                                    header
+                                        .col(|ui| {
+                                            ui.with_layout(
+                                                egui::Layout::top_down(egui::Align::Center),
+                                                |ui| {
+                                                    ui.strong("In stock");
+                                                },
+                                            );
+                                        });
+                                },
+                            )
+                            .body(|body| {
+                                body.rows(
+                                    18.0,
+                                    state.table_22.len(),
+                                    |mut row| {
+                                        let item = &state.table_22[row.index()];
+                                        row.col(|ui| {
+                                            ui.label(&item.item);
+                                        });
+                                        row.col(|ui| {
+                                            ui.with_layout(
+                                                egui::Layout::right_to_left(egui::Align::Center),
+                                                |ui| {
+                                                    ui.label(item.qty.to_string());
+                                                },
+                                            );
+                                        });
+                                        row.col(|ui| {
+                                            ui.with_layout(
+                                                egui::Layout::right_to_left(egui::Align::Center),
+                                                |ui| {
+                                                    ui.label(item.price.to_string());
+                                                },
+                                            );
+                                        });
+                                        row.col(|ui| {
+                                            ui.with_layout(
+                                                egui::Layout::top_down(egui::Align::Center),
+                                                |ui| {
+                                                    ui.label(if item.in_stock { "✔" } else { "" });
+                                                },
+                                            );
+                                        });
+                                    },
+                                );
+                            });
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 680.0),
+                                egui::vec2(300.0, 100.0),
+                            ),
+                        ),
+                    |ui| {
+                        egui::Frame::group(ui.style())
+                            .show(
+                                ui,
+                                |ui| {
+                                    ui.set_min_size(ui.available_size());
+                                    ui.scope_builder(
+                                        egui::UiBuilder::new()
+                                            .max_rect(
+                                                egui::Rect::from_min_size(
+                                                    ui.min_rect().min + egui::vec2(8.0, 8.0),
+                                                    egui::vec2(200.0, 20.0),
+                                                ),
+                                            ),
+                                        |ui| {
+                                            ui.label("Inside a frame");
+                                        },
+                                    );
+                                    let response_26 = ui
+                                        .scope_builder(
+                                            egui::UiBuilder::new()
+                                                .max_rect(
+                                                    egui::Rect::from_min_size(
+                                                        ui.min_rect().min + egui::vec2(8.0, 40.0),
+                                                        egui::vec2(120.0, 28.0),
+                                                    ),
+                                                ),
+                                            |ui| {
+                                                ui
+                                                    .add_sized(
+                                                        egui::vec2(120.0, 28.0),
+                                                        egui::Button::new("Apply"),
+                                                    )
+                                            },
+                                        )
+                                        .inner;
+                                    if response_26.clicked() {
+                                        events.push(UiEvent::ApplyButtonClicked);
+                                    }
+                                },
+                            );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(340.0, 680.0),
+                                egui::vec2(340.0, 40.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.spacing_mut().item_spacing = egui::vec2(12.0, 4.0);
+                        ui.with_layout(
+                            egui::Layout::left_to_right(egui::Align::Center),
+                            |ui| {
+                                ui.allocate_ui(
+                                    egui::vec2(80.0, 28.0),
+                                    |ui| {
+                                        ui.add_sized(
+                                            egui::vec2(80.0, 28.0),
+                                            egui::Button::new("One"),
+                                        );
+                                    },
+                                );
+                                ui.allocate_ui(
+                                    egui::vec2(200.0, 24.0),
+                                    |ui| {
+                                        ui.add_sized(
+                                            egui::vec2(200.0, 24.0),
+                                            egui::Slider::new(&mut state.value_29, 0.000..=10.000)
+                                                .text("Level"),
+                                        );
+                                    },
+                                );
+                            },
+                        );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(20.0, 800.0),
+                                egui::vec2(300.0, 120.0),
+                            ),
+                        ),
+                    |ui| {
+                        egui::ScrollArea::both()
+                            .id_salt(30)
+                            .auto_shrink(false)
+                            .show(
+                                ui,
+                                |ui| {
+                                    ui.scope_builder(
+                                        egui::UiBuilder::new()
+                                            .max_rect(
+                                                egui::Rect::from_min_size(
+                                                    ui.min_rect().min + egui::vec2(0.0, 0.0),
+                                                    egui::vec2(260.0, 200.0),
+                                                ),
+                                            ),
+                                        |ui| {
+                                            ui.vertical(|ui| {
+                                                ui.allocate_ui(
+                                                    egui::vec2(240.0, 20.0),
+                                                    |ui| {
+                                                        ui.label("Scroll down");
+                                                    },
+                                                );
+                                                let response_33 = ui
+                                                    .allocate_ui(
+                                                        egui::vec2(240.0, 120.0),
+                                                        |ui| {
+                                                            ui
+                                                                .add_sized(
+                                                                    egui::vec2(240.0, 120.0),
+                                                                    egui::TextEdit::singleline(&mut state.notes)
+                                                                        .hint_text("Notes"),
+                                                                )
+                                                        },
+                                                    )
+                                                    .inner;
+                                                if response_33.lost_focus() {
+                                                    events.push(UiEvent::NotesLostFocus);
+                                                }
+                                            });
+                                        },
+                                    );
+                                },
+                            );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(340.0, 740.0),
+                                egui::vec2(340.0, 80.0),
+                            ),
+                        ),
+                    |ui| {
+                        ui.spacing_mut().item_spacing = egui::vec2(16.0, 6.0);
+                        egui::Grid::new(34)
+                            .num_columns(2)
+                            .striped(true)
+                            .show(
+                                ui,
+                                |ui| {
+                                    ui.allocate_ui(
+                                        egui::vec2(100.0, 20.0),
+                                        |ui| {
+                                            ui.label("User");
+                                        },
+                                    );
+                                    ui.allocate_ui(
+                                        egui::vec2(200.0, 24.0),
+                                        |ui| {
+                                            ui.add_sized(
+                                                egui::vec2(200.0, 24.0),
+                                                egui::TextEdit::singleline(&mut state.user)
+                                                    .hint_text("guest"),
+                                            );
+                                        },
+                                    );
+                                    ui.end_row();
+                                    ui.allocate_ui(
+                                        egui::vec2(100.0, 20.0),
+                                        |ui| {
+                                            ui.label("Remember me");
+                                        },
+                                    );
+                                    ui.allocate_ui(
+                                        egui::vec2(120.0, 24.0),
+                                        |ui| {
+                                            ui.checkbox(&mut state.checked_38, "");
+                                        },
+                                    );
+                                    ui.end_row();
+                                },
+                            );
+                    },
+                );
+                ui.scope_builder(
+                    egui::UiBuilder::new()
+                        .max_rect(
+                            egui::Rect::from_min_size(
+                                canvas.min + egui::vec2(340.0, 840.0),
+                                egui::vec2(340.0, 60.0),
+                            ),
+                        ),
+                    |ui| {
+                        egui_extras::StripBuilder::new(ui)
+                            .sizes(egui_extras::Size::remainder(), 2)
+                            .horizontal(|mut strip| {
+                                strip
+                                    .cell(|ui| {
+                                        ui.allocate_ui(
+                                            egui::vec2(80.0, 28.0),
+                                            |ui| {
+                                                ui.add_sized(
+                                                    egui::vec2(80.0, 28.0),
+                                                    egui::Button::new("Cancel"),
+                                                );
+                                            },
+                                        );
+                                    });
+                                strip
+                                    .cell(|ui| {
+                                        let response_41 = ui
+                                            .allocate_ui(
+                                                egui::vec2(80.0, 28.0),
+                                                |ui| {
+                                                    ui
+                                                        .add_sized(egui::vec2(80.0, 28.0), egui::Button::new("OK"))
+                                                },
+                                            )
+                                            .inner;
+                                        if response_41.clicked() {
+                                            events.push(UiEvent::OkClicked);
+                                        }
+                                    });
+                            });
+                    },
+                );
+            },
+        );
+    events
+}
+
+// rad:user-begin items
+// rad:user-end
+
+pub struct GeneratedApp {
+    state: GeneratedState,
+}
+
+impl Default for GeneratedApp {
+    fn default() -> Self {
+        Self { state: Default::default() }
+    }
+}
+
+impl eframe::App for GeneratedApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        for event in generated_ui(ctx, &mut self.state) {
+            self.state.handle_event(event);
+        }
+    }
+}
+
+fn main() -> eframe::Result<()> {
+    let native_options = eframe::NativeOptions::default();
+    eframe::run_native(
+        "Generated UI",
+        native_options,
+        Box::new(|cc| {
+            egui_extras::install_image_loaders(&cc.egui_ctx);
+            Ok(Box::new(GeneratedApp::default()))
+        }),
+    )
+}
@@ -0,0 +1,172 @@
+//! Whatever users type into labels, items, urls and icons ends up in valid string
+//! literals of the generated code, and reads back the same.  Hostile numbers are rejected
+//! instead of panicking the generator.
+
+use egui::{pos2, vec2};
+use egui_rad_builder::{
+    Asset, ColumnType, DockArea, OutputMode, PlotSeries, Project, SeriesStyle, TableColumn, Widget, WidgetId, WidgetKind,
+    WidgetProps, export, generate, import,
+};
+use syn::visit::{self, Visit};
+
+const HOSTILE: &[&str] = &[
+    "\"quoted\"",
+    "back\\slash",
+    "trailing backslash\\",
+    "new\nline",
+    "tab\tstop",
+    "carriage\rreturn",
+    "{} {0} {{braces}} {name}",
+    "\"); std::process::exit(1); (\"",
+    "*/ /* // not a comment",
+    "// rad:user-begin items",
+    "nul\0byte",
+    "bell\u{7} escape\u{1b}[0m",
+    "émoji ★ 🖼️ \u{200b}",
+    "r#\"raw\"#",
+    "#[attr] __rad_region!(items);",
+    "",
+];
+
+/// One widget of every kind with `s` in all of its strings
+fn project(s: &str) -> Project {
+    let widgets = WidgetKind::ALL
+        .iter()
+        .zip(1..)
+        .map(|(kind, id)| Widget {
+            id: WidgetId::new(id),
+            kind: kind.clone(),
+            pos: pos2(10.0, 10.0 * id as f32),
+            size: vec2(120.0, 24.0),
+            z: id as i32,
+            area: DockArea::Center,
+            parent: None,
+            props: WidgetProps {
+                text: s.into(),
+                items: vec![s.into(), "plain".into()],
+                url: s.into(),
+                icon: s.into(),
+                image: if *kind == WidgetKind::Image { "logo.png".into() } else { String::new() },
+                series: vec![PlotSeries::sample(s, SeriesStyle::Line), PlotSeries::sample("plain", SeriesStyle::Bar)],
+                x_label: s.into(),
+                y_label: s.into(),
+                columns: vec![
+                    TableColumn { header: s.into(), ..TableColumn::default() },
+                    TableColumn { header: "plain".into(), ty: ColumnType::Integer, ..TableColumn::default() },
+                ],
+                rows: vec![vec![s.into(), "1".into()]],
+                sortable: true,
+                ..WidgetProps::default()
+            },
+            name: String::new(),
+            events: Vec::new(),
+        })
+        .collect();
+    let assets = vec![Asset { name: "logo.png".into(), path: "tests/fixtures/assets/logo.png".into() }];
+    Project { widgets, assets, ..Project::default() }
+}
+
+/// Values of every string literal in `code`, which must be valid Rust
+fn string_literals(code: &str) -> Vec<String> {
+    struct Strings(Vec<String>);
+    impl Visit<'_> for Strings {
+        fn visit_lit_str(&mut self, lit: &syn::LitStr) {
+            self.0.push(lit.value());
+        }
+        fn visit_macro(&mut self, mac: &syn::Macro) {
+            // vec! and format! arguments
+            let args = mac.parse_body_with(
+                syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated,
+            );
+            for arg in args.iter().flatten() {
+                visit::visit_expr(self, arg);
+            }
+        }
+    }
+    let file = syn::parse_file(code).unwrap_or_else(|e| panic!("invalid Rust ({e}):\n{code}"));
+    let mut strings = Strings(Vec::new());
+    strings.visit_file(&file);
+    strings.0
+}
+
+#[test]
+fn hostile_strings_become_valid_literals() {
+    for s in HOSTILE {
+        for output in OutputMode::ALL {
+            let mut project = project(s);
+            project.codegen.output = output;
+            let code = generate(&project).unwrap();
+            let literals = string_literals(&code);
+            let count = literals.iter().filter(|l| l == s).count();
+            // text of 14 kinds (password as initial value, text edit also as hint),
+            // items of 3, the url, the icon, the plot series and its axis labels unless blank,
+            // the table header and cell
+            let labels = if s.is_empty() { 0 } else { 2 };
+            assert!(count >= 14 + 1 + 3 + 2 + 1 + labels + 2, "{s:?} only found {count} times in:\n{code}");
+            if !s.trim().is_empty() {
+                assert!(literals.iter().any(|l| l == s.trim()), "tree label {s:?} missing");
+            }
+        }
+    }
+}
+
+#[test]
+fn hostile_strings_in_exported_projects() {
+    for s in HOSTILE {
+        let files = export::cargo_project(&project(s), "hostile").unwrap();
+        for (path, contents) in files {
+            if path.ends_with(".rs") {
+                string_literals(&contents);
+            }
+        }
+    }
+}
+
+#[test]
+fn hostile_strings_import_unchanged() {
+    for s in HOSTILE {
+        let project = project(s);
+        let imported = import::from_rust(&generate(&project).unwrap()).unwrap();
+        assert!(imported.unrecognized.is_empty(), "{:?}", imported.unrecognized);
+        for (before, after) in project.widgets.iter().zip(&imported.project.widgets) {
+            assert_eq!(before.kind, after.kind);
+            if before.kind.has_text() {
+                assert_eq!(after.props.text, before.props.text, "{:?}", before.kind);
+            }
+            match before.kind {
+                WidgetKind::MenuButton | WidgetKind::RadioGroup | WidgetKind::ComboBox => {
+                    assert_eq!(after.props.items, before.props.items, "{:?}", before.kind);
+                }
+                WidgetKind::Plot => {
+                    assert_eq!(after.props.series, before.props.series);
+                    assert_eq!((&after.props.x_label, &after.props.y_label), (&before.props.x_label, &before.props.y_label));
+                }
+                WidgetKind::Table => {
+                    assert_eq!(after.props.columns, before.props.columns);
+                    assert_eq!(after.props.rows, before.props.rows);
+                    assert!(after.props.sortable);
+                }
+                WidgetKind::Tree => {
+                    // tree items are trimmed lines
+                    let labels: Vec<&str> = before.props.items.iter().map(|i| i.trim()).filter(|i| !i.is_empty()).collect();
+                    assert_eq!(after.props.items, labels);
+                }
+                _ => {}
+            }
+        }
+        let hyperlink = imported.project.widgets.iter().find(|w| w.kind == WidgetKind::Hyperlink).unwrap();
+        assert_eq!(hyperlink.props.url, *s);
+        let icon = imported.project.widgets.iter().find(|w| w.kind == WidgetKind::ImageTextButton).unwrap();
+        assert_eq!(icon.props.icon, *s);
+    }
+}
+
+#[test]
+fn hostile_canvas_sizes_are_rejected() {
+    let huge = Project::from_json(r#"{"widgets": [], "canvas_size": [1e300, 100]}"#).unwrap();
+    assert!(generate(&huge).is_err());
+    for size in [vec2(f32::NAN, 100.0), vec2(100.0, f32::INFINITY), vec2(0.0, 100.0), vec2(100.0, -1.0)] {
+        let project = Project { canvas_size: size, ..project("plain") };
+        assert!(generate(&project).is_err(), "{size:?} was accepted");
+    }
+}
@@ -0,0 +1,46 @@
+//! Designs come back from their generated code: generating the imported design gives
+//! the same code again, and what the importer doesn't understand is reported.
+
+use egui_rad_builder::{Project, generate, import};
+use std::{fs, path::Path};
+
+#[test]
+fn fixtures_round_trip_through_generated_code() {
+    let mut paths: Vec<_> = fs::read_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+    for path in paths {
+        let project = Project::load(&path).unwrap();
+        let code = generate(&project).unwrap();
+        let imported = import::from_rust(&code).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        assert!(imported.unrecognized.is_empty(), "{}: {:?}", path.display(), imported.unrecognized);
+        let again = generate(&imported.project).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+        assert!(again == code, "{} changed on the way back:\n{again}", path.display());
+    }
+}
+
+#[test]
+fn unknown_code_is_reported_with_its_line() {
+    let project = Project::from_json(
+        r#"{"widgets": [{"id": 1, "kind": {"t": "Label"}, "pos": [20, 20], "size": [120, 20], "z": 1,
+            "area": "Center", "props": {"text": "Hello"}}]}"#,
+    )
+    .unwrap();
+    let code = generate(&project).unwrap();
+    // between the widgets of the central panel
+    let at = code.find("ui.scope_builder(").unwrap();
+    let line_start = code[..at].rfind('\n').unwrap() + 1;
+    let line = code[..line_start].lines().count() + 1;
+    let edited = format!("{}{}custom_widget(ui, 42);\n{}", &code[..line_start], &code[line_start..at], &code[line_start..]);
+
+    let imported = import::from_rust(&edited).unwrap();
+    assert_eq!(
+        imported.unrecognized,
+        vec![import::Unrecognized { line, code: "custom_widget(ui, 42);".into() }]
+    );
+    assert_eq!(imported.project.widgets.len(), 1);
+}
@@ -0,0 +1,56 @@
+//! Saved projects of older format versions are upgraded through the migrations, newer
+//! ones are refused.
+
+use egui_rad_builder::{DockArea, FORMAT_VERSION, Project, ProjectError, WidgetKind, generate};
+use serde_json::{Value, json};
+
+/// A design exported by the builder before the format was versioned
+fn baseline() -> Value {
+    let json = include_str!("formats/v0_baseline.json");
+    serde_json::from_str(json).unwrap()
+}
+
+#[test]
+fn unversioned_projects_upgrade_to_the_current_format() {
+    let project = Project::from_json(&baseline().to_string()).unwrap();
+    assert_eq!(project.widgets.len(), 19);
+    assert!(project.panel_top_enabled && project.panel_left_enabled && !project.panel_right_enabled);
+    let menu = &project.widgets[0];
+    assert_eq!(menu.kind, WidgetKind::MenuButton);
+    assert_eq!(menu.area, DockArea::Top);
+    assert_eq!(menu.props.items, ["First", "Second", "Third"]);
+    assert!(project.widgets.iter().all(|w| w.name.is_empty() && w.events.is_empty()));
+    generate(&project).unwrap();
+
+    let saved: Value = serde_json::from_str(&project.to_json().unwrap()).unwrap();
+    assert_eq!(saved["format_version"], json!(FORMAT_VERSION));
+    assert_eq!(Project::from_json(&saved.to_string()).unwrap(), project);
+}
+
+#[test]
+fn every_older_version_upgrades() {
+    let unversioned = Project::from_json(&baseline().to_string()).unwrap();
+    for version in 1..=FORMAT_VERSION {
+        let mut doc = baseline();
+        doc["format_version"] = json!(version);
+        let project = Project::from_json(&doc.to_string()).unwrap_or_else(|e| panic!("version {version}: {e}"));
+        assert_eq!(project, unversioned, "version {version}");
+    }
+}
+
+#[test]
+fn unversioned_documents_must_be_objects() {
+    let result = Project::from_json("[]");
+    assert!(matches!(result, Err(ProjectError::Migration { from: 0, .. })), "{result:?}");
+}
+
+#[test]
+fn newer_formats_are_rejected() {
+    let mut doc = baseline();
+    doc["format_version"] = json!(FORMAT_VERSION + 1);
+    let result = Project::from_json(&doc.to_string());
+    assert!(
+        matches!(result, Err(ProjectError::TooNew { found }) if found == FORMAT_VERSION + 1),
+        "{result:?}"
+    );
+}
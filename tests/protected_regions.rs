@@ -0,0 +1,68 @@
+//! Code written into the protected regions of a generated file is kept when the file
+//! is regenerated.
+
+use egui_rad_builder::{Project, codegen::regions, generate};
+use std::path::PathBuf;
+
+/// `code` with `lines` added at the start of region `name`, indented like its markers
+fn fill(code: &str, name: &str, lines: &[&str]) -> String {
+    let marker = format!("// rad:user-begin {name}\n");
+    let at = code.find(&marker).unwrap_or_else(|| panic!("no region {name}")) + marker.len();
+    let line_start = code[..at - marker.len()].rfind('\n').map_or(0, |i| i + 1);
+    let indent = &code[line_start..at - marker.len()];
+    let body: String = lines.iter().map(|l| format!("{indent}{l}\n")).collect();
+    format!("{}{body}{}", &code[..at], &code[at..])
+}
+
+fn temp_file(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rad-{name}-{}.rs", std::process::id()))
+}
+
+/// A form with a save button reporting clicks
+fn design() -> Project {
+    Project::from_json(
+        r#"{
+            "widgets": [
+                {"id": 1, "kind": {"t": "Label"}, "pos": [20, 20], "size": [120, 20], "z": 1, "area": "Center",
+                 "props": {"text": "Name"}},
+                {"id": 2, "kind": {"t": "TextEdit"}, "pos": [150, 20], "size": [200, 24], "z": 2, "area": "Center",
+                 "name": "name"},
+                {"id": 3, "kind": {"t": "Button"}, "pos": [20, 60], "size": [120, 28], "z": 3, "area": "Center",
+                 "props": {"text": "Save"}, "name": "save_button", "events": ["Clicked"]}
+            ]
+        }"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn hand_written_code_survives_regeneration() {
+    let project = design();
+    let generated = generate(&project).unwrap();
+    let edited = fill(&generated, "state_fields", &["saves: u32,"]);
+    let edited = fill(&edited, "state_defaults", &["saves: 0,"]);
+    let edited = fill(&edited, "on_save_button_clicked", &["self.saves += 1;", "println!(\"saved {} times\", self.saves);"]);
+    let edited = fill(&edited, "items", &["fn helper() {}"]);
+
+    let path = temp_file("kept");
+    std::fs::write(&path, &edited).unwrap();
+    let regenerated = regions::merge_with_file(&generate(&project).unwrap(), &path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(regenerated.unwrap(), edited);
+}
+
+#[test]
+fn handlers_of_deleted_widgets_are_kept_in_a_comment() {
+    let mut project = design();
+    let edited = fill(&generate(&project).unwrap(), "on_save_button_clicked", &["self.save();"]);
+    project.widgets.retain(|w| w.name != "save_button");
+
+    let path = temp_file("orphaned");
+    std::fs::write(&path, &edited).unwrap();
+    let regenerated = regions::merge_with_file(&generate(&project).unwrap(), &path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(!regenerated.contains("SaveButtonClicked"));
+    let orphaned = &regenerated[regenerated.rfind("/*").unwrap()..];
+    assert!(orphaned.contains("// rad:user-begin on_save_button_clicked\n                self.save();\n"), "{orphaned}");
+}